@@ -0,0 +1,12 @@
+-- Add down migration script here
+
+drop table if exists app_setting;
+
+alter table tracker_entry_line_duration
+    drop column deleted_at;
+
+alter table tracker_entry_line
+    drop column deleted_at;
+
+alter table tracker_entry
+    drop column deleted_at;
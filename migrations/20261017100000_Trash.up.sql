@@ -0,0 +1,38 @@
+-- Add up migration script here
+
+alter table tracker_entry
+    add column deleted_at datetime;
+
+alter table tracker_entry_line
+    add column deleted_at datetime;
+
+alter table tracker_entry_line_duration
+    add column deleted_at datetime;
+
+-- Backfill deletion times, children share the timestamp of the parent that removed them
+update tracker_entry
+    set deleted_at = updated_at
+    where is_deleted = true;
+
+update tracker_entry_line
+    set deleted_at = coalesce(
+        (select e.deleted_at from tracker_entry e where e.id = entry_id and e.is_deleted = true),
+        updated_at
+    )
+    where is_deleted = true;
+
+-- Durations of deleted lines used to stay live
+update tracker_entry_line_duration
+    set is_deleted = true,
+        deleted_at = (select l.deleted_at from tracker_entry_line l where l.id = entry_line_id)
+    where entry_line_id in (select id from tracker_entry_line where is_deleted = true);
+
+update tracker_entry_line_duration
+    set deleted_at = updated_at
+    where is_deleted = true and deleted_at is null;
+
+create table if not exists app_setting (
+    key text primary key,
+    value text not null,
+    updated_at datetime default current_timestamp
+);
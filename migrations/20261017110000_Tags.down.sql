@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+drop index if exists idx_tracker_entry_line_tag_tag_id;
+
+drop table if exists tracker_entry_line_tag;
+
+drop table if exists tag;
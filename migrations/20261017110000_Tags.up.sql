@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+create table if not exists tag (
+    id integer primary key autoincrement,
+    name text not null collate nocase unique,
+    created_at datetime default current_timestamp,
+    updated_at datetime default current_timestamp
+);
+
+-- Links disappear together with their line or tag
+create table if not exists tracker_entry_line_tag (
+    line_id integer not null references tracker_entry_line(id) on delete cascade,
+    tag_id integer not null references tag(id) on delete cascade,
+    created_at datetime default current_timestamp,
+    primary key (line_id, tag_id)
+);
+
+create index if not exists idx_tracker_entry_line_tag_tag_id
+    on tracker_entry_line_tag (tag_id);
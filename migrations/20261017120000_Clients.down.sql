@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+drop index if exists idx_tracker_entry_client_id;
+
+alter table tracker_entry
+    drop column client_id;
+
+drop table if exists client;
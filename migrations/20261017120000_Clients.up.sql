@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+create table if not exists client (
+    id integer primary key autoincrement,
+    name text not null,
+    is_default boolean not null default false,
+    created_at datetime default current_timestamp,
+    updated_at datetime default current_timestamp,
+    is_deleted boolean default false,
+    deleted_at datetime
+);
+
+-- Trackers without a client of their own belong to the default client
+insert into client (name, is_default)
+    values ('Unassigned', true);
+
+alter table tracker_entry
+    add column client_id integer references client(id);
+
+update tracker_entry
+    set client_id = (select id from client where is_default = true);
+
+create index if not exists idx_tracker_entry_client_id
+    on tracker_entry (client_id);
@@ -0,0 +1,19 @@
+-- Add down migration script here
+
+alter table tracker_entry_line
+    drop column is_billable;
+
+alter table tracker_entry_line
+    drop column hourly_rate;
+
+alter table tracker_entry
+    drop column currency;
+
+alter table tracker_entry
+    drop column hourly_rate;
+
+alter table client
+    drop column currency;
+
+alter table client
+    drop column hourly_rate;
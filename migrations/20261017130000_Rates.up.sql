@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Rates are decimal strings so amounts can be computed exactly
+alter table client
+    add column hourly_rate text;
+
+alter table client
+    add column currency text;
+
+alter table tracker_entry
+    add column hourly_rate text;
+
+alter table tracker_entry
+    add column currency text;
+
+-- A line only overrides the rate, its currency comes from the tracker or the client
+alter table tracker_entry_line
+    add column hourly_rate text;
+
+alter table tracker_entry_line
+    add column is_billable boolean not null default true;
@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+drop index if exists idx_tracker_entry_line_duration_invoice_line_id;
+
+alter table tracker_entry_line_duration
+    drop column invoice_line_id;
+
+drop table if exists invoice_line;
+drop table if exists invoice;
@@ -0,0 +1,37 @@
+-- Add up migration script here
+
+-- Invoices copy everything they show, so later renames or rate changes do not alter them
+create table if not exists invoice (
+    id integer primary key autoincrement,
+    number text not null unique,
+    client_id integer not null references client(id),
+    client_name text not null,
+    period_from date not null,
+    period_to date not null,
+    currency text not null,
+    total text not null,
+    issued_at datetime not null,
+    created_at datetime default current_timestamp,
+    updated_at datetime default current_timestamp
+);
+
+create table if not exists invoice_line (
+    id integer primary key autoincrement,
+    invoice_id integer not null references invoice(id) on delete cascade,
+    position integer not null,
+    line_id integer references tracker_entry_line(id) on delete set null,
+    tracker_label text not null,
+    line_desc text not null,
+    total_seconds integer not null,
+    hourly_rate text not null,
+    amount text not null,
+    created_at datetime default current_timestamp,
+    unique (invoice_id, position)
+);
+
+-- An interval is invoiced once it points at the invoice line that billed it
+alter table tracker_entry_line_duration
+    add column invoice_line_id integer references invoice_line(id);
+
+create index if not exists idx_tracker_entry_line_duration_invoice_line_id
+    on tracker_entry_line_duration (invoice_line_id);
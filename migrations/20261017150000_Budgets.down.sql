@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+alter table tracker_entry_line
+    drop column estimate_seconds;
+
+alter table tracker_entry
+    drop column budget_period;
+
+alter table tracker_entry
+    drop column budget_seconds;
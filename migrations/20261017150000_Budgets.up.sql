@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Budgets and estimates are stored in seconds like the durations they are compared with
+alter table tracker_entry
+    add column budget_seconds integer;
+
+-- 'one_off', 'weekly' or 'monthly', set whenever budget_seconds is
+alter table tracker_entry
+    add column budget_period text;
+
+alter table tracker_entry_line
+    add column estimate_seconds integer;
@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+alter table tracker_entry
+    drop column archived_at;
+
+alter table tracker_entry
+    drop column is_archived;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+
+-- Archived trackers leave the tracker list but, unlike deleted ones, still count in reports
+alter table tracker_entry
+    add column is_archived boolean not null default false;
+
+alter table tracker_entry
+    add column archived_at datetime;
@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+alter table tracker_entry
+    drop column color;
+
+alter table tracker_entry
+    drop column is_pinned;
+
+alter table tracker_entry
+    drop column sort_order;
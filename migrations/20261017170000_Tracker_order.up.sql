@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+alter table tracker_entry
+    add column sort_order integer not null default 0;
+
+alter table tracker_entry
+    add column is_pinned boolean not null default false;
+
+-- '#rrggbb', or null for the default colour
+alter table tracker_entry
+    add column color text;
+
+-- Start the manual order from the newest-first order the list used so far
+update tracker_entry
+    set sort_order = (
+        select count(*)
+        from tracker_entry other
+        where other.created_at > tracker_entry.created_at
+            or (other.created_at = tracker_entry.created_at and other.id > tracker_entry.id)
+    );
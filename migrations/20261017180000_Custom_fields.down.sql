@@ -0,0 +1,13 @@
+-- Add down migration script here
+
+drop index if exists idx_custom_field_value_line_id;
+
+drop index if exists idx_custom_field_value_entry_id;
+
+drop index if exists idx_custom_field_value_line;
+
+drop index if exists idx_custom_field_value_entry;
+
+drop table if exists custom_field_value;
+
+drop table if exists custom_field;
@@ -0,0 +1,36 @@
+-- Add up migration script here
+
+create table if not exists custom_field (
+    id integer primary key autoincrement,
+    name text not null collate nocase unique,
+    field_type text not null,
+    scope text not null,
+    -- JSON array with the allowed values of an enum field, null for every other type
+    options text,
+    created_at datetime default current_timestamp,
+    updated_at datetime default current_timestamp
+);
+
+-- A value belongs to a tracker or to a line, depending on the scope of its field
+create table if not exists custom_field_value (
+    id integer primary key autoincrement,
+    field_id integer not null references custom_field(id) on delete cascade,
+    entry_id integer references tracker_entry(id) on delete cascade,
+    line_id integer references tracker_entry_line(id) on delete cascade,
+    value text not null,
+    created_at datetime default current_timestamp,
+    updated_at datetime default current_timestamp,
+    check ((entry_id is null) <> (line_id is null))
+);
+
+create unique index if not exists idx_custom_field_value_entry
+    on custom_field_value (field_id, entry_id) where entry_id is not null;
+
+create unique index if not exists idx_custom_field_value_line
+    on custom_field_value (field_id, line_id) where line_id is not null;
+
+create index if not exists idx_custom_field_value_entry_id
+    on custom_field_value (entry_id);
+
+create index if not exists idx_custom_field_value_line_id
+    on custom_field_value (line_id);
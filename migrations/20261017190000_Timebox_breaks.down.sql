@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+drop index if exists idx_timebox_break_line_id;
+
+drop table if exists timebox_break;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+-- Breaks taken during a timebox, kept apart from the tracked intervals of the line
+create table if not exists timebox_break (
+    id integer primary key autoincrement,
+    line_id integer not null references tracker_entry_line(id) on delete cascade,
+    phase text not null,
+    started_at datetime not null,
+    ended_at datetime,
+    -- When the break was due to end, a break left open by a crash is closed there
+    planned_ended_at datetime not null,
+    created_at datetime default current_timestamp
+);
+
+create index if not exists idx_timebox_break_line_id
+    on timebox_break (line_id);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+drop index if exists idx_forced_stop_stopped_at;
+
+drop table if exists forced_stop;
@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+-- Intervals the scheduler stopped on its own, kept so they can be reviewed later
+create table if not exists forced_stop (
+    id integer primary key autoincrement,
+    duration_id integer not null references tracker_entry_line_duration(id) on delete cascade,
+    reason text not null,
+    stopped_at datetime not null,
+    created_at datetime default current_timestamp
+);
+
+create index if not exists idx_forced_stop_stopped_at
+    on forced_stop (stopped_at);
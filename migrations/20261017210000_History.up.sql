@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+-- Changes to trackers that can be undone, each holding the rows it touched before and after
+create table if not exists history_entry (
+    id integer primary key autoincrement,
+    label text not null,
+    changes text not null,
+    is_undone boolean not null default false,
+    created_at datetime default current_timestamp
+);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+-- Nothing to undo, the paired rates are valid under the old rules as well
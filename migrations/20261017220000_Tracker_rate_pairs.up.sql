@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+-- A tracker's rate and currency are now set together and billed together. Rates that
+-- relied on the client's currency take it over. Rates with no currency anywhere keep
+-- their value under the ISO 4217 code for no currency, XXX, until the user picks one.
+-- Currencies without a rate are left as they are, they bill nothing on their own and
+-- are replaced the next time a rate is set.
+update tracker_entry
+set currency = (select c.currency from client c where c.id = tracker_entry.client_id)
+where hourly_rate is not null and currency is null;
+
+update tracker_entry
+set currency = 'XXX'
+where hourly_rate is not null and currency is null;
@@ -0,0 +1,127 @@
+//! Measures `TrackerService::get_trackers` against databases of growing size.
+//!
+//! Run with `cargo bench --bench get_trackers`. Besides the timings it prints
+//! the number of SQL statements issued, which must not grow with the history.
+
+use sqlx::SqlitePool;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+use track_it_lib::{
+    database,
+    domains::tracker::{TrackerQueryDto, TrackerService, TrackerServiceTrait},
+};
+
+const SIZES: [i64; 3] = [10, 100, 1000];
+const LINES_PER_ENTRY: i64 = 5;
+const DURATIONS_PER_LINE: i64 = 4;
+const ITERATIONS: u32 = 10;
+
+/// Counts the statements sqlx logs under its `sqlx::query` target.
+struct QueryCounter {
+    count: AtomicUsize,
+}
+
+impl log::Log for QueryCounter {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("sqlx::query")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static COUNTER: QueryCounter = QueryCounter {
+    count: AtomicUsize::new(0),
+};
+
+async fn seed(pool: &SqlitePool, entries: i64) -> sqlx::Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for entry in 0..entries {
+        let entry_id: i64 =
+            sqlx::query_scalar("INSERT INTO tracker_entry (label) VALUES (?) RETURNING id")
+                .bind(format!("Tracker {}", entry))
+                .fetch_one(&mut *tx)
+                .await?;
+
+        for line in 0..LINES_PER_ENTRY {
+            let line_id: i64 = sqlx::query_scalar(
+                "INSERT INTO tracker_entry_line (entry_id, desc) VALUES (?, ?) RETURNING id",
+            )
+            .bind(entry_id)
+            .bind(format!("Line {}", line))
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for _ in 0..DURATIONS_PER_LINE {
+                sqlx::query(
+                    "INSERT INTO tracker_entry_line_duration (entry_line_id, ended_at) VALUES (?, current_timestamp)",
+                )
+                .bind(line_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    tx.commit().await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    log::set_logger(&COUNTER).map_err(|e| e.to_string())?;
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let bench_dir = std::env::temp_dir().join(format!("track-it-bench-{}", std::process::id()));
+    std::fs::create_dir_all(&bench_dir)?;
+
+    let mut baseline_queries = None;
+
+    for size in SIZES {
+        let pool = database::connect_database(&bench_dir.join(format!("{}.db", size))).await?;
+        seed(&pool, size).await?;
+
+        let service = TrackerService::create_service(pool.clone());
+
+        COUNTER.count.store(0, Ordering::SeqCst);
+        let started = Instant::now();
+        for _ in 0..ITERATIONS {
+            service.get_trackers(TrackerQueryDto::default()).await?;
+        }
+        let elapsed = started.elapsed() / ITERATIONS;
+        let queries = COUNTER.count.load(Ordering::SeqCst) / ITERATIONS as usize;
+
+        println!(
+            "get_trackers: {:>5} entries, {:>6} durations: {:>10.2?}/iter, {} queries/iter",
+            size,
+            size * LINES_PER_ENTRY * DURATIONS_PER_LINE,
+            elapsed,
+            queries
+        );
+
+        pool.close().await;
+
+        match baseline_queries {
+            None => baseline_queries = Some(queries),
+            Some(baseline) if baseline != queries => {
+                return Err(format!(
+                    "query count grew with the data: {} queries for {} entries, expected {}",
+                    queries, size, baseline
+                )
+                .into());
+            }
+            Some(_) => {}
+        }
+    }
+
+    std::fs::remove_dir_all(&bench_dir)?;
+
+    Ok(())
+}
@@ -1,37 +1,109 @@
 use crate::database;
+use crate::domains::backup::{
+    BACKUP_POLL_INTERVAL, BackupDto, BackupReason, BackupService, BackupServiceTrait,
+};
+use crate::domains::billing::{
+    BillingService, BillingServiceTrait, ClientRateDto, ClientRateUpdateDto, LineBillingDto,
+    LineBillingUpdateDto, TrackerRateDto, TrackerRateUpdateDto,
+};
+use crate::domains::budget::{
+    BUDGET_EXCEEDED_EVENT, BudgetPeriod, BudgetService, BudgetServiceTrait, LineEstimateDto,
+    LineEstimateUpdateDto, TrackerBudgetDto, TrackerBudgetUpdateDto,
+};
+use crate::domains::client::{
+    ClientCreateDto, ClientDeleteDto, ClientService, ClientServiceTrait, ClientTrackersDto,
+    ClientUpdateDto, ClientViewDto,
+};
+use crate::domains::custom_field::{
+    CustomFieldCreateDto, CustomFieldDeleteDto, CustomFieldScope, CustomFieldService,
+    CustomFieldServiceTrait, CustomFieldType, CustomFieldUpdateDto, CustomFieldValueUpdateDto,
+    CustomFieldValueViewDto, CustomFieldViewDto,
+};
+use crate::domains::export::{ExportFormat, ExportQueryDto, ExportService, ExportServiceTrait};
+use crate::domains::history::{HistoryEntryDto, HistoryService, HistoryServiceTrait};
+use crate::domains::idle::{
+    IDLE_DETECTED_EVENT, IDLE_POLL_INTERVAL, IdlePeriodDto, IdleResolution, IdleService,
+    IdleServiceTrait,
+};
+use crate::domains::import::{ImportReportDto, ImportService, ImportServiceTrait};
+use crate::domains::invoice::{
+    InvoiceCreateDto, InvoiceFormat, InvoiceService, InvoiceServiceTrait, InvoiceViewDto,
+};
+use crate::domains::recovery::{
+    HEARTBEAT_INTERVAL, RecoveryCaseDto, RecoveryPolicy, RecoveryResolution, RecoveryResolveDto,
+    RecoveryService, RecoveryServiceTrait,
+};
+use crate::domains::report::{
+    ReportDto, ReportPeriod, ReportPeriodTotalDto, ReportQueryDto, ReportService,
+    ReportServiceTrait, ReportTagTotalDto, ReportTrackerTotalDto,
+};
+use crate::domains::schedule::{
+    FORCED_STOP_EVENT, ForcedStopDto, SCHEDULE_POLL_INTERVAL, ScheduleService,
+    ScheduleServiceTrait, StopRules,
+};
+use crate::domains::tag::{
+    LineTagsUpdateDto, TagCreateDto, TagDeleteDto, TagMergeDto, TagService, TagServiceTrait,
+    TagUpdateDto, TagViewDto,
+};
+use crate::domains::timebox::{
+    TIMEBOX_PHASE_EVENT, TIMEBOX_POLL_INTERVAL, TimeboxBreakDto, TimeboxPlan, TimeboxService,
+    TimeboxServiceTrait, TimeboxStartDto, TimeboxStatusDto,
+};
 use crate::domains::tracker::{
     TrackerEntryCreateDto, TrackerEntryLineCreateDto, TrackerEntryLineUpdateDto,
-    TrackerEntryLineViewDto, TrackerEntryViewDto, TrackerService, TrackerServiceTrait,
+    TrackerEntryLineViewDto, TrackerEntryUpdateDto, TrackerEntryViewDto, TrackerQueryDto,
+    TrackerService, TrackerServiceTrait, TrackerSort, TrackerSwitchDto,
 };
-use chrono::Utc;
+use crate::domains::trash::{TrashDto, TrashService, TrashServiceTrait};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use sqlx::SqlitePool;
-use std::sync::Arc;
-use tauri::{AppHandle, Manager, State};
-use tokio::sync::Mutex;
+use std::{path::PathBuf, sync::Arc};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+use tokio::sync::{Mutex, oneshot};
 
 #[derive(Default)]
 pub struct AppState {
     pub db_pool: Arc<Mutex<Option<SqlitePool>>>,
     pub tracker_service: Arc<Mutex<Option<Arc<dyn TrackerServiceTrait>>>>,
+    pub report_service: Arc<Mutex<Option<Arc<dyn ReportServiceTrait>>>>,
+    pub export_service: Arc<Mutex<Option<Arc<dyn ExportServiceTrait>>>>,
+    pub import_service: Arc<Mutex<Option<Arc<dyn ImportServiceTrait>>>>,
+    pub trash_service: Arc<Mutex<Option<Arc<dyn TrashServiceTrait>>>>,
+    pub recovery_service: Arc<Mutex<Option<Arc<dyn RecoveryServiceTrait>>>>,
+    pub tag_service: Arc<Mutex<Option<Arc<dyn TagServiceTrait>>>>,
+    pub client_service: Arc<Mutex<Option<Arc<dyn ClientServiceTrait>>>>,
+    pub billing_service: Arc<Mutex<Option<Arc<dyn BillingServiceTrait>>>>,
+    pub invoice_service: Arc<Mutex<Option<Arc<dyn InvoiceServiceTrait>>>>,
+    pub budget_service: Arc<Mutex<Option<Arc<dyn BudgetServiceTrait>>>>,
+    pub custom_field_service: Arc<Mutex<Option<Arc<dyn CustomFieldServiceTrait>>>>,
+    pub timebox_service: Arc<Mutex<Option<Arc<dyn TimeboxServiceTrait>>>>,
+    pub idle_service: Arc<Mutex<Option<Arc<dyn IdleServiceTrait>>>>,
+    pub schedule_service: Arc<Mutex<Option<Arc<dyn ScheduleServiceTrait>>>>,
+    pub history_service: Arc<Mutex<Option<Arc<dyn HistoryServiceTrait>>>>,
+    pub backup_service: Arc<Mutex<Option<Arc<dyn BackupServiceTrait>>>>,
 }
 
 #[tauri::command]
 pub async fn initialize_app(app_handle: AppHandle) -> Result<String, String> {
     let state: State<AppState> = app_handle.state();
 
-    match database::initialize_database(&app_handle).await {
-        Ok(pool) => {
-            let tracker_service = TrackerService::create_service(pool.clone());
+    // A webview reload initializes again, keeping the pool leaves a restore only one to swap
+    let existing_pool = state.db_pool.lock().await.clone();
+    let pool = match existing_pool {
+        Some(pool) => Ok(pool),
+        None => database::initialize_database(&app_handle).await,
+    };
 
-            // Store the database pool and service in the app state
+    match pool {
+        Ok(pool) => {
+            // Store the database pool and services in the app state
             {
                 let mut db_pool = state.db_pool.lock().await;
-                *db_pool = Some(pool);
-            }
-            {
-                let mut service = state.tracker_service.lock().await;
-                *service = Some(tracker_service);
+                *db_pool = Some(pool.clone());
             }
+            install_services(&app_handle, pool, false).await;
 
             log::info!("Database and services initialized successfully");
             Ok("Database initialized successfully".to_string())
@@ -43,8 +115,324 @@ pub async fn initialize_app(app_handle: AppHandle) -> Result<String, String> {
     }
 }
 
+/// Builds every service on `pool` and stores it in the app state.
+///
+/// Services that run in the background are only built once per process, unless
+/// `replace_background` is set because a restore swapped the database under them. Their
+/// loops look them up in the state on every round, so they move to the new services.
+async fn install_services(app_handle: &AppHandle, pool: SqlitePool, replace_background: bool) {
+    let state: State<AppState> = app_handle.state();
+
+    let tracker_service = TrackerService::create_service(pool.clone());
+    let report_service = ReportService::create_service(pool.clone());
+    let export_service = ExportService::create_service(pool.clone());
+    let import_service = ImportService::create_service(pool.clone());
+    let trash_service = TrashService::create_service(pool.clone());
+    let tag_service = TagService::create_service(pool.clone());
+    let client_service = ClientService::create_service(pool.clone());
+    let billing_service = BillingService::create_service(pool.clone());
+    let invoice_service = InvoiceService::create_service(pool.clone());
+    let budget_service = BudgetService::create_service(pool.clone());
+    let custom_field_service = CustomFieldService::create_service(pool.clone());
+    let history_service = HistoryService::create_service(pool.clone());
+
+    // The startup backup comes first, so it holds the database as the last session left it
+    {
+        let mut service = state.backup_service.lock().await;
+        if service.is_none() || replace_background {
+            match database::get_database_path(app_handle) {
+                Ok(database_path) => {
+                    let backup_service = BackupService::create_service(
+                        pool.clone(),
+                        database::backup_dir(&database_path),
+                    );
+
+                    if service.is_none() {
+                        match backup_service.create_backup(BackupReason::Startup).await {
+                            Ok(backup) => log::info!("Backed up database to {}", backup.file_name),
+                            Err(e) => log::error!("Failed to back up database: {}", e),
+                        }
+
+                        spawn_backup_timer(app_handle.clone());
+                    }
+                    *service = Some(backup_service);
+                }
+                Err(e) => log::error!("Failed to locate the backups: {}", e),
+            }
+        }
+    }
+
+    match trash_service.purge_expired().await {
+        Ok(purged) if purged > 0 => log::info!("Purged {} expired rows from trash", purged),
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to purge expired trash: {}", e),
+    }
+
+    // A webview reload initializes again while this session's intervals are
+    // legitimately open, so recovery and the heartbeat only start once per process
+    {
+        let mut service = state.recovery_service.lock().await;
+        if service.is_none() || replace_background {
+            let recovery_service = RecoveryService::create_service(pool.clone());
+
+            match recovery_service.recover().await {
+                Ok(report) => log::info!(
+                    "Recovered dangling intervals: {} closed, {} kept, {} pending",
+                    report.closed.len(),
+                    report.kept.len(),
+                    report.pending.len()
+                ),
+                Err(e) => log::error!("Failed to recover dangling intervals: {}", e),
+            }
+
+            if service.is_none() {
+                spawn_heartbeat(app_handle.clone());
+            }
+            *service = Some(recovery_service);
+        }
+    }
+
+    // The running timebox lives in the service, so it too survives a webview reload
+    {
+        let mut service = state.timebox_service.lock().await;
+        if service.is_none() || replace_background {
+            let timebox_service = TimeboxService::create_service(pool.clone());
+
+            match timebox_service.close_dangling_breaks().await {
+                Ok(closed) if closed > 0 => log::info!("Closed {} dangling breaks", closed),
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to close dangling breaks: {}", e),
+            }
+
+            if service.is_none() {
+                spawn_timebox_timer(app_handle.clone());
+            }
+            *service = Some(timebox_service);
+        }
+    }
+    {
+        let mut service = state.idle_service.lock().await;
+        if service.is_none() || replace_background {
+            let idle_service = IdleService::create_service(pool.clone());
+
+            if service.is_none() {
+                spawn_idle_monitor(app_handle.clone());
+            }
+            *service = Some(idle_service);
+        }
+    }
+    {
+        let mut service = state.schedule_service.lock().await;
+        if service.is_none() || replace_background {
+            let schedule_service = ScheduleService::create_service(pool.clone());
+
+            if service.is_none() {
+                spawn_scheduler(app_handle.clone());
+            }
+            *service = Some(schedule_service);
+        }
+    }
+
+    {
+        let mut service = state.tracker_service.lock().await;
+        *service = Some(tracker_service);
+    }
+    {
+        let mut service = state.report_service.lock().await;
+        *service = Some(report_service);
+    }
+    {
+        let mut service = state.export_service.lock().await;
+        *service = Some(export_service);
+    }
+    {
+        let mut service = state.import_service.lock().await;
+        *service = Some(import_service);
+    }
+    {
+        let mut service = state.trash_service.lock().await;
+        *service = Some(trash_service);
+    }
+    {
+        let mut service = state.tag_service.lock().await;
+        *service = Some(tag_service);
+    }
+    {
+        let mut service = state.client_service.lock().await;
+        *service = Some(client_service);
+    }
+    {
+        let mut service = state.billing_service.lock().await;
+        *service = Some(billing_service);
+    }
+    {
+        let mut service = state.invoice_service.lock().await;
+        *service = Some(invoice_service);
+    }
+    {
+        let mut service = state.budget_service.lock().await;
+        *service = Some(budget_service);
+    }
+    {
+        let mut service = state.custom_field_service.lock().await;
+        *service = Some(custom_field_service);
+    }
+    {
+        let mut service = state.history_service.lock().await;
+        *service = Some(history_service);
+    }
+}
+
+/// Periodically records that the app is alive, so a crash can be closed at the last heartbeat.
+fn spawn_heartbeat(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let state: State<AppState> = app_handle.state();
+            let Some(service) = state.recovery_service.lock().await.clone() else {
+                continue;
+            };
+
+            if let Err(e) = service.record_heartbeat().await {
+                log::warn!("Failed to record heartbeat: {}", e);
+            }
+        }
+    });
+}
+
+/// Moves the running timebox on when its phase ends and tells the frontend about it.
+fn spawn_timebox_timer(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state: State<AppState> = app_handle.state();
+            let Some(service) = state.timebox_service.lock().await.clone() else {
+                tokio::time::sleep(TIMEBOX_POLL_INTERVAL).await;
+                continue;
+            };
+
+            let wait = service
+                .next_deadline()
+                .await
+                .and_then(|deadline| (deadline - Utc::now()).to_std().ok())
+                .map_or(TIMEBOX_POLL_INTERVAL, |wait| {
+                    wait.min(TIMEBOX_POLL_INTERVAL)
+                });
+            tokio::time::sleep(wait).await;
+
+            match service.advance_timebox(Utc::now()).await {
+                Ok(Some(change)) => {
+                    if let Err(e) = app_handle.emit(TIMEBOX_PHASE_EVENT, change) {
+                        log::error!("Failed to send timebox phase change: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::error!("Failed to advance timebox: {}", e),
+            }
+        }
+    });
+}
+
+/// Watches for the user walking away while intervals are running.
+fn spawn_idle_monitor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let state: State<AppState> = app_handle.state();
+            let Some(service) = state.idle_service.lock().await.clone() else {
+                continue;
+            };
+
+            match service.check_idle(Utc::now()).await {
+                Ok(Some(period)) => {
+                    if let Err(e) = app_handle.emit(IDLE_DETECTED_EVENT, period) {
+                        log::error!("Failed to send idle notice: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to check idle time: {}", e),
+            }
+        }
+    });
+}
+
+/// Applies the stop rules, starting right away so timers left running overnight are
+/// caught on startup.
+fn spawn_scheduler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULE_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let state: State<AppState> = app_handle.state();
+            let Some(service) = state.schedule_service.lock().await.clone() else {
+                continue;
+            };
+
+            match service.enforce_rules(Utc::now()).await {
+                Ok(stopped) if !stopped.is_empty() => {
+                    if let Err(e) = app_handle.emit(FORCED_STOP_EVENT, stopped) {
+                        log::error!("Failed to send forced stops: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("Failed to apply stop rules: {}", e),
+            }
+        }
+    });
+}
+
+/// Takes the daily backup, checking hourly so a machine that sleeps through the day
+/// still gets one soon after waking.
+fn spawn_backup_timer(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(BACKUP_POLL_INTERVAL);
+        // The startup backup was just taken
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let state: State<AppState> = app_handle.state();
+            let Some(service) = state.backup_service.lock().await.clone() else {
+                continue;
+            };
+
+            match service.backup_if_due(Utc::now()).await {
+                Ok(Some(backup)) => log::info!("Took daily backup {}", backup.file_name),
+                Ok(None) => {}
+                Err(e) => log::error!("Failed to take daily backup: {}", e),
+            }
+        }
+    });
+}
+
+/// Backs the database up before a command that destroys data, which does not run without
+/// the backup.
+async fn backup_before(state: &State<'_, AppState>, reason: BackupReason) -> Result<(), String> {
+    let service_guard = state.backup_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .create_backup(reason)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to back up database: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
 #[tauri::command]
 pub async fn truncate_tables(state: State<'_, AppState>) -> Result<(), String> {
+    backup_before(&state, BackupReason::BeforeTruncate).await?;
+
     let pool_guard = state.db_pool.lock().await;
 
     if let Some(pool) = pool_guard.as_ref() {
@@ -57,12 +445,21 @@ pub async fn truncate_tables(state: State<'_, AppState>) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn get_trackers(state: State<'_, AppState>) -> Result<Vec<TrackerEntryViewDto>, String> {
+pub async fn get_trackers(
+    include_archived: Option<bool>,
+    sort: Option<TrackerSort>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackerEntryViewDto>, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
+        let query = TrackerQueryDto {
+            include_archived: include_archived.unwrap_or_default(),
+            sort: sort.unwrap_or_default(),
+        };
+
         service
-            .get_trackers()
+            .get_trackers(query)
             .await
             .map_err(|e| format!("Failed to get trackers: {}", e))
     } else {
@@ -73,12 +470,14 @@ pub async fn get_trackers(state: State<'_, AppState>) -> Result<Vec<TrackerEntry
 #[tauri::command]
 pub async fn create_tracker(
     label: String,
+    client_id: Option<i64>,
     state: State<'_, AppState>,
 ) -> Result<TrackerEntryViewDto, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
         let dto = TrackerEntryCreateDto {
+            client_id,
             label,
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -94,30 +493,96 @@ pub async fn create_tracker(
 }
 
 #[tauri::command]
-pub async fn start_tracking(
-    entry_id: i64,
-    description: String,
+pub async fn get_trackers_by_client(
     state: State<'_, AppState>,
-) -> Result<TrackerEntryLineViewDto, String> {
+) -> Result<Vec<ClientTrackersDto>, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
-        let dto = TrackerEntryLineCreateDto {
-            entry_id,
-            desc: description,
-            created_at: Utc::now(),
+        service
+            .get_trackers_by_client()
+            .await
+            .map_err(|e| format!("Failed to get trackers by client: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_lines_by_tags(
+    tag_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackerEntryLineViewDto>, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_lines_by_tags(tag_ids)
+            .await
+            .map_err(|e| format!("Failed to get lines by tags: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn update_tracker(
+    tracker_id: i64,
+    label: String,
+    client_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TrackerEntryUpdateDto {
+            id: tracker_id,
+            client_id,
+            label,
             updated_at: Utc::now(),
         };
 
         service
-            .start_tracking(dto)
+            .update_tracker(dto)
             .await
-            .map_err(|e| format!("Failed to start tracking: {}", e))
+            .map_err(|e| format!("Failed to update tracker: {}", e))
     } else {
         Err("Service not initialized".to_string())
     }
 }
 
+#[tauri::command]
+pub async fn start_tracking(
+    app_handle: AppHandle,
+    entry_id: i64,
+    description: String,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let line = {
+        let service_guard = state.tracker_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            let dto = TrackerEntryLineCreateDto {
+                entry_id,
+                desc: description,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+
+            service
+                .start_tracking(dto)
+                .await
+                .map_err(|e| format!("Failed to start tracking: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    warn_if_over_budget(&app_handle, &state, line.entry_id).await;
+
+    Ok(line)
+}
+
 #[tauri::command]
 pub async fn stop_tracking(
     line_id: i64,
@@ -137,93 +602,1748 @@ pub async fn stop_tracking(
 
 #[tauri::command]
 pub async fn resume_tracking(
+    app_handle: AppHandle,
     line_id: i64,
     state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let line = {
+        let service_guard = state.tracker_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            service
+                .resume_tracking(line_id)
+                .await
+                .map_err(|e| format!("Failed to resume tracking: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    warn_if_over_budget(&app_handle, &state, line.entry_id).await;
+
+    Ok(line)
+}
+
+#[tauri::command]
+pub async fn switch_tracking(
+    app_handle: AppHandle,
+    from_line_id: i64,
+    to_line_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerSwitchDto, String> {
+    let switched = {
+        let service_guard = state.tracker_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            service
+                .switch_tracking(from_line_id, to_line_id)
+                .await
+                .map_err(|e| format!("Failed to switch tracking: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    warn_if_over_budget(&app_handle, &state, switched.started.entry_id).await;
+
+    Ok(switched)
+}
+
+#[tauri::command]
+pub async fn split_line_duration(
+    duration_id: i64,
+    at: DateTime<Utc>,
+    state: State<'_, AppState>,
 ) -> Result<TrackerEntryLineViewDto, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
         service
-            .resume_tracking(line_id)
+            .split_line_duration(duration_id, at)
             .await
-            .map_err(|e| format!("Failed to resume tracking: {}", e))
+            .map_err(|e| format!("Failed to split interval: {}", e))
     } else {
         Err("Service not initialized".to_string())
     }
 }
 
 #[tauri::command]
-pub async fn delete_tracker(tracker_id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    let service_guard = state.tracker_service.lock().await;
+pub async fn merge_tracker_lines(
+    app_handle: AppHandle,
+    source_line_id: i64,
+    target_line_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let line = {
+        let service_guard = state.tracker_service.lock().await;
 
-    if let Some(service) = service_guard.as_ref() {
-        use crate::domains::tracker::TrackerEntryDeleteDto;
+        if let Some(service) = service_guard.as_ref() {
+            service
+                .merge_lines(source_line_id, target_line_id)
+                .await
+                .map_err(|e| format!("Failed to merge lines: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
 
-        let dto = TrackerEntryDeleteDto { id: tracker_id };
+    warn_if_over_budget(&app_handle, &state, line.entry_id).await;
+
+    Ok(line)
+}
+
+#[tauri::command]
+pub async fn merge_trackers(
+    app_handle: AppHandle,
+    source_entry_id: i64,
+    target_entry_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let tracker = {
+        let service_guard = state.tracker_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            service
+                .merge_trackers(source_entry_id, target_entry_id)
+                .await
+                .map_err(|e| format!("Failed to merge trackers: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    warn_if_over_budget(&app_handle, &state, tracker.id).await;
+
+    Ok(tracker)
+}
+
+#[tauri::command]
+pub async fn get_single_active_tracking(state: State<'_, AppState>) -> Result<bool, String> {
+    let service_guard = state.tracker_service.lock().await;
 
+    if let Some(service) = service_guard.as_ref() {
         service
-            .delete_tracker(dto)
+            .get_single_active()
             .await
-            .map_err(|e| format!("Failed to delete tracker: {}", e))
+            .map_err(|e| format!("Failed to get single-active mode: {}", e))
     } else {
         Err("Service not initialized".to_string())
     }
 }
 
 #[tauri::command]
-pub async fn delete_tracker_line(line_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_single_active_tracking(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
-        use crate::domains::tracker::TrackerEntryLineDeleteDto;
-
-        let dto = TrackerEntryLineDeleteDto { id: line_id };
-
         service
-            .remove_tracked(dto)
+            .set_single_active(enabled)
             .await
-            .map_err(|e| format!("Failed to delete tracker line: {}", e))
+            .map_err(|e| format!("Failed to set single-active mode: {}", e))
     } else {
         Err("Service not initialized".to_string())
     }
 }
 
+/// Tells the frontend when tracking starts on a tracker with no budget left. A failed check
+/// is only logged, it must not keep the timer from starting.
+async fn warn_if_over_budget(app_handle: &AppHandle, state: &State<'_, AppState>, entry_id: i64) {
+    let service_guard = state.budget_service.lock().await;
+
+    let Some(service) = service_guard.as_ref() else {
+        return;
+    };
+
+    match service.check_budget(entry_id).await {
+        Ok(Some(budget)) => {
+            log::warn!(
+                "Tracker {} is over budget by {} seconds",
+                entry_id,
+                -budget.remaining_seconds.unwrap_or_default()
+            );
+
+            if let Err(e) = app_handle.emit(BUDGET_EXCEEDED_EVENT, budget) {
+                log::error!("Failed to send budget warning: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to check budget of tracker {}: {}", entry_id, e),
+    }
+}
+
 #[tauri::command]
-pub async fn stop_all_active_tracking(
+pub async fn update_tracker_line(
+    line_id: i64,
+    entry_id: i64,
+    description: String,
     state: State<'_, AppState>,
-) -> Result<Vec<TrackerEntryLineViewDto>, String> {
+) -> Result<TrackerEntryLineViewDto, String> {
     let service_guard = state.tracker_service.lock().await;
 
     if let Some(service) = service_guard.as_ref() {
-        // Get all trackers (which include their lines)
-        let all_trackers = service
-            .get_trackers()
+        let dto = TrackerEntryLineUpdateDto {
+            id: line_id,
+            entry_id,
+            desc: description,
+            updated_at: Utc::now(),
+        };
+
+        service
+            .update_tracked(dto)
             .await
-            .map_err(|e| format!("Failed to get trackers: {}", e))?;
+            .map_err(|e| format!("Failed to update tracker line: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
 
-        // Extract all lines from all trackers and find active ones
-        let active_lines: Vec<TrackerEntryLineViewDto> = all_trackers
-            .into_iter()
-            .flat_map(|tracker| tracker.lines)
-            .filter(|line| line.durations.iter().any(|d| d.ended_at.is_none()))
-            .collect();
+#[tauri::command]
+pub async fn delete_tracker(tracker_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.tracker_service.lock().await;
 
-        // Stop each active line
-        let mut stopped_lines = Vec::new();
-        for line in active_lines {
-            match service.stop_tracking(line.id).await {
-                Ok(updated_line) => {
-                    log::info!("Stopped active tracking line: {}", updated_line.id);
-                    stopped_lines.push(updated_line);
-                }
-                Err(e) => {
-                    log::error!("Failed to stop tracking line {}: {}", line.id, e);
-                }
-            }
-        }
+    if let Some(service) = service_guard.as_ref() {
+        use crate::domains::tracker::TrackerEntryDeleteDto;
+
+        let dto = TrackerEntryDeleteDto { id: tracker_id };
 
-        Ok(stopped_lines)
+        service
+            .delete_tracker(dto)
+            .await
+            .map_err(|e| format!("Failed to delete tracker: {}", e))
     } else {
         Err("Service not initialized".to_string())
     }
 }
+
+#[tauri::command]
+pub async fn archive_tracker(
+    tracker_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .archive_tracker(tracker_id)
+            .await
+            .map_err(|e| format!("Failed to archive tracker: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn unarchive_tracker(
+    tracker_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .unarchive_tracker(tracker_id)
+            .await
+            .map_err(|e| format!("Failed to unarchive tracker: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn reorder_trackers(
+    tracker_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackerEntryViewDto>, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .reorder_trackers(tracker_ids)
+            .await
+            .map_err(|e| format!("Failed to reorder trackers: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_tracker_pinned(
+    tracker_id: i64,
+    pinned: bool,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_tracker_pinned(tracker_id, pinned)
+            .await
+            .map_err(|e| format!("Failed to pin tracker: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_tracker_color(
+    tracker_id: i64,
+    color: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_tracker_color(tracker_id, color)
+            .await
+            .map_err(|e| format!("Failed to set tracker colour: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn delete_tracker_line(line_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        use crate::domains::tracker::TrackerEntryLineDeleteDto;
+
+        let dto = TrackerEntryLineDeleteDto { id: line_id };
+
+        service
+            .remove_tracked(dto)
+            .await
+            .map_err(|e| format!("Failed to delete tracker line: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn add_line_duration(
+    line_id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        use crate::domains::tracker::TrackerEntryLineDurationCreateDto;
+
+        let dto = TrackerEntryLineDurationCreateDto {
+            entry_line_id: line_id,
+            started_at,
+            ended_at,
+        };
+
+        service
+            .add_line_duration(dto)
+            .await
+            .map_err(|e| format!("Failed to add interval: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn update_line_duration(
+    duration_id: i64,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        use crate::domains::tracker::TrackerEntryLineDurationUpdateDto;
+
+        let dto = TrackerEntryLineDurationUpdateDto {
+            id: duration_id,
+            started_at,
+            ended_at,
+        };
+
+        service
+            .update_line_duration(dto)
+            .await
+            .map_err(|e| format!("Failed to update interval: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn delete_line_duration(
+    duration_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerEntryLineViewDto, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        use crate::domains::tracker::TrackerEntryLineDurationDeleteDto;
+
+        let dto = TrackerEntryLineDurationDeleteDto { id: duration_id };
+
+        service
+            .delete_line_duration(dto)
+            .await
+            .map_err(|e| format!("Failed to delete interval: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn stop_all_active_tracking(
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackerEntryLineViewDto>, String> {
+    let service_guard = state.tracker_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .stop_all_tracking()
+            .await
+            .map_err(|e| format!("Failed to stop active tracking: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_report(
+    from: NaiveDate,
+    to: NaiveDate,
+    state: State<'_, AppState>,
+) -> Result<ReportDto, String> {
+    let service_guard = state.report_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_report(ReportQueryDto { from, to })
+            .await
+            .map_err(|e| format!("Failed to get report: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_period_totals(
+    from: NaiveDate,
+    to: NaiveDate,
+    period: ReportPeriod,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReportPeriodTotalDto>, String> {
+    let service_guard = state.report_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_period_totals(ReportQueryDto { from, to }, period)
+            .await
+            .map_err(|e| format!("Failed to get period totals: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_tracker_totals(
+    from: NaiveDate,
+    to: NaiveDate,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReportTrackerTotalDto>, String> {
+    let service_guard = state.report_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_tracker_totals(ReportQueryDto { from, to })
+            .await
+            .map_err(|e| format!("Failed to get tracker totals: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_tag_totals(
+    from: NaiveDate,
+    to: NaiveDate,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReportTagTotalDto>, String> {
+    let service_guard = state.report_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_tag_totals(ReportQueryDto { from, to })
+            .await
+            .map_err(|e| format!("Failed to get tag totals: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+/// Shows the native save dialog and resolves to the chosen path, or `None` if it was cancelled.
+async fn pick_save_path(
+    app_handle: &AppHandle,
+    filter_name: &str,
+    extension: &str,
+    file_name: String,
+) -> Result<Option<PathBuf>, String> {
+    let (tx, rx) = oneshot::channel();
+
+    app_handle
+        .dialog()
+        .file()
+        .add_filter(filter_name, &[extension])
+        .set_file_name(file_name)
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+
+    match rx.await.map_err(|e| format!("Save dialog failed: {}", e))? {
+        Some(file_path) => file_path
+            .into_path()
+            .map(Some)
+            .map_err(|e| format!("Invalid save path: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Shows the native open dialog and resolves to the chosen path, or `None` if it was cancelled.
+async fn pick_open_path(
+    app_handle: &AppHandle,
+    filter_name: &str,
+    extensions: &[&str],
+) -> Result<Option<PathBuf>, String> {
+    let (tx, rx) = oneshot::channel();
+
+    app_handle
+        .dialog()
+        .file()
+        .add_filter(filter_name, extensions)
+        .pick_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+
+    match rx.await.map_err(|e| format!("Open dialog failed: {}", e))? {
+        Some(file_path) => file_path
+            .into_path()
+            .map(Some)
+            .map_err(|e| format!("Invalid file path: {}", e)),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn export_tracked_time(
+    app_handle: AppHandle,
+    format: ExportFormat,
+    query: ExportQueryDto,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let contents = {
+        let service_guard = state.export_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            service
+                .export(query, format)
+                .await
+                .map_err(|e| format!("Failed to export tracked time: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    let file_name = format!(
+        "track-it-export-{}.{}",
+        Utc::now().format("%Y-%m-%d"),
+        format.extension()
+    );
+
+    // The user closing the dialog is not an error, there is just nothing to write
+    let Some(path) = pick_save_path(
+        &app_handle,
+        &format.extension().to_uppercase(),
+        format.extension(),
+        file_name,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    log::info!("Exported tracked time to {}", path.display());
+
+    Ok(Some(path.display().to_string()))
+}
+
+#[tauri::command]
+pub async fn pick_import_file(app_handle: AppHandle) -> Result<Option<String>, String> {
+    let path = pick_open_path(&app_handle, "Export files", &["csv", "json"]).await?;
+
+    Ok(path.map(|path| path.display().to_string()))
+}
+
+#[tauri::command]
+pub async fn import_tracked_time(
+    path: String,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<ImportReportDto, String> {
+    let path = PathBuf::from(path);
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("json") => ExportFormat::Json,
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => ExportFormat::Csv,
+        _ => return Err("Import file must be a .csv or .json file".to_string()),
+    };
+
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read import file: {}", e))?;
+
+    let service_guard = state.import_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .import(contents, format, dry_run)
+            .await
+            .map_err(|e| format!("Failed to import tracked time: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_trash(state: State<'_, AppState>) -> Result<TrashDto, String> {
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_trash()
+            .await
+            .map_err(|e| format!("Failed to get trash: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn restore_tracker(tracker_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .restore_tracker(tracker_id)
+            .await
+            .map_err(|e| format!("Failed to restore tracker: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn restore_tracker_line(line_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .restore_line(line_id)
+            .await
+            .map_err(|e| format!("Failed to restore tracker line: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn purge_tracker(tracker_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    backup_before(&state, BackupReason::BeforePurge).await?;
+
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .purge_tracker(tracker_id)
+            .await
+            .map_err(|e| format!("Failed to purge tracker: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn purge_tracker_line(line_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    backup_before(&state, BackupReason::BeforePurge).await?;
+
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .purge_line(line_id)
+            .await
+            .map_err(|e| format!("Failed to purge tracker line: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn purge_expired_trash(state: State<'_, AppState>) -> Result<u64, String> {
+    backup_before(&state, BackupReason::BeforePurge).await?;
+
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .purge_expired()
+            .await
+            .map_err(|e| format!("Failed to purge expired trash: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_trash_retention(state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_retention_days()
+            .await
+            .map_err(|e| format!("Failed to get trash retention: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_trash_retention(days: u32, state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.trash_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_retention_days(days)
+            .await
+            .map_err(|e| format!("Failed to set trash retention: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_recovery_cases(
+    state: State<'_, AppState>,
+) -> Result<Vec<RecoveryCaseDto>, String> {
+    let service_guard = state.recovery_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_pending_cases()
+            .await
+            .map_err(|e| format!("Failed to get recovery cases: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn resolve_recovery_case(
+    duration_id: i64,
+    resolution: RecoveryResolution,
+    state: State<'_, AppState>,
+) -> Result<Vec<RecoveryCaseDto>, String> {
+    let service_guard = state.recovery_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = RecoveryResolveDto {
+            duration_id,
+            resolution,
+        };
+
+        service
+            .resolve_case(dto)
+            .await
+            .map_err(|e| format!("Failed to resolve recovery case: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_recovery_policy(state: State<'_, AppState>) -> Result<RecoveryPolicy, String> {
+    let service_guard = state.recovery_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_policy()
+            .await
+            .map_err(|e| format!("Failed to get recovery policy: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_recovery_policy(
+    policy: RecoveryPolicy,
+    state: State<'_, AppState>,
+) -> Result<RecoveryPolicy, String> {
+    let service_guard = state.recovery_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_policy(policy)
+            .await
+            .map_err(|e| format!("Failed to set recovery policy: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_tags(state: State<'_, AppState>) -> Result<Vec<TagViewDto>, String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_tags()
+            .await
+            .map_err(|e| format!("Failed to get tags: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn create_tag(name: String, state: State<'_, AppState>) -> Result<TagViewDto, String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TagCreateDto { name };
+
+        service
+            .create_tag(dto)
+            .await
+            .map_err(|e| format!("Failed to create tag: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn rename_tag(
+    tag_id: i64,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<TagViewDto, String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TagUpdateDto { id: tag_id, name };
+
+        service
+            .rename_tag(dto)
+            .await
+            .map_err(|e| format!("Failed to rename tag: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn merge_tags(
+    source_id: i64,
+    target_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TagViewDto, String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TagMergeDto {
+            source_id,
+            target_id,
+        };
+
+        service
+            .merge_tags(dto)
+            .await
+            .map_err(|e| format!("Failed to merge tags: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn delete_tag(tag_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TagDeleteDto { id: tag_id };
+
+        service
+            .delete_tag(dto)
+            .await
+            .map_err(|e| format!("Failed to delete tag: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_line_tags(
+    line_id: i64,
+    tag_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TagViewDto>, String> {
+    let service_guard = state.tag_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = LineTagsUpdateDto { line_id, tag_ids };
+
+        service
+            .set_line_tags(dto)
+            .await
+            .map_err(|e| format!("Failed to set line tags: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_clients(state: State<'_, AppState>) -> Result<Vec<ClientViewDto>, String> {
+    let service_guard = state.client_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_clients()
+            .await
+            .map_err(|e| format!("Failed to get clients: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn create_client(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<ClientViewDto, String> {
+    let service_guard = state.client_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = ClientCreateDto { name };
+
+        service
+            .create_client(dto)
+            .await
+            .map_err(|e| format!("Failed to create client: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn update_client(
+    client_id: i64,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<ClientViewDto, String> {
+    let service_guard = state.client_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = ClientUpdateDto {
+            id: client_id,
+            name,
+        };
+
+        service
+            .update_client(dto)
+            .await
+            .map_err(|e| format!("Failed to update client: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn delete_client(client_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.client_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = ClientDeleteDto { id: client_id };
+
+        service
+            .delete_client(dto)
+            .await
+            .map_err(|e| format!("Failed to delete client: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_client_rates(state: State<'_, AppState>) -> Result<Vec<ClientRateDto>, String> {
+    let service_guard = state.billing_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_client_rates()
+            .await
+            .map_err(|e| format!("Failed to get client rates: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_client_rate(
+    client_id: i64,
+    hourly_rate: Option<Decimal>,
+    currency: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ClientRateDto, String> {
+    let service_guard = state.billing_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = ClientRateUpdateDto {
+            client_id,
+            hourly_rate,
+            currency,
+        };
+
+        service
+            .set_client_rate(dto)
+            .await
+            .map_err(|e| format!("Failed to set client rate: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_tracker_rate(
+    tracker_id: i64,
+    hourly_rate: Option<Decimal>,
+    currency: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TrackerRateDto, String> {
+    let service_guard = state.billing_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TrackerRateUpdateDto {
+            entry_id: tracker_id,
+            hourly_rate,
+            currency,
+        };
+
+        service
+            .set_tracker_rate(dto)
+            .await
+            .map_err(|e| format!("Failed to set tracker rate: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_line_billing(
+    line_id: i64,
+    is_billable: bool,
+    hourly_rate: Option<Decimal>,
+    state: State<'_, AppState>,
+) -> Result<LineBillingDto, String> {
+    let service_guard = state.billing_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = LineBillingUpdateDto {
+            line_id,
+            is_billable,
+            hourly_rate,
+        };
+
+        service
+            .set_line_billing(dto)
+            .await
+            .map_err(|e| format!("Failed to set line billing: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn preview_invoice(
+    client_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+    currency: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<InvoiceViewDto, String> {
+    let service_guard = state.invoice_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = InvoiceCreateDto {
+            client_id,
+            from,
+            to,
+            currency,
+        };
+
+        service
+            .preview_invoice(dto)
+            .await
+            .map_err(|e| format!("Failed to preview invoice: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn create_invoice(
+    client_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+    currency: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<InvoiceViewDto, String> {
+    let service_guard = state.invoice_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = InvoiceCreateDto {
+            client_id,
+            from,
+            to,
+            currency,
+        };
+
+        service
+            .create_invoice(dto)
+            .await
+            .map_err(|e| format!("Failed to create invoice: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_invoices(state: State<'_, AppState>) -> Result<Vec<InvoiceViewDto>, String> {
+    let service_guard = state.invoice_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_invoices()
+            .await
+            .map_err(|e| format!("Failed to get invoices: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_invoice(
+    invoice_id: i64,
+    state: State<'_, AppState>,
+) -> Result<InvoiceViewDto, String> {
+    let service_guard = state.invoice_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_invoice(invoice_id)
+            .await
+            .map_err(|e| format!("Failed to get invoice: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn export_invoice(
+    app_handle: AppHandle,
+    invoice_id: i64,
+    format: InvoiceFormat,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let (number, contents) = {
+        let service_guard = state.invoice_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            let invoice = service
+                .get_invoice(invoice_id)
+                .await
+                .map_err(|e| format!("Failed to get invoice: {}", e))?;
+            let contents = service
+                .render_invoice(invoice_id, format)
+                .await
+                .map_err(|e| format!("Failed to render invoice: {}", e))?;
+
+            (invoice.number, contents)
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    let file_name = format!("invoice-{}.{}", number, format.extension());
+
+    let Some(path) = pick_save_path(
+        &app_handle,
+        &format.extension().to_uppercase(),
+        format.extension(),
+        file_name,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write invoice file: {}", e))?;
+
+    log::info!("Saved invoice {} to {}", number, path.display());
+
+    Ok(Some(path.display().to_string()))
+}
+
+#[tauri::command]
+pub async fn get_tracker_budgets(
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackerBudgetDto>, String> {
+    let service_guard = state.budget_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_tracker_budgets()
+            .await
+            .map_err(|e| format!("Failed to get tracker budgets: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_tracker_budget(
+    tracker_id: i64,
+    state: State<'_, AppState>,
+) -> Result<TrackerBudgetDto, String> {
+    let service_guard = state.budget_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_tracker_budget(tracker_id)
+            .await
+            .map_err(|e| format!("Failed to get tracker budget: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_tracker_budget(
+    tracker_id: i64,
+    budget_hours: Option<Decimal>,
+    period: Option<BudgetPeriod>,
+    state: State<'_, AppState>,
+) -> Result<TrackerBudgetDto, String> {
+    let service_guard = state.budget_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = TrackerBudgetUpdateDto {
+            entry_id: tracker_id,
+            budget_hours,
+            period: period.unwrap_or_default(),
+        };
+
+        service
+            .set_tracker_budget(dto)
+            .await
+            .map_err(|e| format!("Failed to set tracker budget: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_line_estimate(
+    line_id: i64,
+    estimate_hours: Option<Decimal>,
+    state: State<'_, AppState>,
+) -> Result<LineEstimateDto, String> {
+    let service_guard = state.budget_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = LineEstimateUpdateDto {
+            line_id,
+            estimate_hours,
+        };
+
+        service
+            .set_line_estimate(dto)
+            .await
+            .map_err(|e| format!("Failed to set line estimate: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_custom_fields(
+    state: State<'_, AppState>,
+) -> Result<Vec<CustomFieldViewDto>, String> {
+    let service_guard = state.custom_field_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_fields()
+            .await
+            .map_err(|e| format!("Failed to get custom fields: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn create_custom_field(
+    name: String,
+    field_type: CustomFieldType,
+    scope: CustomFieldScope,
+    options: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<CustomFieldViewDto, String> {
+    let service_guard = state.custom_field_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = CustomFieldCreateDto {
+            name,
+            field_type,
+            scope,
+            options: options.unwrap_or_default(),
+        };
+
+        service
+            .create_field(dto)
+            .await
+            .map_err(|e| format!("Failed to create custom field: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn update_custom_field(
+    field_id: i64,
+    name: String,
+    options: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<CustomFieldViewDto, String> {
+    let service_guard = state.custom_field_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = CustomFieldUpdateDto {
+            id: field_id,
+            name,
+            options: options.unwrap_or_default(),
+        };
+
+        service
+            .update_field(dto)
+            .await
+            .map_err(|e| format!("Failed to update custom field: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn delete_custom_field(field_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.custom_field_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = CustomFieldDeleteDto { id: field_id };
+
+        service
+            .delete_field(dto)
+            .await
+            .map_err(|e| format!("Failed to delete custom field: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_custom_field_value(
+    field_id: i64,
+    target_id: i64,
+    value: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CustomFieldValueViewDto>, String> {
+    let service_guard = state.custom_field_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        let dto = CustomFieldValueUpdateDto {
+            field_id,
+            target_id,
+            value,
+        };
+
+        service
+            .set_field_value(dto)
+            .await
+            .map_err(|e| format!("Failed to set custom field value: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn start_timebox(
+    app_handle: AppHandle,
+    entry_id: i64,
+    description: String,
+    plan: Option<TimeboxPlan>,
+    state: State<'_, AppState>,
+) -> Result<TimeboxStatusDto, String> {
+    let status = {
+        let service_guard = state.timebox_service.lock().await;
+
+        if let Some(service) = service_guard.as_ref() {
+            let dto = TimeboxStartDto {
+                entry_id,
+                desc: description,
+                plan: plan.unwrap_or_default(),
+            };
+
+            service
+                .start_timebox(dto)
+                .await
+                .map_err(|e| format!("Failed to start timebox: {}", e))?
+        } else {
+            return Err("Service not initialized".to_string());
+        }
+    };
+
+    warn_if_over_budget(&app_handle, &state, status.entry_id).await;
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn get_timebox(state: State<'_, AppState>) -> Result<Option<TimeboxStatusDto>, String> {
+    let service_guard = state.timebox_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_timebox()
+            .await
+            .map_err(|e| format!("Failed to get timebox: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_timebox(state: State<'_, AppState>) -> Result<(), String> {
+    let service_guard = state.timebox_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .cancel_timebox()
+            .await
+            .map_err(|e| format!("Failed to cancel timebox: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_timebox_breaks(
+    line_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<TimeboxBreakDto>, String> {
+    let service_guard = state.timebox_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_breaks(line_id)
+            .await
+            .map_err(|e| format!("Failed to get timebox breaks: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_pending_idle(state: State<'_, AppState>) -> Result<Option<IdlePeriodDto>, String> {
+    let service_guard = state.idle_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_pending_idle()
+            .await
+            .map_err(|e| format!("Failed to get pending idle time: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn resolve_idle(
+    resolution: IdleResolution,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let service_guard = state.idle_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .resolve_idle(resolution)
+            .await
+            .map_err(|e| format!("Failed to resolve idle time: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_idle_threshold(state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.idle_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_threshold()
+            .await
+            .map_err(|e| format!("Failed to get idle threshold: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_idle_threshold(minutes: u32, state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.idle_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_threshold(minutes)
+            .await
+            .map_err(|e| format!("Failed to set idle threshold: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_stop_rules(state: State<'_, AppState>) -> Result<StopRules, String> {
+    let service_guard = state.schedule_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_rules()
+            .await
+            .map_err(|e| format!("Failed to get stop rules: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_stop_rules(
+    rules: StopRules,
+    state: State<'_, AppState>,
+) -> Result<StopRules, String> {
+    let service_guard = state.schedule_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_rules(rules)
+            .await
+            .map_err(|e| format!("Failed to set stop rules: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_forced_stops(
+    since: Option<DateTime<Utc>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ForcedStopDto>, String> {
+    let service_guard = state.schedule_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_forced_stops(since)
+            .await
+            .map_err(|e| format!("Failed to get forced stops: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn undo(state: State<'_, AppState>) -> Result<Option<HistoryEntryDto>, String> {
+    let service_guard = state.history_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .undo()
+            .await
+            .map_err(|e| format!("Failed to undo: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn redo(state: State<'_, AppState>) -> Result<Option<HistoryEntryDto>, String> {
+    let service_guard = state.history_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .redo()
+            .await
+            .map_err(|e| format!("Failed to redo: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_history(state: State<'_, AppState>) -> Result<Vec<HistoryEntryDto>, String> {
+    let service_guard = state.history_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_history()
+            .await
+            .map_err(|e| format!("Failed to get history: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn list_backups(state: State<'_, AppState>) -> Result<Vec<BackupDto>, String> {
+    let service_guard = state.backup_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .list_backups()
+            .await
+            .map_err(|e| format!("Failed to list backups: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn get_backup_keep_count(state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.backup_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .get_keep_count()
+            .await
+            .map_err(|e| format!("Failed to get backup count: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn set_backup_keep_count(count: u32, state: State<'_, AppState>) -> Result<u32, String> {
+    let service_guard = state.backup_service.lock().await;
+
+    if let Some(service) = service_guard.as_ref() {
+        service
+            .set_keep_count(count)
+            .await
+            .map_err(|e| format!("Failed to set backup count: {}", e))
+    } else {
+        Err("Service not initialized".to_string())
+    }
+}
+
+/// Replaces the database with a backup, after backing up what it holds now. Every service
+/// is rebuilt on the restored database, so the frontend should reload its data afterwards.
+#[tauri::command]
+pub async fn restore_backup(file_name: String, app_handle: AppHandle) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+
+    let Some(service) = state.backup_service.lock().await.clone() else {
+        return Err("Service not initialized".to_string());
+    };
+    let backup_path = service
+        .get_backup_path(file_name)
+        .await
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    let database_path = database::get_database_path(&app_handle)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+
+    // Staged before the backup below, whose rotation may remove the file being restored
+    let staged_path = database::stage_database(&database_path, &backup_path)
+        .await
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    if let Err(e) = database::check_staged_database(&staged_path).await {
+        let _ = tokio::fs::remove_file(&staged_path).await;
+        return Err(format!("Failed to restore backup: {}", e));
+    }
+
+    // Holding the pool keeps truncation and other restores out until the swap is done
+    let mut pool_guard = state.db_pool.lock().await;
+    let Some(pool) = pool_guard.clone() else {
+        let _ = tokio::fs::remove_file(&staged_path).await;
+        return Err("Database not initialized".to_string());
+    };
+
+    if let Err(e) = service.create_backup(BackupReason::BeforeRestore).await {
+        let _ = tokio::fs::remove_file(&staged_path).await;
+        return Err(format!("Failed to back up database: {}", e));
+    }
+
+    // Refused while another program uses the database, the current pool stays as it is
+    let lock = match database::lock_database(&database_path).await {
+        Ok(lock) => lock,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&staged_path).await;
+            return Err(format!("Failed to restore backup: {}", e));
+        }
+    };
+
+    // Waits for running queries to finish, anything started from now on fails instead of
+    // writing into the file being replaced
+    pool.close().await;
+
+    let (pool, result) = match database::replace_database(&database_path, &staged_path, lock).await
+    {
+        Ok(pool) => (pool, Ok(())),
+        Err(e) => {
+            log::error!("Failed to restore {}: {}", backup_path.display(), e);
+
+            // The swap is a rename, so unless it went through the old database is still there
+            match database::connect_database(&database_path).await {
+                Ok(pool) => (pool, Err(format!("Failed to restore backup: {}", e))),
+                Err(reopen_error) => {
+                    // No pool beats a closed one, commands report the database as missing
+                    *pool_guard = None;
+                    return Err(format!(
+                        "Failed to restore backup: {}, and to reopen the database: {}",
+                        e, reopen_error
+                    ));
+                }
+            }
+        }
+    };
+
+    *pool_guard = Some(pool.clone());
+    install_services(&app_handle, pool, true).await;
+
+    result
+}
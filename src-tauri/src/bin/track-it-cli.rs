@@ -0,0 +1,336 @@
+use chrono::{Local, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::{path::PathBuf, process::ExitCode};
+use track_it_lib::{
+    database,
+    domains::tracker::{
+        TrackerEntryCreateDto, TrackerEntryDeleteDto, TrackerEntryLineCreateDto,
+        TrackerEntryLineDeleteDto, TrackerEntryLineViewDto, TrackerQueryDto, TrackerService,
+        TrackerServiceTrait, TrackerSort,
+    },
+};
+
+#[derive(Parser)]
+#[command(
+    name = "track-it-cli",
+    version,
+    about = "Headless front-end for track-it"
+)]
+struct Cli {
+    /// Path to the database, defaults to the one used by the desktop app
+    #[arg(long, env = "TRACK_IT_DB", global = true)]
+    db: Option<PathBuf>,
+
+    /// Print results as JSON instead of plain text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List all trackers with their lines
+    List {
+        /// Include archived trackers
+        #[arg(long)]
+        archived: bool,
+        /// Order of the list, pinned trackers always come first
+        #[arg(long, value_enum, default_value_t = SortArg::Manual)]
+        sort: SortArg,
+    },
+    /// Create a new tracker
+    Create {
+        label: String,
+        /// Client that owns the tracker, defaults to the "Unassigned" client
+        #[arg(long)]
+        client: Option<i64>,
+    },
+    /// Start tracking a new line on a tracker
+    Start {
+        tracker_id: i64,
+        description: String,
+    },
+    /// Stop a running line, or every running line with --all
+    Stop {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        line_id: Option<i64>,
+        #[arg(long)]
+        all: bool,
+    },
+    /// Resume a stopped line
+    Resume { line_id: i64 },
+    /// Stop a running line and start another one at the same instant
+    Switch { from_line_id: i64, to_line_id: i64 },
+    /// Archive a tracker, hiding it from the list but not from reports
+    Archive { tracker_id: i64 },
+    /// Bring an archived tracker back to the list
+    Unarchive { tracker_id: i64 },
+    /// Delete a tracker and its lines
+    Delete { tracker_id: i64 },
+    /// Delete a single line
+    DeleteLine { line_id: i64 },
+    /// Show the currently running lines
+    Status,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    Manual,
+    Recent,
+    Name,
+    Total,
+}
+
+impl From<SortArg> for TrackerSort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::Manual => TrackerSort::Manual,
+            SortArg::Recent => TrackerSort::Recent,
+            SortArg::Name => TrackerSort::Name,
+            SortArg::Total => TrackerSort::Total,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let database_file_path = match cli.db {
+        Some(path) => path,
+        None => database::default_database_path()?,
+    };
+    let pool = database::connect_database(&database_file_path).await?;
+    let service = TrackerService::create_service(pool);
+
+    match cli.command {
+        Command::List { archived, sort } => {
+            let query = TrackerQueryDto {
+                include_archived: archived,
+                sort: sort.into(),
+            };
+            let trackers = service.get_trackers(query).await?;
+
+            if cli.json {
+                return print_json(&trackers);
+            }
+
+            for tracker in trackers {
+                let total: i64 = tracker.lines.iter().map(line_seconds).sum();
+                println!(
+                    "[{}] {} ({}){}",
+                    tracker.id,
+                    tracker.label,
+                    format_seconds(total),
+                    if tracker.is_archived {
+                        " [archived]"
+                    } else {
+                        ""
+                    }
+                );
+
+                for line in tracker.lines {
+                    print_line(&line);
+                }
+            }
+        }
+        Command::Create { label, client } => {
+            let dto = TrackerEntryCreateDto {
+                client_id: client,
+                label,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let tracker = service.create_tracker(dto).await?;
+
+            if cli.json {
+                return print_json(&tracker);
+            }
+
+            println!("Created tracker [{}] {}", tracker.id, tracker.label);
+        }
+        Command::Start {
+            tracker_id,
+            description,
+        } => {
+            let dto = TrackerEntryLineCreateDto {
+                entry_id: tracker_id,
+                desc: description,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let line = service.start_tracking(dto).await?;
+
+            if cli.json {
+                return print_json(&line);
+            }
+
+            println!("Started line [{}] {}", line.id, line.desc);
+        }
+        Command::Stop { line_id, all } => {
+            let lines = if all {
+                service.stop_all_tracking().await?
+            } else if let Some(line_id) = line_id {
+                vec![service.stop_tracking(line_id).await?]
+            } else {
+                Vec::new()
+            };
+
+            if cli.json {
+                return print_json(&lines);
+            }
+
+            for line in lines {
+                println!("Stopped line [{}] {}", line.id, line.desc);
+            }
+        }
+        Command::Resume { line_id } => {
+            let line = service.resume_tracking(line_id).await?;
+
+            if cli.json {
+                return print_json(&line);
+            }
+
+            println!("Resumed line [{}] {}", line.id, line.desc);
+        }
+        Command::Switch {
+            from_line_id,
+            to_line_id,
+        } => {
+            let switched = service.switch_tracking(from_line_id, to_line_id).await?;
+
+            if cli.json {
+                return print_json(&switched);
+            }
+
+            for line in &switched.stopped {
+                println!("Stopped line [{}] {}", line.id, line.desc);
+            }
+            println!(
+                "Started line [{}] {}",
+                switched.started.id, switched.started.desc
+            );
+        }
+        Command::Archive { tracker_id } => {
+            let tracker = service.archive_tracker(tracker_id).await?;
+
+            if cli.json {
+                return print_json(&tracker);
+            }
+
+            println!("Archived tracker [{}] {}", tracker.id, tracker.label);
+        }
+        Command::Unarchive { tracker_id } => {
+            let tracker = service.unarchive_tracker(tracker_id).await?;
+
+            if cli.json {
+                return print_json(&tracker);
+            }
+
+            println!("Unarchived tracker [{}] {}", tracker.id, tracker.label);
+        }
+        Command::Delete { tracker_id } => {
+            let dto = TrackerEntryDeleteDto { id: tracker_id };
+            service.delete_tracker(dto).await?;
+
+            if !cli.json {
+                println!("Deleted tracker [{}]", tracker_id);
+            }
+        }
+        Command::DeleteLine { line_id } => {
+            let dto = TrackerEntryLineDeleteDto { id: line_id };
+            service.remove_tracked(dto).await?;
+
+            if !cli.json {
+                println!("Deleted line [{}]", line_id);
+            }
+        }
+        Command::Status => {
+            let active_lines: Vec<TrackerEntryLineViewDto> = service
+                .get_trackers(TrackerQueryDto {
+                    include_archived: true,
+                    ..Default::default()
+                })
+                .await?
+                .into_iter()
+                .flat_map(|tracker| tracker.lines)
+                .filter(|line| line.durations.iter().any(|d| d.ended_at.is_none()))
+                .collect();
+
+            if cli.json {
+                return print_json(&active_lines);
+            }
+
+            if active_lines.is_empty() {
+                println!("No active tracking");
+            }
+
+            for line in active_lines {
+                print_line(&line);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_line(line: &TrackerEntryLineViewDto) {
+    let active = line.durations.iter().any(|d| d.ended_at.is_none());
+    let last_started = line
+        .durations
+        .iter()
+        .map(|d| d.started_at)
+        .max()
+        .map(|started_at| {
+            started_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    println!(
+        "  {} [{}] {} ({}, last started {})",
+        if active { "*" } else { " " },
+        line.id,
+        line.desc,
+        format_seconds(line_seconds(line)),
+        last_started
+    );
+}
+
+fn line_seconds(line: &TrackerEntryLineViewDto) -> i64 {
+    let now = Utc::now();
+
+    line.durations
+        .iter()
+        .map(|d| (d.ended_at.unwrap_or(now) - d.started_at).num_seconds())
+        .sum()
+}
+
+fn format_seconds(seconds: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
@@ -1,11 +1,31 @@
-use sqlx::{Sqlite, SqlitePool, migrate::MigrateDatabase as _, sqlite::SqliteConnectOptions};
-use std::path::PathBuf;
+use crate::domains::backup::BACKUP_DIR_NAME;
+use sqlx::{
+    Connection as _, Sqlite, SqliteConnection, SqlitePool, migrate::MigrateDatabase as _,
+    sqlite::SqliteConnectOptions,
+};
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
+/// Bundle identifier from `tauri.conf.json`, used to locate the app data
+/// directory outside of a running Tauri app.
+pub const APP_IDENTIFIER: &str = "com.bendi.track-it";
+
+/// File name of the database inside the app data directory.
+pub const DATABASE_FILE_NAME: &str = "trackers.db";
+
 pub async fn initialize_database(
     app_handle: &AppHandle,
 ) -> Result<SqlitePool, Box<dyn std::error::Error + Send + Sync>> {
-    let (database_url, database_file_path) = get_database_path(app_handle)?;
+    let database_file_path = get_database_path(app_handle)?;
+
+    connect_database(&database_file_path).await
+}
+
+/// Opens (and creates if needed) the database at the given path and runs migrations.
+pub async fn connect_database(
+    database_file_path: &Path,
+) -> Result<SqlitePool, Box<dyn std::error::Error + Send + Sync>> {
+    let database_url = format!("sqlite://{}", database_file_path.display());
 
     // Check if database exists, if not create it
     if !Sqlite::database_exists(&database_url).await? {
@@ -15,7 +35,7 @@ pub async fn initialize_database(
 
     // Connect to the database
     let options = SqliteConnectOptions::new()
-        .filename(&database_file_path)
+        .filename(database_file_path)
         .create_if_missing(true);
 
     let pool = SqlitePool::connect_with(options).await?;
@@ -28,9 +48,9 @@ pub async fn initialize_database(
     Ok(pool)
 }
 
-fn get_database_path(
+pub fn get_database_path(
     app_handle: &AppHandle,
-) -> Result<(String, PathBuf), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
     // Get the app data directory from Tauri
     let app_data_dir = app_handle
         .path()
@@ -42,12 +62,106 @@ fn get_database_path(
         .map_err(|e| format!("Failed to create app data directory: {}", e))?;
 
     // Create the database path in the app data directory
-    let db_path = app_data_dir.join("trackers.db");
+    let db_path = app_data_dir.join(DATABASE_FILE_NAME);
+
+    Ok(db_path)
+}
+
+/// Directory the backups of the database at the given path are kept in.
+pub fn backup_dir(database_file_path: &Path) -> PathBuf {
+    database_file_path.with_file_name(BACKUP_DIR_NAME)
+}
+
+/// Copies `source` next to the database at the given path, ready for `replace_database`.
+pub async fn stage_database(
+    database_file_path: &Path,
+    source: &Path,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let staged_path = database_file_path.with_extension("db.restore");
+    tokio::fs::copy(source, &staged_path)
+        .await
+        .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+
+    Ok(staged_path)
+}
 
-    // Create URL format for SQLite
-    let db_url = format!("sqlite://{}", db_path.display());
+/// Opens the staged copy once, migrating it, so a file that is no database or cannot be
+/// brought up to date is refused before anything is replaced.
+pub async fn check_staged_database(
+    staged_path: &Path,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let pool = connect_database(staged_path).await?;
+    pool.close().await;
+
+    Ok(())
+}
+
+/// Takes an exclusive lock on the database at the given path, so no other program, like
+/// the command line tool, is reading or writing it while it is replaced. Fails when the lock
+/// cannot be had within the busy timeout.
+pub async fn lock_database(
+    database_file_path: &Path,
+) -> Result<SqliteConnection, Box<dyn std::error::Error + Send + Sync>> {
+    let options = SqliteConnectOptions::new().filename(database_file_path);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    // Folds a write-ahead log back into the database, a reader still on it keeps it busy
+    let (busy, _, _): (i64, i64, i64) = sqlx::query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+        .fetch_one(&mut conn)
+        .await?;
+    if busy != 0 {
+        return Err("The database is in use by another program".into());
+    }
+
+    sqlx::query("BEGIN EXCLUSIVE")
+        .execute(&mut conn)
+        .await
+        .map_err(|e| format!("The database is in use by another program: {}", e))?;
 
-    Ok((db_url, db_path))
+    Ok(conn)
+}
+
+/// Puts the staged copy in place of the database at the given path and opens it, migrating
+/// it if it was taken by an older version. `lock` comes from `lock_database` and is held
+/// until the copy is in place. Every pool on the old database must be closed first.
+pub async fn replace_database(
+    database_file_path: &Path,
+    staged_path: &Path,
+    lock: SqliteConnection,
+) -> Result<SqlitePool, Box<dyn std::error::Error + Send + Sync>> {
+    // The journal files belong to the old database and would be replayed into the new one
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let mut journal_path = database_file_path.as_os_str().to_owned();
+        journal_path.push(suffix);
+        match tokio::fs::remove_file(&journal_path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    tokio::fs::rename(staged_path, database_file_path).await?;
+    log::info!("Restored database at {}", database_file_path.display());
+
+    // Nothing was written under the lock, closing it leaves the new file alone
+    lock.close().await?;
+
+    connect_database(database_file_path).await
+}
+
+/// Resolves the database path the desktop app uses without an `AppHandle`.
+///
+/// Mirrors Tauri's `app_data_dir`, which is the platform data directory joined
+/// with the bundle identifier.
+pub fn default_database_path() -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let app_data_dir = dirs::data_dir()
+        .ok_or("Failed to resolve the platform data directory")?
+        .join(APP_IDENTIFIER);
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir.join(DATABASE_FILE_NAME))
 }
 
 pub async fn truncate_tables(
@@ -57,19 +171,44 @@ pub async fn truncate_tables(
     log::info!("Truncating tables...");
 
     // Delete tables in correct order because of foreign key constraints
+    sqlx::query("DELETE FROM custom_field_value")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM custom_field")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM tracker_entry_line_tag")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM tag").execute(pool).await?;
+    sqlx::query("DELETE FROM history_entry")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM forced_stop").execute(pool).await?;
+    sqlx::query("DELETE FROM timebox_break")
+        .execute(pool)
+        .await?;
     sqlx::query("DELETE FROM tracker_entry_line_duration")
         .execute(pool)
         .await?;
+    sqlx::query("DELETE FROM invoice_line")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM invoice").execute(pool).await?;
     sqlx::query("DELETE FROM tracker_entry_line")
         .execute(pool)
         .await?;
     sqlx::query("DELETE FROM tracker_entry")
         .execute(pool)
         .await?;
+    // The default client stays, new trackers are assigned to it
+    sqlx::query("DELETE FROM client WHERE is_default = 0")
+        .execute(pool)
+        .await?;
 
     // Reset auto-increment counters
     sqlx::query(
-        "DELETE FROM sqlite_sequence WHERE name IN ('tracker_entry', 'tracker_entry_line', 'tracker_entry_line_duration')",
+        "DELETE FROM sqlite_sequence WHERE name IN ('tracker_entry', 'tracker_entry_line', 'tracker_entry_line_duration', 'tag', 'invoice', 'invoice_line', 'custom_field', 'custom_field_value', 'timebox_break', 'forced_stop', 'history_entry')",
     )
     .execute(pool)
     .await?;
@@ -1 +1,18 @@
+pub mod backup;
+pub mod billing;
+pub mod budget;
+pub mod client;
+pub mod custom_field;
+pub mod export;
+pub mod history;
+pub mod idle;
+pub mod import;
+pub mod invoice;
+pub mod recovery;
+pub mod report;
+pub mod schedule;
+pub mod settings;
+pub mod tag;
+pub mod timebox;
 pub mod tracker;
+pub mod trash;
@@ -0,0 +1,25 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod backup_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    BACKUP_DIR_NAME, BACKUP_INTERVAL, BACKUP_KEEP_SETTING_KEY, BACKUP_POLL_INTERVAL, Backup,
+    BackupReason, DEFAULT_BACKUP_KEEP,
+};
+pub use domain::repository::BackupRepositoryTrait;
+pub use domain::service::BackupServiceTrait;
+pub use dto::backup_dto::*;
+pub use infra::impl_repository::BackupRepository;
+pub use infra::impl_service::BackupService;
@@ -0,0 +1,118 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Directory next to the database that holds the backups.
+pub const BACKUP_DIR_NAME: &str = "backups";
+/// Setting that holds how many backups of each reason are kept before the oldest are removed.
+pub const BACKUP_KEEP_SETTING_KEY: &str = "backup.keep_count";
+pub const DEFAULT_BACKUP_KEEP: u32 = 10;
+/// How old the newest daily backup may get before the next one is taken.
+pub const BACKUP_INTERVAL: TimeDelta = TimeDelta::days(1);
+pub const BACKUP_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const MAX_BACKUP_KEEP: u32 = 100;
+const FILE_PREFIX: &str = "trackers-";
+const FILE_EXTENSION: &str = ".db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+pub fn validate_keep_count(count: u32) -> Result<(), String> {
+    if !(1..=MAX_BACKUP_KEEP).contains(&count) {
+        return Err(format!(
+            "Between 1 and {} backups can be kept",
+            MAX_BACKUP_KEEP
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupReason {
+    Startup,
+    Daily,
+    BeforeTruncate,
+    BeforePurge,
+    BeforeRestore,
+}
+
+impl BackupReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupReason::Startup => "startup",
+            BackupReason::Daily => "daily",
+            BackupReason::BeforeTruncate => "before_truncate",
+            BackupReason::BeforePurge => "before_purge",
+            BackupReason::BeforeRestore => "before_restore",
+        }
+    }
+}
+
+impl FromStr for BackupReason {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "startup" => Ok(BackupReason::Startup),
+            "daily" => Ok(BackupReason::Daily),
+            "before_truncate" => Ok(BackupReason::BeforeTruncate),
+            "before_purge" => Ok(BackupReason::BeforePurge),
+            "before_restore" => Ok(BackupReason::BeforeRestore),
+            _ => Err(format!("Unknown backup reason '{}'", value)),
+        }
+    }
+}
+
+/// A copy of the database in the backup directory. Its name records when and why it was
+/// taken, so the directory alone describes every backup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub file_name: String,
+    pub reason: BackupReason,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+}
+
+impl Backup {
+    pub fn file_name_for(reason: BackupReason, created_at: DateTime<Utc>) -> String {
+        format!(
+            "{}{}-{}{}",
+            FILE_PREFIX,
+            created_at.format(TIMESTAMP_FORMAT),
+            reason.as_str(),
+            FILE_EXTENSION
+        )
+    }
+
+    /// Reads a backup back from its file name, `None` for files that are not backups.
+    pub fn from_file_name(file_name: &str, size_bytes: u64) -> Option<Self> {
+        let stem = file_name
+            .strip_prefix(FILE_PREFIX)?
+            .strip_suffix(FILE_EXTENSION)?;
+        let (timestamp, reason) = stem.split_once('-')?;
+
+        let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+            .ok()?
+            .and_utc();
+
+        Some(Self {
+            file_name: file_name.to_string(),
+            reason: reason.parse().ok()?,
+            created_at,
+            size_bytes,
+        })
+    }
+}
+
+impl fmt::Display for Backup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Backup(file_name: {}, reason: {}, size_bytes: {})",
+            self.file_name,
+            self.reason.as_str(),
+            self.size_bytes
+        )
+    }
+}
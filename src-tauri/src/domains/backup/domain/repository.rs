@@ -0,0 +1,13 @@
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+pub trait BackupRepositoryTrait {
+    /// Writes a consistent copy of the live database to `path`, which must not exist yet.
+    fn vacuum_into<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
@@ -0,0 +1,45 @@
+use crate::{
+    domains::backup::{domain::model::BackupReason, dto::backup_dto::BackupDto},
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{future::Future, path::PathBuf, pin::Pin, sync::Arc};
+
+pub trait BackupServiceTrait: Send + Sync {
+    /// Keeps the backups of the database behind `pool` in `backup_dir`.
+    fn create_service(pool: SqlitePool, backup_dir: PathBuf) -> Arc<dyn BackupServiceTrait>
+    where
+        Self: Sized;
+
+    /// Takes a backup now and removes the oldest ones past the number to keep.
+    fn create_backup(
+        &self,
+        reason: BackupReason,
+    ) -> Pin<Box<dyn Future<Output = Result<BackupDto, AppError>> + Send + '_>>;
+
+    /// Takes the daily backup once the newest daily backup is older than a day.
+    fn backup_if_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<BackupDto>, AppError>> + Send + '_>>;
+
+    /// Returns the backups, newest first.
+    fn list_backups(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<BackupDto>, AppError>> + Send + '_>>;
+
+    /// Resolves a backup listed by `list_backups` to its file.
+    fn get_backup_path(
+        &self,
+        file_name: String,
+    ) -> Pin<Box<dyn Future<Output = Result<PathBuf, AppError>> + Send + '_>>;
+
+    fn get_keep_count(&self) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+
+    /// Stores how many backups of each reason to keep and removes any past it right away.
+    fn set_keep_count(
+        &self,
+        count: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,22 @@
+use crate::domains::backup::{Backup, BackupReason};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupDto {
+    pub file_name: String,
+    pub reason: BackupReason,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+}
+
+impl From<Backup> for BackupDto {
+    fn from(backup: Backup) -> Self {
+        Self {
+            file_name: backup.file_name,
+            reason: backup.reason,
+            created_at: backup.created_at,
+            size_bytes: backup.size_bytes,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::domains::backup::BackupRepositoryTrait;
+use sqlx::SqliteConnection;
+use std::{future::Future, path::PathBuf};
+
+pub struct BackupRepository;
+
+impl BackupRepositoryTrait for BackupRepository {
+    fn vacuum_into<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        path: PathBuf,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query("VACUUM INTO ?")
+                .bind(path.to_string_lossy().into_owned())
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
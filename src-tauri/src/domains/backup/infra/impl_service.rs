@@ -0,0 +1,217 @@
+use crate::{
+    domains::{
+        backup::{
+            BACKUP_INTERVAL, BACKUP_KEEP_SETTING_KEY, Backup, BackupReason, BackupRepositoryTrait,
+            BackupServiceTrait, DEFAULT_BACKUP_KEEP, domain::model::validate_keep_count,
+            dto::backup_dto::BackupDto, infra::impl_repository::BackupRepository,
+        },
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{collections::HashMap, future::Future, io::ErrorKind, path::PathBuf, sync::Arc};
+
+pub struct BackupService {
+    pool: SqlitePool,
+    backup_dir: PathBuf,
+    repo: Arc<dyn BackupRepositoryTrait + Send + Sync>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+}
+
+impl BackupService {
+    async fn keep_count(&self, conn: &mut SqliteConnection) -> Result<u32, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, BACKUP_KEEP_SETTING_KEY)
+            .await?
+        else {
+            return Ok(DEFAULT_BACKUP_KEEP);
+        };
+
+        Ok(setting.value.parse().unwrap_or_else(|e| {
+            log::warn!(
+                "Invalid backup count '{}': {}, keeping {} backups",
+                setting.value,
+                e,
+                DEFAULT_BACKUP_KEEP
+            );
+            DEFAULT_BACKUP_KEEP
+        }))
+    }
+
+    /// The backups in the backup directory, newest first. Other files there are ignored.
+    async fn backups(&self) -> Result<Vec<Backup>, AppError> {
+        let mut backups = Vec::new();
+
+        let mut dir = match tokio::fs::read_dir(&self.backup_dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(backups),
+            Err(e) => return Err(e.into()),
+        };
+        while let Some(file) = dir.next_entry().await? {
+            let metadata = file.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            let file_name = file.file_name();
+            if let Some(backup) = file_name
+                .to_str()
+                .and_then(|name| Backup::from_file_name(name, metadata.len()))
+            {
+                backups.push(backup);
+            }
+        }
+
+        backups.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| b.file_name.cmp(&a.file_name))
+        });
+
+        Ok(backups)
+    }
+
+    /// Removes the oldest backups past the number to keep. Each reason is counted on its
+    /// own, so frequent restarts cannot push out the daily backups.
+    async fn rotate(&self, conn: &mut SqliteConnection) -> Result<(), AppError> {
+        let keep = self.keep_count(conn).await? as usize;
+
+        let mut kept: HashMap<BackupReason, usize> = HashMap::new();
+        for backup in self.backups().await? {
+            let count = kept.entry(backup.reason).or_default();
+            if *count < keep {
+                *count += 1;
+                continue;
+            }
+
+            tokio::fs::remove_file(self.backup_dir.join(&backup.file_name)).await?;
+            log::info!("Removed old {}", backup);
+        }
+
+        Ok(())
+    }
+}
+
+impl BackupServiceTrait for BackupService {
+    fn create_service(pool: SqlitePool, backup_dir: PathBuf) -> Arc<dyn BackupServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            backup_dir,
+            repo: Arc::new(BackupRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+        })
+    }
+
+    fn create_backup(
+        &self,
+        reason: BackupReason,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<BackupDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            tokio::fs::create_dir_all(&self.backup_dir).await?;
+
+            let created_at = Utc::now();
+            let file_name = Backup::file_name_for(reason, created_at);
+            let path = self.backup_dir.join(&file_name);
+
+            let mut conn = self.pool.acquire().await?;
+
+            self.repo.vacuum_into(&mut conn, path.clone()).await?;
+            let backup = Backup {
+                file_name,
+                reason,
+                created_at,
+                size_bytes: tokio::fs::metadata(&path).await?.len(),
+            };
+            log::info!("Created {}", backup);
+
+            self.rotate(&mut conn).await?;
+
+            Ok(BackupDto::from(backup))
+        })
+    }
+
+    fn backup_if_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Option<BackupDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            // Only daily backups count, they are rotated apart from the others
+            if self
+                .backups()
+                .await?
+                .iter()
+                .find(|backup| backup.reason == BackupReason::Daily)
+                .is_some_and(|newest| now - newest.created_at < BACKUP_INTERVAL)
+            {
+                return Ok(None);
+            }
+
+            Ok(Some(self.create_backup(BackupReason::Daily).await?))
+        })
+    }
+
+    fn list_backups(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<BackupDto>, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let backups = self.backups().await?;
+
+            Ok(backups.into_iter().map(BackupDto::from).collect())
+        })
+    }
+
+    fn get_backup_path(
+        &self,
+        file_name: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<PathBuf, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            // Only names found in the directory are accepted, so no path can point elsewhere
+            let backup = self
+                .backups()
+                .await?
+                .into_iter()
+                .find(|backup| backup.file_name == file_name)
+                .ok_or_else(|| AppError::NotFound(format!("Backup '{}' not found", file_name)))?;
+
+            Ok(self.backup_dir.join(backup.file_name))
+        })
+    }
+
+    fn get_keep_count(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.keep_count(&mut conn).await
+        })
+    }
+
+    fn set_keep_count(
+        &self,
+        count: u32,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            validate_keep_count(count).map_err(AppError::ValidationError)?;
+
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(BACKUP_KEEP_SETTING_KEY, count.to_string()),
+                )
+                .await?;
+            self.rotate(&mut conn).await?;
+
+            Ok(count)
+        })
+    }
+}
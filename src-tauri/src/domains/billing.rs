@@ -0,0 +1,24 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod billing_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    ClientRate, EntryRate, LineRate, billable_amount, billable_rate, parse_rate, validate_currency,
+};
+pub use domain::repository::BillingRepositoryTrait;
+pub use domain::service::BillingServiceTrait;
+pub use dto::billing_dto::*;
+pub use infra::impl_repository::BillingRepository;
+pub use infra::impl_service::BillingService;
@@ -0,0 +1,125 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::{fmt, str::FromStr};
+
+/// Amounts are rounded to cents, once per interval.
+const AMOUNT_DECIMAL_PLACES: u32 = 2;
+const MAX_RATE_DECIMAL_PLACES: u32 = 4;
+const SECONDS_PER_HOUR: i64 = 3600;
+
+/// The rate and currency set on a client.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct ClientRate {
+    pub client_id: i64,
+    pub hourly_rate: Option<String>,
+    pub currency: Option<String>,
+}
+
+/// The rate and currency of a tracker, both its own and the ones it ends up billing with.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct EntryRate {
+    pub entry_id: i64,
+    pub rate_override: Option<String>,
+    pub currency_override: Option<String>,
+    /// The tracker's rate, or its client's when it has none.
+    pub hourly_rate: Option<String>,
+    /// The currency of the same rate, the tracker's or its client's.
+    pub currency: Option<String>,
+}
+
+/// The billing settings of a line, with the rate resolved through its tracker and client.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct LineRate {
+    pub line_id: i64,
+    pub entry_id: i64,
+    pub is_billable: bool,
+    pub rate_override: Option<String>,
+    /// The line's rate, else its tracker's, else its client's.
+    pub hourly_rate: Option<String>,
+    /// Lines never override the currency, this is the one of the tracker's rate when it has
+    /// one, else the client's.
+    pub currency: Option<String>,
+}
+
+impl LineRate {
+    /// Returns the rate and currency the line bills at, if it is billable and both are known.
+    pub fn billable_rate(&self) -> Option<(Decimal, String)> {
+        billable_rate(self.is_billable, &self.hourly_rate, &self.currency)
+    }
+}
+
+impl fmt::Display for LineRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LineRate(line_id: {}, entry_id: {}, is_billable: {}, hourly_rate: {:?}, currency: {:?})",
+            self.line_id, self.entry_id, self.is_billable, self.hourly_rate, self.currency
+        )
+    }
+}
+
+/// Parses a rate as stored in the database.
+///
+/// Rates are only written after validation, so a value that fails to parse is logged and
+/// treated as missing rather than failing the whole view.
+pub fn parse_rate(value: &Option<String>) -> Option<Decimal> {
+    let value = value.as_deref()?;
+
+    match Decimal::from_str(value) {
+        Ok(rate) => Some(rate),
+        Err(e) => {
+            log::warn!("Ignoring invalid hourly rate '{}': {}", value, e);
+            None
+        }
+    }
+}
+
+/// Resolves the rate and currency to bill at, `None` when nothing should be billed.
+pub fn billable_rate(
+    is_billable: bool,
+    hourly_rate: &Option<String>,
+    currency: &Option<String>,
+) -> Option<(Decimal, String)> {
+    if !is_billable {
+        return None;
+    }
+
+    Some((parse_rate(hourly_rate)?, currency.clone()?))
+}
+
+/// Amount earned for `seconds` at an hourly `rate`, rounded half away from zero to cents.
+pub fn billable_amount(rate: Decimal, seconds: i64) -> Decimal {
+    (rate * Decimal::from(seconds) / Decimal::from(SECONDS_PER_HOUR)).round_dp_with_strategy(
+        AMOUNT_DECIMAL_PLACES,
+        RoundingStrategy::MidpointAwayFromZero,
+    )
+}
+
+/// Checks an hourly rate entered by the user and returns it in its shortest form.
+pub fn validate_rate(rate: Decimal) -> Result<Decimal, String> {
+    if rate.is_sign_negative() && !rate.is_zero() {
+        return Err("Hourly rate must not be negative".to_string());
+    }
+
+    let rate = rate.normalize();
+    if rate.scale() > MAX_RATE_DECIMAL_PLACES {
+        return Err(format!(
+            "Hourly rate must not have more than {} decimal places",
+            MAX_RATE_DECIMAL_PLACES
+        ));
+    }
+
+    Ok(rate)
+}
+
+/// Checks a currency code and returns it upper-cased.
+pub fn validate_currency(currency: &str) -> Result<String, String> {
+    let currency = currency.trim();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!(
+            "Currency '{}' is not a three-letter ISO 4217 code",
+            currency
+        ));
+    }
+
+    Ok(currency.to_ascii_uppercase())
+}
@@ -0,0 +1,70 @@
+use crate::domains::billing::domain::model::{ClientRate, EntryRate, LineRate};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait BillingRepositoryTrait {
+    fn get_client_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<ClientRate>>> + Send + 'a>>;
+
+    fn get_client_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<ClientRate>>> + Send + 'a>>;
+
+    /// Returns the rates of all live trackers.
+    fn get_entry_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<EntryRate>>> + Send + 'a>>;
+
+    fn get_entry_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryRate>>> + Send + 'a>>;
+
+    /// Returns the rates of all live lines of live trackers.
+    fn get_line_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineRate>>> + Send + 'a>>;
+
+    fn get_line_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<LineRate>>> + Send + 'a>>;
+
+    fn set_client_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+        hourly_rate: Option<Decimal>,
+        currency: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn set_entry_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        hourly_rate: Option<Decimal>,
+        currency: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn set_line_billing<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        is_billable: bool,
+        hourly_rate: Option<Decimal>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
@@ -0,0 +1,34 @@
+use crate::{
+    domains::billing::dto::billing_dto::{
+        ClientRateDto, ClientRateUpdateDto, LineBillingDto, LineBillingUpdateDto, TrackerRateDto,
+        TrackerRateUpdateDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait BillingServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn BillingServiceTrait>
+    where
+        Self: Sized;
+
+    fn get_client_rates(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ClientRateDto>, AppError>> + Send + '_>>;
+
+    fn set_client_rate(
+        &self,
+        dto: ClientRateUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientRateDto, AppError>> + Send + '_>>;
+
+    fn set_tracker_rate(
+        &self,
+        dto: TrackerRateUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerRateDto, AppError>> + Send + '_>>;
+
+    fn set_line_billing(
+        &self,
+        dto: LineBillingUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<LineBillingDto, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,100 @@
+use crate::domains::billing::{ClientRate, EntryRate, LineRate, domain::model::parse_rate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientRateDto {
+    pub client_id: i64,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+}
+
+impl From<ClientRate> for ClientRateDto {
+    fn from(rate: ClientRate) -> Self {
+        Self {
+            client_id: rate.client_id,
+            hourly_rate: parse_rate(&rate.hourly_rate),
+            currency: rate.currency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerRateDto {
+    pub entry_id: i64,
+    /// Set on the tracker itself, `None` when it inherits from its client.
+    pub rate_override: Option<Decimal>,
+    pub currency_override: Option<String>,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+}
+
+impl From<EntryRate> for TrackerRateDto {
+    fn from(rate: EntryRate) -> Self {
+        Self {
+            entry_id: rate.entry_id,
+            rate_override: parse_rate(&rate.rate_override),
+            currency_override: rate.currency_override,
+            hourly_rate: parse_rate(&rate.hourly_rate),
+            currency: rate.currency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineBillingDto {
+    pub line_id: i64,
+    pub entry_id: i64,
+    pub is_billable: bool,
+    /// Set on the line itself, `None` when it inherits from its tracker or client.
+    pub rate_override: Option<Decimal>,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+}
+
+impl From<LineRate> for LineBillingDto {
+    fn from(rate: LineRate) -> Self {
+        Self {
+            line_id: rate.line_id,
+            entry_id: rate.entry_id,
+            is_billable: rate.is_billable,
+            rate_override: parse_rate(&rate.rate_override),
+            hourly_rate: parse_rate(&rate.hourly_rate),
+            currency: rate.currency,
+        }
+    }
+}
+
+/// Clearing both fields makes the client unbilled, a rate always needs a currency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientRateUpdateDto {
+    pub client_id: i64,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+}
+
+/// Rate and currency are set or cleared together, cleared ones fall back to the client's.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerRateUpdateDto {
+    pub entry_id: i64,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+}
+
+/// A cleared rate falls back to the tracker's or the client's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineBillingUpdateDto {
+    pub line_id: i64,
+    pub is_billable: bool,
+    pub hourly_rate: Option<Decimal>,
+}
+
+impl Default for LineBillingUpdateDto {
+    fn default() -> Self {
+        Self {
+            line_id: 0,
+            is_billable: true,
+            hourly_rate: None,
+        }
+    }
+}
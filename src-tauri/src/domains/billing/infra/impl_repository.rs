@@ -0,0 +1,242 @@
+use crate::domains::billing::{
+    BillingRepositoryTrait,
+    domain::model::{ClientRate, EntryRate, LineRate},
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct BillingRepository;
+
+impl BillingRepositoryTrait for BillingRepository {
+    fn get_client_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<ClientRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let rates = sqlx::query_as::<_, ClientRate>(
+                r#"
+                SELECT id AS client_id, hourly_rate, currency
+                FROM client
+                WHERE is_deleted = 0
+                ORDER BY id
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(rates)
+        })
+    }
+
+    fn get_client_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<ClientRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let rate = sqlx::query_as::<_, ClientRate>(
+                r#"
+                SELECT id AS client_id, hourly_rate, currency
+                FROM client
+                WHERE id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(client_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(rate)
+        })
+    }
+
+    fn get_entry_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<EntryRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            // Rate and currency come as a pair, both from the tracker or both from the client
+            let rates = sqlx::query_as::<_, EntryRate>(
+                r#"
+                SELECT e.id AS entry_id, e.hourly_rate AS rate_override,
+                    e.currency AS currency_override,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.hourly_rate
+                        ELSE c.hourly_rate END AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency
+                FROM tracker_entry e
+                LEFT JOIN client c ON c.id = e.client_id
+                WHERE e.is_deleted = 0
+                ORDER BY e.id
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(rates)
+        })
+    }
+
+    fn get_entry_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let rate = sqlx::query_as::<_, EntryRate>(
+                r#"
+                SELECT e.id AS entry_id, e.hourly_rate AS rate_override,
+                    e.currency AS currency_override,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.hourly_rate
+                        ELSE c.hourly_rate END AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency
+                FROM tracker_entry e
+                LEFT JOIN client c ON c.id = e.client_id
+                WHERE e.id = ? AND e.is_deleted = 0
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(rate)
+        })
+    }
+
+    fn get_line_rates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            // A line's own rate is billed in the currency of the pair it would otherwise use
+            let rates = sqlx::query_as::<_, LineRate>(
+                r#"
+                SELECT l.id AS line_id, l.entry_id, l.is_billable,
+                    l.hourly_rate AS rate_override,
+                    coalesce(l.hourly_rate, e.hourly_rate, c.hourly_rate) AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                LEFT JOIN client c ON c.id = e.client_id
+                WHERE l.is_deleted = 0 AND e.is_deleted = 0
+                ORDER BY l.id
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(rates)
+        })
+    }
+
+    fn get_line_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<LineRate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let rate = sqlx::query_as::<_, LineRate>(
+                r#"
+                SELECT l.id AS line_id, l.entry_id, l.is_billable,
+                    l.hourly_rate AS rate_override,
+                    coalesce(l.hourly_rate, e.hourly_rate, c.hourly_rate) AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                LEFT JOIN client c ON c.id = e.client_id
+                WHERE l.id = ? AND l.is_deleted = 0
+                "#,
+            )
+            .bind(line_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(rate)
+        })
+    }
+
+    fn set_client_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+        hourly_rate: Option<Decimal>,
+        currency: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE client
+                SET hourly_rate = ?, currency = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(hourly_rate.map(|rate| rate.to_string()))
+            .bind(currency)
+            .bind(updated_at)
+            .bind(client_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_entry_rate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        hourly_rate: Option<Decimal>,
+        currency: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry
+                SET hourly_rate = ?, currency = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(hourly_rate.map(|rate| rate.to_string()))
+            .bind(currency)
+            .bind(updated_at)
+            .bind(entry_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_line_billing<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        is_billable: bool,
+        hourly_rate: Option<Decimal>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line
+                SET is_billable = ?, hourly_rate = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(is_billable)
+            .bind(hourly_rate.map(|rate| rate.to_string()))
+            .bind(updated_at)
+            .bind(line_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
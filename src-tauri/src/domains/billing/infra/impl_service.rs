@@ -0,0 +1,216 @@
+use crate::{
+    domains::{
+        billing::{
+            BillingRepositoryTrait, BillingServiceTrait,
+            domain::model::{validate_currency, validate_rate},
+            dto::billing_dto::{
+                ClientRateDto, ClientRateUpdateDto, LineBillingDto, LineBillingUpdateDto,
+                TrackerRateDto, TrackerRateUpdateDto,
+            },
+            infra::impl_repository::BillingRepository,
+        },
+        history::HistoryRecorder,
+        tracker::{TrackerRepository, TrackerRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sqlx::SqlitePool;
+use std::{future::Future, sync::Arc};
+
+pub struct BillingService {
+    pool: SqlitePool,
+    repo: Arc<dyn BillingRepositoryTrait + Send + Sync>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+fn validate_rate_input(
+    hourly_rate: Option<Decimal>,
+    currency: Option<String>,
+) -> Result<(Option<Decimal>, Option<String>), AppError> {
+    let hourly_rate = hourly_rate
+        .map(validate_rate)
+        .transpose()
+        .map_err(AppError::ValidationError)?;
+    let currency = currency
+        .as_deref()
+        .map(validate_currency)
+        .transpose()
+        .map_err(AppError::ValidationError)?;
+
+    Ok((hourly_rate, currency))
+}
+
+fn missing_currency() -> AppError {
+    AppError::ValidationError("An hourly rate needs a currency to bill in".to_string())
+}
+
+impl BillingServiceTrait for BillingService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn BillingServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(BillingRepository {}),
+            tracker_repo: Arc::new(TrackerRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn get_client_rates(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ClientRateDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let rates = self.repo.get_client_rates(&mut conn).await?;
+
+            Ok(rates.into_iter().map(ClientRateDto::from).collect())
+        })
+    }
+
+    fn set_client_rate(
+        &self,
+        dto: ClientRateUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<ClientRateDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let (hourly_rate, currency) = validate_rate_input(dto.hourly_rate, dto.currency)?;
+            if hourly_rate.is_some() && currency.is_none() {
+                return Err(missing_currency());
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            if self
+                .repo
+                .get_client_rate(&mut tx, dto.client_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::NotFound(format!(
+                    "Client with id {} not found",
+                    dto.client_id
+                )));
+            }
+
+            self.repo
+                .set_client_rate(&mut tx, dto.client_id, hourly_rate, currency, Utc::now())
+                .await?;
+
+            let rate = self
+                .repo
+                .get_client_rate(&mut tx, dto.client_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Client with id {} not found", dto.client_id))
+                })?;
+
+            tx.commit().await?;
+
+            Ok(rate.into())
+        })
+    }
+
+    fn set_tracker_rate(
+        &self,
+        dto: TrackerRateUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerRateDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let (hourly_rate, currency) = validate_rate_input(dto.hourly_rate, dto.currency)?;
+            // A half override would mix the tracker's rate or currency with the client's
+            match (&hourly_rate, &currency) {
+                (Some(_), None) => return Err(missing_currency()),
+                (None, Some(_)) => {
+                    return Err(AppError::ValidationError(
+                        "A currency needs an hourly rate to bill at".to_string(),
+                    ));
+                }
+                _ => {}
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .tracker_repo
+                .get_entry(&mut tx, dto.entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", dto.entry_id))
+                })?;
+
+            let before = self.history.capture(&mut tx, vec![entry.id]).await?;
+            self.repo
+                .set_entry_rate(&mut tx, entry.id, hourly_rate, currency, Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Set tracker rate", before, &[])
+                .await?;
+
+            let rate = self
+                .repo
+                .get_entry_rate(&mut tx, entry.id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", entry.id))
+                })?;
+
+            tx.commit().await?;
+
+            Ok(rate.into())
+        })
+    }
+
+    fn set_line_billing(
+        &self,
+        dto: LineBillingUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<LineBillingDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let (hourly_rate, _) = validate_rate_input(dto.hourly_rate, None)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_line_rate(&mut tx, dto.line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", dto.line_id))
+                })?;
+
+            // Lines cannot choose a currency, so one must come from the tracker or the client
+            if hourly_rate.is_some() && line.currency.is_none() {
+                return Err(missing_currency());
+            }
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo
+                .set_line_billing(
+                    &mut tx,
+                    line.line_id,
+                    dto.is_billable,
+                    hourly_rate,
+                    Utc::now(),
+                )
+                .await?;
+            self.history
+                .record(&mut tx, "Set line billing", before, &[])
+                .await?;
+
+            let rate = self
+                .repo
+                .get_line_rate(&mut tx, line.line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", line.line_id))
+                })?;
+
+            tx.commit().await?;
+
+            Ok(rate.into())
+        })
+    }
+}
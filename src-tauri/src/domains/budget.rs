@@ -0,0 +1,24 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod budget_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    BUDGET_EXCEEDED_EVENT, BudgetInterval, BudgetPeriod, EntryBudget, LineEstimate,
+};
+pub use domain::repository::BudgetRepositoryTrait;
+pub use domain::service::BudgetServiceTrait;
+pub use dto::budget_dto::*;
+pub use infra::impl_repository::BudgetRepository;
+pub use infra::impl_service::BudgetService;
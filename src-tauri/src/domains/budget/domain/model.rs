@@ -0,0 +1,183 @@
+use crate::domains::report::start_of_day;
+use chrono::{DateTime, Datelike, Days, Months, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Event sent to the frontend when tracking starts on a tracker with no budget left.
+pub const BUDGET_EXCEEDED_EVENT: &str = "budget-exceeded";
+
+const SECONDS_PER_HOUR: i64 = 3600;
+/// Hundredths of an hour are whole seconds, so hours round-trip exactly.
+const MAX_HOURS_DECIMAL_PLACES: u32 = 2;
+
+/// How often a tracker's budget starts over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    #[default]
+    OneOff,
+    Weekly,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::OneOff => "one_off",
+            BudgetPeriod::Weekly => "weekly",
+            BudgetPeriod::Monthly => "monthly",
+        }
+    }
+
+    /// Returns the local week or month containing `now` as `[start, end)`, `None` for a
+    /// one-off budget which covers all time.
+    pub fn window<Tz: TimeZone>(
+        &self,
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let today = now.with_timezone(tz).date_naive();
+
+        let (start, end) = match self {
+            BudgetPeriod::OneOff => return None,
+            BudgetPeriod::Weekly => {
+                let monday = today
+                    .checked_sub_days(Days::new(today.weekday().num_days_from_monday() as u64))?;
+                (monday, monday.checked_add_days(Days::new(7))?)
+            }
+            BudgetPeriod::Monthly => {
+                let first = today.with_day(1)?;
+                (first, first.checked_add_months(Months::new(1))?)
+            }
+        };
+
+        Some((start_of_day(start, tz), start_of_day(end, tz)))
+    }
+}
+
+impl FromStr for BudgetPeriod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "one_off" => Ok(BudgetPeriod::OneOff),
+            "weekly" => Ok(BudgetPeriod::Weekly),
+            "monthly" => Ok(BudgetPeriod::Monthly),
+            _ => Err(format!("Unknown budget period '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct EntryBudget {
+    pub entry_id: i64,
+    pub budget_seconds: Option<i64>,
+    pub budget_period: Option<String>,
+}
+
+impl EntryBudget {
+    /// Returns the budget and its period, `None` when the tracker has no budget.
+    ///
+    /// Budgets are only written after validation, so an unknown period is logged and
+    /// treated as one-off rather than failing the whole view.
+    pub fn budget(&self) -> Option<(i64, BudgetPeriod)> {
+        let seconds = self.budget_seconds?;
+        let period = match self.budget_period.as_deref().map(BudgetPeriod::from_str) {
+            Some(Ok(period)) => period,
+            Some(Err(e)) => {
+                log::warn!("Ignoring budget period of tracker {}: {}", self.entry_id, e);
+                BudgetPeriod::OneOff
+            }
+            None => BudgetPeriod::OneOff,
+        };
+
+        Some((seconds, period))
+    }
+}
+
+impl fmt::Display for EntryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "EntryBudget(entry_id: {}, budget_seconds: {:?}, budget_period: {:?})",
+            self.entry_id, self.budget_seconds, self.budget_period
+        )
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct LineEstimate {
+    pub line_id: i64,
+    pub entry_id: i64,
+    pub estimate_seconds: Option<i64>,
+}
+
+impl fmt::Display for LineEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LineEstimate(line_id: {}, entry_id: {}, estimate_seconds: {:?})",
+            self.line_id, self.entry_id, self.estimate_seconds
+        )
+    }
+}
+
+/// A tracked interval counted against its tracker's budget and its line's estimate.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct BudgetInterval {
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub line_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl BudgetInterval {
+    /// Seconds spent inside `window`, or in total without one, counting a running interval
+    /// up to `now`.
+    pub fn seconds_within(
+        &self,
+        window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+        now: DateTime<Utc>,
+    ) -> i64 {
+        let mut start = self.started_at;
+        let mut end = self.ended_at.unwrap_or(now);
+        if let Some((from, to)) = window {
+            start = start.max(from);
+            end = end.min(to);
+        }
+
+        (end - start).num_seconds().max(0)
+    }
+}
+
+impl fmt::Display for BudgetInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BudgetInterval(duration_id: {}, entry_id: {}, line_id: {}, started_at: {}, ended_at: {:?})",
+            self.duration_id, self.entry_id, self.line_id, self.started_at, self.ended_at
+        )
+    }
+}
+
+/// Checks a budget or estimate entered in hours and returns it in seconds.
+pub fn hours_to_seconds(hours: Decimal) -> Result<i64, String> {
+    if hours <= Decimal::ZERO {
+        return Err("Hours must be greater than zero".to_string());
+    }
+    if hours.normalize().scale() > MAX_HOURS_DECIMAL_PLACES {
+        return Err(format!(
+            "Hours must not have more than {} decimal places",
+            MAX_HOURS_DECIMAL_PLACES
+        ));
+    }
+
+    i64::try_from(hours * Decimal::from(SECONDS_PER_HOUR))
+        .map_err(|_| "Hours are out of range".to_string())
+}
+
+pub fn seconds_to_hours(seconds: i64) -> Decimal {
+    (Decimal::from(seconds) / Decimal::from(SECONDS_PER_HOUR)).normalize()
+}
@@ -0,0 +1,69 @@
+use crate::domains::budget::domain::model::{BudgetInterval, EntryBudget, LineEstimate};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait BudgetRepositoryTrait {
+    /// Returns the live trackers that have a budget or a line with an estimate.
+    fn get_entry_budgets<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<EntryBudget>>> + Send + 'a>>;
+
+    fn get_entry_budget<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryBudget>>> + Send + 'a>>;
+
+    /// Returns the estimated live lines of live trackers.
+    fn get_line_estimates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineEstimate>>> + Send + 'a>>;
+
+    /// Returns the estimated live lines of the tracker.
+    fn get_line_estimates_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineEstimate>>> + Send + 'a>>;
+
+    fn get_line_estimate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<LineEstimate>>> + Send + 'a>>;
+
+    /// Returns the intervals of live lines of live trackers that have a budget or a line
+    /// with an estimate.
+    fn get_budget_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<BudgetInterval>>> + Send + 'a>>;
+
+    /// Returns the intervals of the tracker's live lines.
+    fn get_budget_intervals_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<BudgetInterval>>> + Send + 'a>>;
+
+    fn set_entry_budget<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        budget_seconds: Option<i64>,
+        budget_period: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn set_line_estimate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        estimate_seconds: Option<i64>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
@@ -0,0 +1,40 @@
+use crate::{
+    domains::budget::dto::budget_dto::{
+        LineEstimateDto, LineEstimateUpdateDto, TrackerBudgetDto, TrackerBudgetUpdateDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait BudgetServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn BudgetServiceTrait>
+    where
+        Self: Sized;
+
+    /// Returns the trackers that have a budget or an estimated line.
+    fn get_tracker_budgets(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TrackerBudgetDto>, AppError>> + Send + '_>>;
+
+    fn get_tracker_budget(
+        &self,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerBudgetDto, AppError>> + Send + '_>>;
+
+    fn set_tracker_budget(
+        &self,
+        dto: TrackerBudgetUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerBudgetDto, AppError>> + Send + '_>>;
+
+    fn set_line_estimate(
+        &self,
+        dto: LineEstimateUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<LineEstimateDto, AppError>> + Send + '_>>;
+
+    /// Returns the tracker's budget status when it has no budget left, `None` otherwise.
+    fn check_budget(
+        &self,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TrackerBudgetDto>, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,136 @@
+use crate::domains::budget::{
+    BudgetInterval, BudgetPeriod, EntryBudget, LineEstimate, domain::model::seconds_to_hours,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Time spent on a line against its estimate, which always covers all time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineEstimateDto {
+    pub line_id: i64,
+    pub entry_id: i64,
+    pub estimate_hours: Option<Decimal>,
+    pub consumed_seconds: i64,
+    /// Negative once the estimate is overrun, `None` without an estimate.
+    pub remaining_seconds: Option<i64>,
+    pub is_exceeded: bool,
+}
+
+impl LineEstimateDto {
+    pub fn compute(
+        estimate: &LineEstimate,
+        intervals: &[BudgetInterval],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let consumed_seconds = intervals
+            .iter()
+            .filter(|interval| interval.line_id == estimate.line_id)
+            .map(|interval| interval.seconds_within(None, now))
+            .sum();
+        let remaining_seconds = estimate
+            .estimate_seconds
+            .map(|estimate_seconds| estimate_seconds - consumed_seconds);
+
+        Self {
+            line_id: estimate.line_id,
+            entry_id: estimate.entry_id,
+            estimate_hours: estimate.estimate_seconds.map(seconds_to_hours),
+            consumed_seconds,
+            remaining_seconds,
+            is_exceeded: remaining_seconds.is_some_and(|remaining| remaining < 0),
+        }
+    }
+}
+
+/// Time spent on a tracker in its current budget period against its budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerBudgetDto {
+    pub entry_id: i64,
+    pub budget_hours: Option<Decimal>,
+    pub period: Option<BudgetPeriod>,
+    /// Bounds of the current week or month, `None` for one-off budgets.
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub consumed_seconds: i64,
+    /// Negative once the budget is overrun, `None` without a budget.
+    pub remaining_seconds: Option<i64>,
+    /// Set once no budget is left, so starting to track runs over it.
+    pub is_exceeded: bool,
+    /// The tracker's lines that have an estimate.
+    pub lines: Vec<LineEstimateDto>,
+}
+
+impl TrackerBudgetDto {
+    /// Works out the budget status from the tracker's intervals, counting running ones
+    /// up to `now`. Intervals of other trackers are ignored.
+    pub fn compute<Tz: TimeZone>(
+        budget: &EntryBudget,
+        estimates: &[LineEstimate],
+        intervals: &[BudgetInterval],
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Self {
+        let intervals: Vec<BudgetInterval> = intervals
+            .iter()
+            .filter(|interval| interval.entry_id == budget.entry_id)
+            .cloned()
+            .collect();
+        let lines = estimates
+            .iter()
+            .filter(|estimate| estimate.entry_id == budget.entry_id)
+            .map(|estimate| LineEstimateDto::compute(estimate, &intervals, now))
+            .collect();
+
+        let Some((budget_seconds, period)) = budget.budget() else {
+            return Self {
+                entry_id: budget.entry_id,
+                budget_hours: None,
+                period: None,
+                period_start: None,
+                period_end: None,
+                consumed_seconds: intervals
+                    .iter()
+                    .map(|interval| interval.seconds_within(None, now))
+                    .sum(),
+                remaining_seconds: None,
+                is_exceeded: false,
+                lines,
+            };
+        };
+
+        let window = period.window(now, tz);
+        let consumed_seconds: i64 = intervals
+            .iter()
+            .map(|interval| interval.seconds_within(window, now))
+            .sum();
+        let remaining_seconds = budget_seconds - consumed_seconds;
+
+        Self {
+            entry_id: budget.entry_id,
+            budget_hours: Some(seconds_to_hours(budget_seconds)),
+            period: Some(period),
+            period_start: window.map(|(start, _)| start),
+            period_end: window.map(|(_, end)| end),
+            consumed_seconds,
+            remaining_seconds: Some(remaining_seconds),
+            is_exceeded: remaining_seconds <= 0,
+            lines,
+        }
+    }
+}
+
+/// Clearing the hours removes the budget.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerBudgetUpdateDto {
+    pub entry_id: i64,
+    pub budget_hours: Option<Decimal>,
+    pub period: BudgetPeriod,
+}
+
+/// Clearing the hours removes the estimate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineEstimateUpdateDto {
+    pub line_id: i64,
+    pub estimate_hours: Option<Decimal>,
+}
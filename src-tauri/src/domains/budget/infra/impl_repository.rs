@@ -0,0 +1,230 @@
+use crate::domains::budget::{
+    BudgetRepositoryTrait,
+    domain::model::{BudgetInterval, EntryBudget, LineEstimate},
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct BudgetRepository;
+
+impl BudgetRepositoryTrait for BudgetRepository {
+    fn get_entry_budgets<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<EntryBudget>>> + Send + 'a>> {
+        Box::pin(async move {
+            let budgets = sqlx::query_as::<_, EntryBudget>(
+                r#"
+                SELECT e.id AS entry_id, e.budget_seconds, e.budget_period
+                FROM tracker_entry e
+                WHERE e.is_deleted = 0
+                    AND (e.budget_seconds IS NOT NULL OR EXISTS (
+                        SELECT 1 FROM tracker_entry_line l
+                        WHERE l.entry_id = e.id AND l.is_deleted = 0
+                            AND l.estimate_seconds IS NOT NULL
+                    ))
+                ORDER BY e.id
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(budgets)
+        })
+    }
+
+    fn get_entry_budget<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryBudget>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let budget = sqlx::query_as::<_, EntryBudget>(
+                r#"
+                SELECT id AS entry_id, budget_seconds, budget_period
+                FROM tracker_entry
+                WHERE id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(budget)
+        })
+    }
+
+    fn get_line_estimates<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineEstimate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let estimates = sqlx::query_as::<_, LineEstimate>(
+                r#"
+                SELECT l.id AS line_id, l.entry_id, l.estimate_seconds
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE l.is_deleted = 0 AND e.is_deleted = 0
+                    AND l.estimate_seconds IS NOT NULL
+                ORDER BY l.id
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(estimates)
+        })
+    }
+
+    fn get_line_estimates_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineEstimate>>> + Send + 'a>> {
+        Box::pin(async move {
+            let estimates = sqlx::query_as::<_, LineEstimate>(
+                r#"
+                SELECT id AS line_id, entry_id, estimate_seconds
+                FROM tracker_entry_line
+                WHERE entry_id = ? AND is_deleted = 0 AND estimate_seconds IS NOT NULL
+                ORDER BY id
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(estimates)
+        })
+    }
+
+    fn get_line_estimate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<LineEstimate>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let estimate = sqlx::query_as::<_, LineEstimate>(
+                r#"
+                SELECT id AS line_id, entry_id, estimate_seconds
+                FROM tracker_entry_line
+                WHERE id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(line_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(estimate)
+        })
+    }
+
+    fn get_budget_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<BudgetInterval>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let intervals = sqlx::query_as::<_, BudgetInterval>(
+                r#"
+                SELECT d.id AS duration_id, l.entry_id, l.id AS line_id,
+                    d.started_at, d.ended_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                    AND (e.budget_seconds IS NOT NULL OR EXISTS (
+                        SELECT 1 FROM tracker_entry_line el
+                        WHERE el.entry_id = e.id AND el.is_deleted = 0
+                            AND el.estimate_seconds IS NOT NULL
+                    ))
+                ORDER BY d.started_at
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(intervals)
+        })
+    }
+
+    fn get_budget_intervals_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<BudgetInterval>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let intervals = sqlx::query_as::<_, BudgetInterval>(
+                r#"
+                SELECT d.id AS duration_id, l.entry_id, l.id AS line_id,
+                    d.started_at, d.ended_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                WHERE l.entry_id = ? AND d.is_deleted = 0 AND l.is_deleted = 0
+                ORDER BY d.started_at
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(intervals)
+        })
+    }
+
+    fn set_entry_budget<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        budget_seconds: Option<i64>,
+        budget_period: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry
+                SET budget_seconds = ?, budget_period = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(budget_seconds)
+            .bind(budget_period)
+            .bind(updated_at)
+            .bind(entry_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_line_estimate<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        estimate_seconds: Option<i64>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line
+                SET estimate_seconds = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(estimate_seconds)
+            .bind(updated_at)
+            .bind(line_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
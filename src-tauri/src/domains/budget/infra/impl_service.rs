@@ -0,0 +1,211 @@
+use crate::{
+    domains::{
+        budget::{
+            BudgetRepositoryTrait, BudgetServiceTrait,
+            domain::model::hours_to_seconds,
+            dto::budget_dto::{
+                LineEstimateDto, LineEstimateUpdateDto, TrackerBudgetDto, TrackerBudgetUpdateDto,
+            },
+            infra::impl_repository::BudgetRepository,
+        },
+        history::HistoryRecorder,
+    },
+    error::AppError,
+};
+use chrono::{Local, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+pub struct BudgetService {
+    pool: SqlitePool,
+    repo: Arc<dyn BudgetRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+impl BudgetService {
+    async fn tracker_budget(
+        &self,
+        conn: &mut SqliteConnection,
+        entry_id: i64,
+    ) -> Result<TrackerBudgetDto, AppError> {
+        let budget = self
+            .repo
+            .get_entry_budget(conn, entry_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Entry with id {} not found", entry_id)))?;
+        let estimates = self
+            .repo
+            .get_line_estimates_for_entry(conn, entry_id)
+            .await?;
+        let intervals = self
+            .repo
+            .get_budget_intervals_for_entry(conn, entry_id)
+            .await?;
+
+        Ok(TrackerBudgetDto::compute(
+            &budget,
+            &estimates,
+            &intervals,
+            Utc::now(),
+            &Local,
+        ))
+    }
+}
+
+impl BudgetServiceTrait for BudgetService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn BudgetServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(BudgetRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn get_tracker_budgets(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<TrackerBudgetDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let budgets = self.repo.get_entry_budgets(&mut conn).await?;
+            let estimates = self.repo.get_line_estimates(&mut conn).await?;
+            let intervals = self.repo.get_budget_intervals(&mut conn).await?;
+
+            let now = Utc::now();
+            Ok(budgets
+                .iter()
+                .map(|budget| {
+                    TrackerBudgetDto::compute(budget, &estimates, &intervals, now, &Local)
+                })
+                .collect())
+        })
+    }
+
+    fn get_tracker_budget(
+        &self,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerBudgetDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.tracker_budget(&mut conn, entry_id).await
+        })
+    }
+
+    fn set_tracker_budget(
+        &self,
+        dto: TrackerBudgetUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerBudgetDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let budget_seconds = dto
+                .budget_hours
+                .map(hours_to_seconds)
+                .transpose()
+                .map_err(AppError::ValidationError)?;
+            let budget_period = budget_seconds.map(|_| dto.period.as_str().to_string());
+
+            let mut tx = self.pool.begin().await?;
+
+            if self
+                .repo
+                .get_entry_budget(&mut tx, dto.entry_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::NotFound(format!(
+                    "Entry with id {} not found",
+                    dto.entry_id
+                )));
+            }
+
+            let before = self.history.capture(&mut tx, vec![dto.entry_id]).await?;
+            self.repo
+                .set_entry_budget(
+                    &mut tx,
+                    dto.entry_id,
+                    budget_seconds,
+                    budget_period,
+                    Utc::now(),
+                )
+                .await?;
+            self.history
+                .record(&mut tx, "Set tracker budget", before, &[])
+                .await?;
+
+            let budget = self.tracker_budget(&mut tx, dto.entry_id).await?;
+
+            tx.commit().await?;
+
+            Ok(budget)
+        })
+    }
+
+    fn set_line_estimate(
+        &self,
+        dto: LineEstimateUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<LineEstimateDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let estimate_seconds = dto
+                .estimate_hours
+                .map(hours_to_seconds)
+                .transpose()
+                .map_err(AppError::ValidationError)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_line_estimate(&mut tx, dto.line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", dto.line_id))
+                })?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo
+                .set_line_estimate(&mut tx, dto.line_id, estimate_seconds, Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Set line estimate", before, &[])
+                .await?;
+
+            let estimate = self
+                .repo
+                .get_line_estimate(&mut tx, dto.line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", dto.line_id))
+                })?;
+            let intervals = self
+                .repo
+                .get_budget_intervals_for_entry(&mut tx, estimate.entry_id)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(LineEstimateDto::compute(&estimate, &intervals, Utc::now()))
+        })
+    }
+
+    fn check_budget(
+        &self,
+        entry_id: i64,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<TrackerBudgetDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let budget = self.tracker_budget(&mut conn, entry_id).await?;
+
+            Ok(budget.is_exceeded.then_some(budget))
+        })
+    }
+}
@@ -0,0 +1,22 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod client_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::Client;
+pub use domain::repository::ClientRepositoryTrait;
+pub use domain::service::ClientServiceTrait;
+pub use dto::client_dto::*;
+pub use infra::impl_repository::ClientRepository;
+pub use infra::impl_service::ClientService;
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct Client {
+    pub id: i64,
+    pub name: String,
+    /// The "Unassigned" client, which collects trackers without a client of their own.
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub is_deleted: bool,
+}
+
+impl Client {
+    pub fn new(id: i64, name: String) -> Self {
+        Self {
+            id,
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: String::new(),
+            is_default: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_deleted: false,
+        }
+    }
+}
+
+impl fmt::Display for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Client(id: {}, name: {}, is_default: {}, created_at: {}, updated_at: {}, is_deleted: {})",
+            self.id, self.name, self.is_default, self.created_at, self.updated_at, self.is_deleted
+        )
+    }
+}
@@ -0,0 +1,51 @@
+use crate::domains::client::domain::model::Client;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait ClientRepositoryTrait {
+    fn create_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>>;
+
+    fn get_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<Client>>> + Send + 'a>>;
+
+    /// Returns the "Unassigned" client created by the migration.
+    fn get_default_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>>;
+
+    fn get_all_clients<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<Client>>> + Send + 'a>>;
+
+    fn update_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>>;
+
+    fn delete_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Moves every tracker of `from`, deleted ones included, to `to`.
+    fn reassign_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: Client,
+        to: Client,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
@@ -0,0 +1,34 @@
+use crate::{
+    domains::client::dto::client_dto::{
+        ClientCreateDto, ClientDeleteDto, ClientUpdateDto, ClientViewDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait ClientServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ClientServiceTrait>
+    where
+        Self: Sized;
+
+    fn create_client(
+        &self,
+        dto: ClientCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientViewDto, AppError>> + Send + '_>>;
+
+    fn get_clients(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ClientViewDto>, AppError>> + Send + '_>>;
+
+    fn update_client(
+        &self,
+        dto: ClientUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientViewDto, AppError>> + Send + '_>>;
+
+    /// Deletes the client and hands its trackers over to the default client.
+    fn delete_client(
+        &self,
+        dto: ClientDeleteDto,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+}
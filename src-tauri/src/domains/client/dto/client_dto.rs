@@ -0,0 +1,47 @@
+use crate::domains::{client::Client, tracker::TrackerEntryViewDto};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientViewDto {
+    pub id: i64,
+    pub name: String,
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Client> for ClientViewDto {
+    fn from(client: Client) -> Self {
+        Self {
+            id: client.id,
+            name: client.name,
+            is_default: client.is_default,
+            created_at: client.created_at,
+            updated_at: client.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientCreateDto {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientUpdateDto {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientDeleteDto {
+    pub id: i64,
+}
+
+/// A client together with its trackers, as returned when grouping trackers by client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientTrackersDto {
+    pub client: ClientViewDto,
+    pub trackers: Vec<TrackerEntryViewDto>,
+}
@@ -0,0 +1,165 @@
+use crate::domains::client::{ClientRepositoryTrait, domain::model::Client};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct ClientRepository;
+
+impl ClientRepositoryTrait for ClientRepository {
+    fn create_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>> {
+        Box::pin(async move {
+            let client = sqlx::query_as::<_, Client>(
+                r#"
+                INSERT INTO client (name, is_default, created_at, updated_at, is_deleted)
+                VALUES (?, ?, ?, ?, ?)
+                RETURNING id, name, is_default, created_at, updated_at, is_deleted
+                "#,
+            )
+            .bind(&client.name)
+            .bind(client.is_default)
+            .bind(client.created_at)
+            .bind(client.updated_at)
+            .bind(client.is_deleted)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(client)
+        })
+    }
+
+    fn get_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<Client>>> + Send + 'a>> {
+        Box::pin(async move {
+            let client = sqlx::query_as::<_, Client>(
+                r#"
+                SELECT id, name, is_default, created_at, updated_at, is_deleted
+                FROM client
+                WHERE id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(client)
+        })
+    }
+
+    fn get_default_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>> {
+        Box::pin(async move {
+            let client = sqlx::query_as::<_, Client>(
+                r#"
+                SELECT id, name, is_default, created_at, updated_at, is_deleted
+                FROM client
+                WHERE is_default = 1
+                ORDER BY id
+                LIMIT 1
+                "#,
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(client)
+        })
+    }
+
+    fn get_all_clients<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<Client>>> + Send + 'a>> {
+        Box::pin(async move {
+            let clients = sqlx::query_as::<_, Client>(
+                r#"
+                SELECT id, name, is_default, created_at, updated_at, is_deleted
+                FROM client
+                WHERE is_deleted = 0
+                ORDER BY is_default DESC, name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(clients)
+        })
+    }
+
+    fn update_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Client>> + Send + 'a>> {
+        Box::pin(async move {
+            let client = sqlx::query_as::<_, Client>(
+                r#"
+                UPDATE client
+                SET name = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, name, is_default, created_at, updated_at, is_deleted
+                "#,
+            )
+            .bind(&client.name)
+            .bind(client.updated_at)
+            .bind(client.id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(client)
+        })
+    }
+
+    fn delete_client<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client: Client,
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE client
+                SET is_deleted = 1, deleted_at = ?
+                WHERE id = ? AND is_deleted = 0 AND is_default = 0
+                "#,
+            )
+            .bind(deleted_at)
+            .bind(client.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn reassign_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: Client,
+        to: Client,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry
+                SET client_id = ?
+                WHERE client_id = ?
+                "#,
+            )
+            .bind(to.id)
+            .bind(from.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
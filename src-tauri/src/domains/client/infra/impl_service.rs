@@ -0,0 +1,126 @@
+use crate::{
+    domains::client::{
+        Client, ClientRepositoryTrait, ClientServiceTrait,
+        dto::client_dto::{ClientCreateDto, ClientDeleteDto, ClientUpdateDto, ClientViewDto},
+        infra::impl_repository::ClientRepository,
+    },
+    error::AppError,
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::{future::Future, sync::Arc};
+
+pub struct ClientService {
+    pool: SqlitePool,
+    repo: Arc<dyn ClientRepositoryTrait + Send + Sync>,
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Client name must not be empty".to_string(),
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+impl ClientServiceTrait for ClientService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ClientServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(ClientRepository {}),
+        })
+    }
+
+    fn create_client(
+        &self,
+        dto: ClientCreateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<ClientViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let client = Client::new(0, validate_name(&dto.name)?);
+
+            let mut conn = self.pool.acquire().await?;
+            let created = self.repo.create_client(&mut conn, client).await?;
+
+            Ok(created.into())
+        })
+    }
+
+    fn get_clients(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ClientViewDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let clients = self.repo.get_all_clients(&mut conn).await?;
+
+            Ok(clients.into_iter().map(ClientViewDto::from).collect())
+        })
+    }
+
+    fn update_client(
+        &self,
+        dto: ClientUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<ClientViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let name = validate_name(&dto.name)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            let mut client = self
+                .repo
+                .get_client(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Client with id {} not found", dto.id))
+                })?;
+
+            client.name = name;
+            client.updated_at = Utc::now();
+
+            let updated = self.repo.update_client(&mut tx, client).await?;
+
+            tx.commit().await?;
+
+            Ok(updated.into())
+        })
+    }
+
+    fn delete_client(
+        &self,
+        dto: ClientDeleteDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let client = self
+                .repo
+                .get_client(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Client with id {} not found", dto.id))
+                })?;
+            if client.is_default {
+                return Err(AppError::ValidationError(
+                    "The default client cannot be deleted".to_string(),
+                ));
+            }
+
+            let default_client = self.repo.get_default_client(&mut tx).await?;
+            self.repo
+                .reassign_entries(&mut tx, client.clone(), default_client)
+                .await?;
+            self.repo.delete_client(&mut tx, client, Utc::now()).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+}
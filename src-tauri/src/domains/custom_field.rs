@@ -0,0 +1,22 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod custom_field_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{CustomField, CustomFieldScope, CustomFieldType, CustomFieldValue};
+pub use domain::repository::CustomFieldRepositoryTrait;
+pub use domain::service::CustomFieldServiceTrait;
+pub use dto::custom_field_dto::*;
+pub use infra::impl_repository::CustomFieldRepository;
+pub use infra::impl_service::CustomFieldService;
@@ -0,0 +1,253 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, str::FromStr};
+
+const MAX_NAME_LENGTH: usize = 50;
+const MAX_VALUE_LENGTH: usize = 200;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The kind of value a custom field holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    #[default]
+    Text,
+    Number,
+    Date,
+    Enum,
+}
+
+impl CustomFieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CustomFieldType::Text => "text",
+            CustomFieldType::Number => "number",
+            CustomFieldType::Date => "date",
+            CustomFieldType::Enum => "enum",
+        }
+    }
+}
+
+impl FromStr for CustomFieldType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(CustomFieldType::Text),
+            "number" => Ok(CustomFieldType::Number),
+            "date" => Ok(CustomFieldType::Date),
+            "enum" => Ok(CustomFieldType::Enum),
+            _ => Err(format!("Unknown custom field type '{}'", value)),
+        }
+    }
+}
+
+/// Whether a custom field is set on trackers or on their lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldScope {
+    #[default]
+    Tracker,
+    Line,
+}
+
+impl CustomFieldScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CustomFieldScope::Tracker => "tracker",
+            CustomFieldScope::Line => "line",
+        }
+    }
+}
+
+impl FromStr for CustomFieldScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "tracker" => Ok(CustomFieldScope::Tracker),
+            "line" => Ok(CustomFieldScope::Line),
+            _ => Err(format!("Unknown custom field scope '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct CustomField {
+    pub id: i64,
+    pub name: String,
+    pub field_type: String,
+    pub scope: String,
+    /// JSON array with the allowed values of an enum field.
+    pub options: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomField {
+    /// Fields are only written after validation, so an unknown type is logged and treated
+    /// as text, which accepts any value, rather than failing the whole view.
+    pub fn field_type(&self) -> CustomFieldType {
+        CustomFieldType::from_str(&self.field_type).unwrap_or_else(|e| {
+            log::warn!("Treating custom field {} as text: {}", self.id, e);
+            CustomFieldType::Text
+        })
+    }
+
+    pub fn scope(&self) -> CustomFieldScope {
+        CustomFieldScope::from_str(&self.scope).unwrap_or_else(|e| {
+            log::warn!(
+                "Treating custom field {} as a tracker field: {}",
+                self.id,
+                e
+            );
+            CustomFieldScope::Tracker
+        })
+    }
+
+    /// The allowed values of an enum field, empty for every other type.
+    pub fn options(&self) -> Vec<String> {
+        let Some(options) = &self.options else {
+            return Vec::new();
+        };
+
+        serde_json::from_str(options).unwrap_or_else(|e| {
+            log::warn!("Ignoring options of custom field {}: {}", self.id, e);
+            Vec::new()
+        })
+    }
+
+    /// Trims the name in place and checks that it is usable.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        self.name = self.name.trim().to_string();
+
+        if self.name.is_empty() {
+            return Err("Field name must not be empty".to_string());
+        }
+        if self.name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "Field name must not be longer than {} characters",
+                MAX_NAME_LENGTH
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the options against the field type and stores them trimmed.
+    ///
+    /// Enum fields need at least one option and no two may differ only in case, every
+    /// other type takes none.
+    pub fn set_options(&mut self, options: Vec<String>) -> Result<(), String> {
+        if self.field_type() != CustomFieldType::Enum {
+            if !options.is_empty() {
+                return Err("Only enum fields have options".to_string());
+            }
+            self.options = None;
+            return Ok(());
+        }
+
+        let mut seen = HashSet::new();
+        let mut trimmed = Vec::with_capacity(options.len());
+        for option in options {
+            let option = option.trim().to_string();
+            if option.is_empty() {
+                return Err("Enum options must not be empty".to_string());
+            }
+            if option.chars().count() > MAX_VALUE_LENGTH {
+                return Err(format!(
+                    "Enum options must not be longer than {} characters",
+                    MAX_VALUE_LENGTH
+                ));
+            }
+            if !seen.insert(option.to_lowercase()) {
+                return Err(format!("Enum option '{}' is listed twice", option));
+            }
+            trimmed.push(option);
+        }
+        if trimmed.is_empty() {
+            return Err("Enum fields need at least one option".to_string());
+        }
+
+        self.options = Some(serde_json::to_string(&trimmed).map_err(|e| e.to_string())?);
+        Ok(())
+    }
+
+    /// Checks a value against the field type and returns it in the form it is stored in.
+    ///
+    /// Numbers and dates are stored canonically (`1.5`, `2026-01-31`) and enum values with
+    /// the spelling of their option, so equal values always compare equal as text.
+    pub fn parse_value(&self, value: &str) -> Result<String, String> {
+        let value = value.trim();
+
+        match self.field_type() {
+            CustomFieldType::Text => {
+                if value.chars().count() > MAX_VALUE_LENGTH {
+                    return Err(format!(
+                        "Value of '{}' must not be longer than {} characters",
+                        self.name, MAX_VALUE_LENGTH
+                    ));
+                }
+                Ok(value.to_string())
+            }
+            CustomFieldType::Number => Decimal::from_str(value)
+                .map(|number| number.normalize().to_string())
+                .map_err(|_| format!("Value of '{}' must be a number", self.name)),
+            CustomFieldType::Date => NaiveDate::parse_from_str(value, DATE_FORMAT)
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .map_err(|_| format!("Value of '{}' must be a date like 2026-01-31", self.name)),
+            CustomFieldType::Enum => self
+                .options()
+                .into_iter()
+                .find(|option| option.to_lowercase() == value.to_lowercase())
+                .ok_or_else(|| format!("'{}' is not one of the options of '{}'", value, self.name)),
+        }
+    }
+}
+
+impl Default for CustomField {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: String::new(),
+            field_type: CustomFieldType::default().as_str().to_string(),
+            scope: CustomFieldScope::default().as_str().to_string(),
+            options: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl fmt::Display for CustomField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CustomField(id: {}, name: {}, field_type: {}, scope: {}, options: {:?})",
+            self.id, self.name, self.field_type, self.scope, self.options
+        )
+    }
+}
+
+/// A value set on a tracker or a line, joined with the field it belongs to.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct CustomFieldValue {
+    pub field_id: i64,
+    pub name: String,
+    pub field_type: String,
+    pub entry_id: Option<i64>,
+    pub line_id: Option<i64>,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl fmt::Display for CustomFieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CustomFieldValue(field_id: {}, entry_id: {:?}, line_id: {:?}, value: {})",
+            self.field_id, self.entry_id, self.line_id, self.value
+        )
+    }
+}
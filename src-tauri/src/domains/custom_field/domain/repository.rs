@@ -0,0 +1,90 @@
+use crate::domains::custom_field::domain::model::{CustomField, CustomFieldValue};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait CustomFieldRepositoryTrait {
+    /* Fields */
+    fn create_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<CustomField>> + Send + 'a>>;
+
+    fn get_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<CustomField>>> + Send + 'a>>;
+
+    /// Names are compared case-insensitively.
+    fn get_field_by_name<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        name: String,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<CustomField>>> + Send + 'a>>;
+
+    fn get_all_fields<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomField>>> + Send + 'a>>;
+
+    /// Updates the name and options, the type and scope of a field never change.
+    fn update_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<CustomField>> + Send + 'a>>;
+
+    /// Removes the field for good, its values go with it.
+    fn delete_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /* Values */
+    fn get_all_values<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>;
+
+    fn get_values_for_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>;
+
+    fn get_values_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>;
+
+    fn get_values_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>;
+
+    /// Sets the value of a tracker field, `None` removes it.
+    fn set_entry_value<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+        entry_id: i64,
+        value: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Sets the value of a line field, `None` removes it.
+    fn set_line_value<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+        line_id: i64,
+        value: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
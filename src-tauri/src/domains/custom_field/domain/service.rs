@@ -0,0 +1,41 @@
+use crate::{
+    domains::custom_field::dto::custom_field_dto::{
+        CustomFieldCreateDto, CustomFieldDeleteDto, CustomFieldUpdateDto,
+        CustomFieldValueUpdateDto, CustomFieldValueViewDto, CustomFieldViewDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait CustomFieldServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn CustomFieldServiceTrait>
+    where
+        Self: Sized;
+
+    fn create_field(
+        &self,
+        dto: CustomFieldCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<CustomFieldViewDto, AppError>> + Send + '_>>;
+
+    fn get_fields(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<CustomFieldViewDto>, AppError>> + Send + '_>>;
+
+    /// Rejects dropping an enum option that is still set somewhere.
+    fn update_field(
+        &self,
+        dto: CustomFieldUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<CustomFieldViewDto, AppError>> + Send + '_>>;
+
+    fn delete_field(
+        &self,
+        dto: CustomFieldDeleteDto,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Validates and stores the value, then returns every value of the tracker or line.
+    fn set_field_value(
+        &self,
+        dto: CustomFieldValueUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<CustomFieldValueViewDto>, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,84 @@
+use crate::domains::custom_field::{
+    CustomField, CustomFieldScope, CustomFieldType, CustomFieldValue,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomFieldViewDto {
+    pub id: i64,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub scope: CustomFieldScope,
+    /// Allowed values of an enum field, empty for every other type.
+    pub options: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CustomField> for CustomFieldViewDto {
+    fn from(field: CustomField) -> Self {
+        Self {
+            field_type: field.field_type(),
+            scope: field.scope(),
+            options: field.options(),
+            id: field.id,
+            name: field.name,
+            created_at: field.created_at,
+            updated_at: field.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomFieldCreateDto {
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub scope: CustomFieldScope,
+    pub options: Vec<String>,
+}
+
+/// Renames a field or changes its options, its type and scope are fixed once created.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomFieldUpdateDto {
+    pub id: i64,
+    pub name: String,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomFieldDeleteDto {
+    pub id: i64,
+}
+
+/// Sets a field on a tracker or a line, depending on the field's scope. An empty or
+/// missing value removes it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomFieldValueUpdateDto {
+    pub field_id: i64,
+    pub target_id: i64,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomFieldValueViewDto {
+    pub field_id: i64,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CustomFieldValue> for CustomFieldValueViewDto {
+    fn from(value: CustomFieldValue) -> Self {
+        Self {
+            // Types are validated on write, text is the safe reading of anything else
+            field_type: CustomFieldType::from_str(&value.field_type).unwrap_or_default(),
+            field_id: value.field_id,
+            name: value.name,
+            value: value.value,
+            updated_at: value.updated_at,
+        }
+    }
+}
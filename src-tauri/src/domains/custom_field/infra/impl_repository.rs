@@ -0,0 +1,343 @@
+use crate::domains::custom_field::{
+    CustomFieldRepositoryTrait,
+    domain::model::{CustomField, CustomFieldValue},
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct CustomFieldRepository;
+
+impl CustomFieldRepositoryTrait for CustomFieldRepository {
+    fn create_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<CustomField>> + Send + 'a>> {
+        Box::pin(async move {
+            let field = sqlx::query_as::<_, CustomField>(
+                r#"
+                INSERT INTO custom_field (name, field_type, scope, options, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                RETURNING id, name, field_type, scope, options, created_at, updated_at
+                "#,
+            )
+            .bind(&field.name)
+            .bind(&field.field_type)
+            .bind(&field.scope)
+            .bind(&field.options)
+            .bind(field.created_at)
+            .bind(field.updated_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(field)
+        })
+    }
+
+    fn get_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<CustomField>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let field = sqlx::query_as::<_, CustomField>(
+                r#"
+                SELECT id, name, field_type, scope, options, created_at, updated_at
+                FROM custom_field
+                WHERE id = ?
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(field)
+        })
+    }
+
+    fn get_field_by_name<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        name: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<CustomField>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            // The column is declared with NOCASE collation
+            let field = sqlx::query_as::<_, CustomField>(
+                r#"
+                SELECT id, name, field_type, scope, options, created_at, updated_at
+                FROM custom_field
+                WHERE name = ?
+                "#,
+            )
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(field)
+        })
+    }
+
+    fn get_all_fields<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomField>>> + Send + 'a>> {
+        Box::pin(async move {
+            let fields = sqlx::query_as::<_, CustomField>(
+                r#"
+                SELECT id, name, field_type, scope, options, created_at, updated_at
+                FROM custom_field
+                ORDER BY name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(fields)
+        })
+    }
+
+    fn update_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<CustomField>> + Send + 'a>> {
+        Box::pin(async move {
+            let field = sqlx::query_as::<_, CustomField>(
+                r#"
+                UPDATE custom_field
+                SET name = ?, options = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, name, field_type, scope, options, created_at, updated_at
+                "#,
+            )
+            .bind(&field.name)
+            .bind(&field.options)
+            .bind(field.updated_at)
+            .bind(field.id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(field)
+        })
+    }
+
+    fn delete_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field: CustomField,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                DELETE FROM custom_field_value
+                WHERE field_id = ?
+                "#,
+            )
+            .bind(field.id)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM custom_field
+                WHERE id = ?
+                "#,
+            )
+            .bind(field.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn get_all_values<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let values = sqlx::query_as::<_, CustomFieldValue>(
+                r#"
+                SELECT v.field_id, f.name, f.field_type, v.entry_id, v.line_id, v.value,
+                    v.updated_at
+                FROM custom_field_value v
+                JOIN custom_field f ON f.id = v.field_id
+                ORDER BY f.name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(values)
+        })
+    }
+
+    fn get_values_for_field<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let values = sqlx::query_as::<_, CustomFieldValue>(
+                r#"
+                SELECT v.field_id, f.name, f.field_type, v.entry_id, v.line_id, v.value,
+                    v.updated_at
+                FROM custom_field_value v
+                JOIN custom_field f ON f.id = v.field_id
+                WHERE v.field_id = ?
+                ORDER BY v.id
+                "#,
+            )
+            .bind(field_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(values)
+        })
+    }
+
+    fn get_values_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let values = sqlx::query_as::<_, CustomFieldValue>(
+                r#"
+                SELECT v.field_id, f.name, f.field_type, v.entry_id, v.line_id, v.value,
+                    v.updated_at
+                FROM custom_field_value v
+                JOIN custom_field f ON f.id = v.field_id
+                WHERE v.entry_id = ?
+                ORDER BY f.name
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(values)
+        })
+    }
+
+    fn get_values_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<CustomFieldValue>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let values = sqlx::query_as::<_, CustomFieldValue>(
+                r#"
+                SELECT v.field_id, f.name, f.field_type, v.entry_id, v.line_id, v.value,
+                    v.updated_at
+                FROM custom_field_value v
+                JOIN custom_field f ON f.id = v.field_id
+                WHERE v.line_id = ?
+                ORDER BY f.name
+                "#,
+            )
+            .bind(line_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(values)
+        })
+    }
+
+    fn set_entry_value<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+        entry_id: i64,
+        value: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let Some(value) = value else {
+                sqlx::query(
+                    r#"
+                    DELETE FROM custom_field_value
+                    WHERE field_id = ? AND entry_id = ?
+                    "#,
+                )
+                .bind(field_id)
+                .bind(entry_id)
+                .execute(&mut *conn)
+                .await?;
+
+                return Ok(());
+            };
+
+            // The conflict target names the partial unique index on tracker values
+            sqlx::query(
+                r#"
+                INSERT INTO custom_field_value (field_id, entry_id, value, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (field_id, entry_id) WHERE entry_id IS NOT NULL
+                DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(field_id)
+            .bind(entry_id)
+            .bind(value)
+            .bind(updated_at)
+            .bind(updated_at)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_line_value<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        field_id: i64,
+        line_id: i64,
+        value: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let Some(value) = value else {
+                sqlx::query(
+                    r#"
+                    DELETE FROM custom_field_value
+                    WHERE field_id = ? AND line_id = ?
+                    "#,
+                )
+                .bind(field_id)
+                .bind(line_id)
+                .execute(&mut *conn)
+                .await?;
+
+                return Ok(());
+            };
+
+            // The conflict target names the partial unique index on line values
+            sqlx::query(
+                r#"
+                INSERT INTO custom_field_value (field_id, line_id, value, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (field_id, line_id) WHERE line_id IS NOT NULL
+                DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(field_id)
+            .bind(line_id)
+            .bind(value)
+            .bind(updated_at)
+            .bind(updated_at)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    domains::{
+        custom_field::{
+            CustomField, CustomFieldRepositoryTrait, CustomFieldScope, CustomFieldServiceTrait,
+            dto::custom_field_dto::{
+                CustomFieldCreateDto, CustomFieldDeleteDto, CustomFieldUpdateDto,
+                CustomFieldValueUpdateDto, CustomFieldValueViewDto, CustomFieldViewDto,
+            },
+            infra::impl_repository::CustomFieldRepository,
+        },
+        history::HistoryRecorder,
+        tracker::{TrackerRepository, TrackerRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+pub struct CustomFieldService {
+    pool: SqlitePool,
+    repo: Arc<dyn CustomFieldRepositoryTrait + Send + Sync>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+impl CustomFieldService {
+    async fn get_field(
+        &self,
+        conn: &mut SqliteConnection,
+        id: i64,
+    ) -> Result<CustomField, AppError> {
+        self.repo
+            .get_field(conn, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Field with id {} not found", id)))
+    }
+
+    /// Rejects a name that is already used by another field.
+    async fn ensure_unique(
+        &self,
+        conn: &mut SqliteConnection,
+        field: &CustomField,
+    ) -> Result<(), AppError> {
+        if let Some(existing) = self
+            .repo
+            .get_field_by_name(conn, field.name.clone())
+            .await?
+            && existing.id != field.id
+        {
+            return Err(AppError::ValidationError(format!(
+                "Field '{}' already exists",
+                existing.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl CustomFieldServiceTrait for CustomFieldService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn CustomFieldServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(CustomFieldRepository {}),
+            tracker_repo: Arc::new(TrackerRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn create_field(
+        &self,
+        dto: CustomFieldCreateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<CustomFieldViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut field = CustomField {
+                name: dto.name,
+                field_type: dto.field_type.as_str().to_string(),
+                scope: dto.scope.as_str().to_string(),
+                ..Default::default()
+            };
+            field.normalize().map_err(AppError::ValidationError)?;
+            field
+                .set_options(dto.options)
+                .map_err(AppError::ValidationError)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            self.ensure_unique(&mut tx, &field).await?;
+            let created = self.repo.create_field(&mut tx, field).await?;
+
+            tx.commit().await?;
+
+            Ok(created.into())
+        })
+    }
+
+    fn get_fields(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<CustomFieldViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let fields = self.repo.get_all_fields(&mut conn).await?;
+
+            Ok(fields.into_iter().map(CustomFieldViewDto::from).collect())
+        })
+    }
+
+    fn update_field(
+        &self,
+        dto: CustomFieldUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<CustomFieldViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let mut field = self.get_field(&mut tx, dto.id).await?;
+            field.name = dto.name;
+            field.updated_at = Utc::now();
+            field.normalize().map_err(AppError::ValidationError)?;
+            field
+                .set_options(dto.options)
+                .map_err(AppError::ValidationError)?;
+
+            // Stored values must stay valid, so an option can only go once nothing uses it
+            let values = self.repo.get_values_for_field(&mut tx, field.id).await?;
+            if let Some(value) = values
+                .iter()
+                .find(|value| field.parse_value(&value.value).as_ref() != Ok(&value.value))
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Option '{}' of '{}' is still in use",
+                    value.value, field.name
+                )));
+            }
+
+            self.ensure_unique(&mut tx, &field).await?;
+            let updated = self.repo.update_field(&mut tx, field).await?;
+
+            tx.commit().await?;
+
+            Ok(updated.into())
+        })
+    }
+
+    fn delete_field(
+        &self,
+        dto: CustomFieldDeleteDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let field = self.get_field(&mut tx, dto.id).await?;
+            self.repo.delete_field(&mut tx, field).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_field_value(
+        &self,
+        dto: CustomFieldValueUpdateDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<CustomFieldValueViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let field = self.get_field(&mut tx, dto.field_id).await?;
+
+            let value = match dto.value.as_deref().map(str::trim) {
+                None | Some("") => None,
+                Some(value) => Some(
+                    field
+                        .parse_value(value)
+                        .map_err(AppError::ValidationError)?,
+                ),
+            };
+            let updated_at = Utc::now();
+
+            let values = match field.scope() {
+                CustomFieldScope::Tracker => {
+                    if self
+                        .tracker_repo
+                        .get_entry(&mut tx, dto.target_id)
+                        .await?
+                        .is_none()
+                    {
+                        return Err(AppError::NotFound(format!(
+                            "Entry with id {} not found",
+                            dto.target_id
+                        )));
+                    }
+
+                    let before = self.history.capture(&mut tx, vec![dto.target_id]).await?;
+                    self.repo
+                        .set_entry_value(&mut tx, field.id, dto.target_id, value, updated_at)
+                        .await?;
+                    self.history
+                        .record(&mut tx, "Set field value", before, &[])
+                        .await?;
+                    self.repo
+                        .get_values_for_entry(&mut tx, dto.target_id)
+                        .await?
+                }
+                CustomFieldScope::Line => {
+                    let line = self
+                        .tracker_repo
+                        .get_entry_line(&mut tx, dto.target_id)
+                        .await?
+                        .ok_or_else(|| {
+                            AppError::NotFound(format!("Line with id {} not found", dto.target_id))
+                        })?;
+
+                    let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+                    self.repo
+                        .set_line_value(&mut tx, field.id, dto.target_id, value, updated_at)
+                        .await?;
+                    self.history
+                        .record(&mut tx, "Set field value", before, &[])
+                        .await?;
+                    self.repo
+                        .get_values_for_line(&mut tx, dto.target_id)
+                        .await?
+                }
+            };
+
+            tx.commit().await?;
+
+            Ok(values
+                .into_iter()
+                .map(CustomFieldValueViewDto::from)
+                .collect())
+        })
+    }
+}
@@ -0,0 +1,23 @@
+mod domain {
+    pub mod model;
+    pub mod render;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod export_dto;
+}
+
+mod infra {
+    mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{ExportFields, ExportRow};
+pub use domain::render::render_export;
+pub use domain::repository::ExportRepositoryTrait;
+pub use domain::service::ExportServiceTrait;
+pub use dto::export_dto::*;
+pub use infra::impl_service::ExportService;
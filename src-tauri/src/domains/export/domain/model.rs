@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use std::{collections::BTreeMap, fmt};
+
+/// A tracked interval together with the line description and tracker label it belongs to.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct ExportRow {
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub duration_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Custom field values of the tracker and of the line by field name, filled in by
+    /// the service.
+    #[sqlx(skip)]
+    pub entry_fields: BTreeMap<String, String>,
+    #[sqlx(skip)]
+    pub line_fields: BTreeMap<String, String>,
+}
+
+impl ExportRow {
+    /// Length of a finished interval, `None` while it is still running.
+    pub fn duration_seconds(&self) -> Option<i64> {
+        self.ended_at
+            .map(|ended_at| (ended_at - self.started_at).num_seconds())
+    }
+
+    /// Value of the named field on the line or its tracker. Names are unique across
+    /// scopes, so at most one of them has it.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.line_fields
+            .get(name)
+            .or_else(|| self.entry_fields.get(name))
+            .map(String::as_str)
+    }
+}
+
+/// How custom fields shape the export: every field gets a CSV column, in the order of
+/// `names`, and `group_by` names the field the intervals are grouped by.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFields {
+    pub names: Vec<String>,
+    pub group_by: Option<String>,
+}
+
+impl fmt::Display for ExportRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ExportRow(entry_id: {}, line_id: {}, duration_id: {}, started_at: {}, ended_at: {:?})",
+            self.entry_id, self.line_id, self.duration_id, self.started_at, self.ended_at
+        )
+    }
+}
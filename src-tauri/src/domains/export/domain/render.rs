@@ -0,0 +1,153 @@
+use crate::{
+    domains::export::{
+        ExportFields, ExportRow,
+        dto::export_dto::{
+            ExportDocumentDto, ExportDurationDto, ExportFormat, ExportGroupDto, ExportLineDto,
+            ExportQueryDto, ExportRecordDto, ExportTrackerDto,
+        },
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Renders the rows into the requested format without touching the database or the UI.
+pub fn render_export(
+    mut rows: Vec<ExportRow>,
+    format: ExportFormat,
+    query: &ExportQueryDto,
+    fields: &ExportFields,
+    exported_at: DateTime<Utc>,
+) -> Result<String, AppError> {
+    // Bring the groups together, rows without a value come last. The sort is stable, so
+    // each group keeps the order the rows arrived in
+    if let Some(group_by) = &fields.group_by {
+        rows.sort_by_key(|row| group_key(row, group_by));
+    }
+
+    match format {
+        ExportFormat::Csv => render_csv(rows, fields),
+        ExportFormat::Json => render_json(rows, query, fields, exported_at),
+    }
+}
+
+fn group_key(row: &ExportRow, group_by: &str) -> (bool, Option<String>) {
+    let value = row.field(group_by).map(str::to_string);
+    (value.is_none(), value)
+}
+
+fn render_csv(rows: Vec<ExportRow>, fields: &ExportFields) -> Result<String, AppError> {
+    // The header is written by hand because the field columns are only known at runtime
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+
+    writer
+        .write_record(
+            ExportRecordDto::COLUMNS
+                .iter()
+                .copied()
+                .chain(fields.names.iter().map(String::as_str)),
+        )
+        .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+    for row in rows {
+        let values: Vec<String> = fields
+            .names
+            .iter()
+            .map(|name| row.field(name).unwrap_or_default().to_string())
+            .collect();
+
+        writer
+            .serialize((ExportRecordDto::from(row), values))
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+    String::from_utf8(bytes).map_err(|e| AppError::SerializationError(e.to_string()))
+}
+
+fn render_json(
+    rows: Vec<ExportRow>,
+    query: &ExportQueryDto,
+    fields: &ExportFields,
+    exported_at: DateTime<Utc>,
+) -> Result<String, AppError> {
+    let mut document = ExportDocumentDto {
+        exported_at,
+        from: query.from,
+        to: query.to,
+        group_by: fields.group_by.clone(),
+        trackers: Vec::new(),
+        groups: Vec::new(),
+    };
+
+    match &fields.group_by {
+        Some(group_by) => {
+            // The rows are sorted by group, so each group is one consecutive run
+            let mut runs: Vec<(Option<String>, Vec<ExportRow>)> = Vec::new();
+            for row in rows {
+                let value = row.field(group_by).map(str::to_string);
+                match runs.last_mut() {
+                    Some((run_value, run_rows)) if *run_value == value => run_rows.push(row),
+                    _ => runs.push((value, vec![row])),
+                }
+            }
+
+            document.groups = runs
+                .into_iter()
+                .map(|(value, rows)| ExportGroupDto {
+                    value,
+                    total_seconds: rows.iter().filter_map(ExportRow::duration_seconds).sum(),
+                    trackers: nest(rows),
+                })
+                .collect();
+        }
+        None => document.trackers = nest(rows),
+    }
+
+    serde_json::to_string_pretty(&document).map_err(|e| AppError::SerializationError(e.to_string()))
+}
+
+/// Groups the flat rows into trackers and lines, keeping the order they arrived in.
+fn nest(rows: Vec<ExportRow>) -> Vec<ExportTrackerDto> {
+    let mut trackers: Vec<ExportTrackerDto> = Vec::new();
+    let mut tracker_positions: HashMap<i64, usize> = HashMap::new();
+    let mut line_positions: HashMap<i64, usize> = HashMap::new();
+
+    for row in rows {
+        let duration = ExportDurationDto {
+            id: row.duration_id,
+            started_at: row.started_at,
+            ended_at: row.ended_at,
+            duration_seconds: row.duration_seconds(),
+        };
+
+        let tracker_position = *tracker_positions.entry(row.entry_id).or_insert_with(|| {
+            trackers.push(ExportTrackerDto {
+                id: row.entry_id,
+                label: row.entry_label.clone(),
+                fields: row.entry_fields.clone(),
+                lines: Vec::new(),
+            });
+            trackers.len() - 1
+        });
+        let tracker = &mut trackers[tracker_position];
+
+        let line_position = *line_positions.entry(row.line_id).or_insert_with(|| {
+            tracker.lines.push(ExportLineDto {
+                id: row.line_id,
+                desc: row.line_desc.clone(),
+                fields: row.line_fields.clone(),
+                durations: Vec::new(),
+            });
+            tracker.lines.len() - 1
+        });
+        tracker.lines[line_position].durations.push(duration);
+    }
+
+    trackers
+}
@@ -0,0 +1,20 @@
+use crate::domains::export::domain::model::ExportRow;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait ExportRepositoryTrait {
+    /// Returns live intervals that started in `[from, to)`, limited to `entry_ids` unless empty.
+    ///
+    /// Each `(field_id, value)` pair must be set on the interval's tracker or line, values
+    /// are compared in their stored form.
+    fn get_rows<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        entry_ids: Vec<i64>,
+        field_values: Vec<(i64, String)>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<ExportRow>>> + Send + 'a>>;
+}
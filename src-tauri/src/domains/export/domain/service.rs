@@ -0,0 +1,19 @@
+use crate::{
+    domains::export::dto::export_dto::{ExportFormat, ExportQueryDto},
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait ExportServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ExportServiceTrait>
+    where
+        Self: Sized;
+
+    /// Renders the selected intervals and returns the file contents.
+    fn export(
+        &self,
+        dto: ExportQueryDto,
+        format: ExportFormat,
+    ) -> Pin<Box<dyn Future<Output = Result<String, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,137 @@
+use crate::domains::export::ExportRow;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Selects the intervals to export. Dates are inclusive local calendar days,
+/// an empty `entry_ids` list exports every tracker. Every filter may be left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportQueryDto {
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub entry_ids: Vec<i64>,
+    /// Keeps only intervals whose tracker or line has every one of these values.
+    #[serde(default)]
+    pub fields: Vec<ExportFieldFilterDto>,
+    /// Custom field whose value the intervals are grouped by.
+    #[serde(default)]
+    pub group_by: Option<i64>,
+}
+
+/// Matches a custom field value, given in any form the field accepts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportFieldFilterDto {
+    pub field_id: i64,
+    pub value: String,
+}
+
+/// One CSV row per tracked interval, followed by a column per custom field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecordDto {
+    pub tracker_id: i64,
+    pub tracker: String,
+    pub line_id: i64,
+    pub line: String,
+    pub duration_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+}
+
+impl ExportRecordDto {
+    /// Header of the fixed columns, in field order.
+    pub const COLUMNS: [&'static str; 8] = [
+        "tracker_id",
+        "tracker",
+        "line_id",
+        "line",
+        "duration_id",
+        "started_at",
+        "ended_at",
+        "duration_seconds",
+    ];
+}
+
+impl From<ExportRow> for ExportRecordDto {
+    fn from(row: ExportRow) -> Self {
+        Self {
+            duration_seconds: row.duration_seconds(),
+            tracker_id: row.entry_id,
+            tracker: row.entry_label,
+            line_id: row.line_id,
+            line: row.line_desc,
+            duration_id: row.duration_id,
+            started_at: row.started_at,
+            ended_at: row.ended_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDurationDto {
+    pub id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportLineDto {
+    pub id: i64,
+    pub desc: String,
+    /// Line-scoped custom field values by field name.
+    pub fields: BTreeMap<String, String>,
+    pub durations: Vec<ExportDurationDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTrackerDto {
+    pub id: i64,
+    pub label: String,
+    /// Tracker-scoped custom field values by field name.
+    pub fields: BTreeMap<String, String>,
+    pub lines: Vec<ExportLineDto>,
+}
+
+/// The intervals sharing one value of the grouping field. A tracker whose lines differ
+/// in a line-scoped field shows up in several groups, each with the matching lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportGroupDto {
+    /// `None` for the intervals without a value.
+    pub value: Option<String>,
+    /// Sum of the finished intervals.
+    pub total_seconds: i64,
+    pub trackers: Vec<ExportTrackerDto>,
+}
+
+/// Root of the structured JSON export. A grouped export lists its trackers in `groups`
+/// and leaves `trackers` empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDocumentDto {
+    pub exported_at: DateTime<Utc>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Name of the custom field the export is grouped by.
+    pub group_by: Option<String>,
+    pub trackers: Vec<ExportTrackerDto>,
+    pub groups: Vec<ExportGroupDto>,
+}
@@ -0,0 +1,74 @@
+use crate::domains::export::{ExportRepositoryTrait, domain::model::ExportRow};
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+use std::future::Future;
+
+pub struct ExportRepository;
+
+impl ExportRepositoryTrait for ExportRepository {
+    fn get_rows<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        entry_ids: Vec<i64>,
+        field_values: Vec<(i64, String)>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<ExportRow>>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                r#"
+                SELECT e.id AS entry_id, e.label AS entry_label, l.id AS line_id,
+                    l.desc AS line_desc, d.id AS duration_id, d.started_at, d.ended_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                "#,
+            );
+
+            if let Some(from) = from {
+                query
+                    .push(" AND julianday(d.started_at) >= julianday(")
+                    .push_bind(from)
+                    .push(")");
+            }
+
+            if let Some(to) = to {
+                query
+                    .push(" AND julianday(d.started_at) < julianday(")
+                    .push_bind(to)
+                    .push(")");
+            }
+
+            if !entry_ids.is_empty() {
+                query.push(" AND e.id IN (");
+                let mut ids = query.separated(", ");
+                for entry_id in entry_ids {
+                    ids.push_bind(entry_id);
+                }
+                ids.push_unseparated(")");
+            }
+
+            for (field_id, value) in field_values {
+                query
+                    .push(
+                        " AND EXISTS (SELECT 1 FROM custom_field_value v \
+                        WHERE (v.entry_id = e.id OR v.line_id = l.id) AND v.field_id = ",
+                    )
+                    .push_bind(field_id)
+                    .push(" AND v.value = ")
+                    .push_bind(value)
+                    .push(")");
+            }
+
+            query.push(" ORDER BY e.label, e.id, l.created_at, l.id, d.started_at");
+
+            let rows = query
+                .build_query_as::<ExportRow>()
+                .fetch_all(&mut *conn)
+                .await?;
+
+            Ok(rows)
+        })
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    domains::{
+        custom_field::{CustomField, CustomFieldRepository, CustomFieldRepositoryTrait},
+        export::{
+            ExportFields, ExportRepositoryTrait, ExportServiceTrait,
+            domain::render::render_export,
+            dto::export_dto::{ExportFormat, ExportQueryDto},
+            infra::impl_repository::ExportRepository,
+        },
+        report::start_of_day,
+    },
+    error::AppError,
+};
+use chrono::{Days, Local, Utc};
+use sqlx::SqlitePool;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
+
+pub struct ExportService {
+    pool: SqlitePool,
+    repo: Arc<dyn ExportRepositoryTrait + Send + Sync>,
+    field_repo: Arc<dyn CustomFieldRepositoryTrait + Send + Sync>,
+}
+
+fn field_not_found(field_id: i64) -> AppError {
+    AppError::NotFound(format!("Field with id {} not found", field_id))
+}
+
+impl ExportServiceTrait for ExportService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ExportServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(ExportRepository {}),
+            field_repo: Arc::new(CustomFieldRepository {}),
+        })
+    }
+
+    fn export(
+        &self,
+        dto: ExportQueryDto,
+        format: ExportFormat,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<String, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            if let (Some(from), Some(to)) = (dto.from, dto.to)
+                && from > to
+            {
+                return Err(AppError::ValidationError(
+                    "Export start date must not be after its end date".to_string(),
+                ));
+            }
+
+            // Convert the inclusive local dates into a half-open UTC range
+            let from = dto.from.map(|from| start_of_day(from, &Local));
+            let to = dto
+                .to
+                .and_then(|to| to.checked_add_days(Days::new(1)))
+                .map(|day_after| start_of_day(day_after, &Local));
+
+            let mut conn = self.pool.acquire().await?;
+
+            let custom_fields = self.field_repo.get_all_fields(&mut conn).await?;
+            let fields_by_id: HashMap<i64, &CustomField> = custom_fields
+                .iter()
+                .map(|field| (field.id, field))
+                .collect();
+
+            // Filter values are matched in the form they are stored in
+            let mut field_values = Vec::with_capacity(dto.fields.len());
+            for filter in &dto.fields {
+                let field = fields_by_id
+                    .get(&filter.field_id)
+                    .ok_or_else(|| field_not_found(filter.field_id))?;
+                let value = field
+                    .parse_value(&filter.value)
+                    .map_err(AppError::ValidationError)?;
+                field_values.push((field.id, value));
+            }
+
+            let group_by = match dto.group_by {
+                Some(field_id) => Some(
+                    fields_by_id
+                        .get(&field_id)
+                        .ok_or_else(|| field_not_found(field_id))?
+                        .name
+                        .clone(),
+                ),
+                None => None,
+            };
+
+            let mut rows = self
+                .repo
+                .get_rows(&mut conn, from, to, dto.entry_ids.clone(), field_values)
+                .await?;
+
+            let mut entry_fields: HashMap<i64, BTreeMap<String, String>> = HashMap::new();
+            let mut line_fields: HashMap<i64, BTreeMap<String, String>> = HashMap::new();
+            for value in self.field_repo.get_all_values(&mut conn).await? {
+                let target = match (value.entry_id, value.line_id) {
+                    (Some(entry_id), _) => entry_fields.entry(entry_id).or_default(),
+                    (None, Some(line_id)) => line_fields.entry(line_id).or_default(),
+                    (None, None) => continue,
+                };
+                target.insert(value.name, value.value);
+            }
+            for row in &mut rows {
+                row.entry_fields = entry_fields.get(&row.entry_id).cloned().unwrap_or_default();
+                row.line_fields = line_fields.get(&row.line_id).cloned().unwrap_or_default();
+            }
+
+            let fields = ExportFields {
+                names: custom_fields
+                    .iter()
+                    .map(|field| field.name.clone())
+                    .collect(),
+                group_by,
+            };
+
+            render_export(rows, format, &dto, &fields, Utc::now())
+        })
+    }
+}
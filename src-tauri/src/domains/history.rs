@@ -0,0 +1,27 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod history_dto;
+}
+
+mod infra {
+    pub mod impl_recorder;
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    DurationImage, EntryImage, FieldValueImage, HISTORY_LIMIT, HistoryChange, HistoryEntry,
+    LineImage, TrackerSnapshot,
+};
+pub use domain::repository::HistoryRepositoryTrait;
+pub use domain::service::HistoryServiceTrait;
+pub use dto::history_dto::*;
+pub use infra::impl_recorder::HistoryRecorder;
+pub use infra::impl_repository::HistoryRepository;
+pub use infra::impl_service::HistoryService;
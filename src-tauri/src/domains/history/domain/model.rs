@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// How many changes are kept for undo, older ones are forgotten.
+pub const HISTORY_LIMIT: i64 = 50;
+
+/// Every column of a tracker row at one point in time, along with its field values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EntryImage {
+    pub id: i64,
+    pub client_id: i64,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub is_deleted: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub hourly_rate: Option<String>,
+    pub currency: Option<String>,
+    pub budget_seconds: Option<i64>,
+    pub budget_period: Option<String>,
+    pub is_archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub sort_order: i64,
+    pub is_pinned: bool,
+    pub color: Option<String>,
+    /// Sorted by field, so two images of the same values compare equal.
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub field_values: Vec<FieldValueImage>,
+}
+
+/// Every column of a line row at one point in time, along with its tags and field values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LineImage {
+    pub id: i64,
+    pub entry_id: i64,
+    pub desc: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub is_deleted: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub hourly_rate: Option<String>,
+    pub is_billable: bool,
+    pub estimate_seconds: Option<i64>,
+    /// Sorted, so two images of the same tags compare equal.
+    #[sqlx(skip)]
+    pub tag_ids: Vec<i64>,
+    /// Sorted by field, like the tags.
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub field_values: Vec<FieldValueImage>,
+}
+
+/// A custom field value set on a tracker or a line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FieldValueImage {
+    pub field_id: i64,
+    pub value: String,
+}
+
+/// Every column of an interval row at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DurationImage {
+    pub id: i64,
+    pub entry_line_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub is_deleted: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub invoice_line_id: Option<i64>,
+}
+
+/// The rows of some trackers, including trashed ones, taken before and after a change.
+#[derive(Debug, Clone, Default)]
+pub struct TrackerSnapshot {
+    pub entry_ids: Vec<i64>,
+    pub entries: Vec<EntryImage>,
+    pub lines: Vec<LineImage>,
+    pub durations: Vec<DurationImage>,
+}
+
+impl TrackerSnapshot {
+    /// The changes that turn `self` into `after`, trackers before their lines and lines
+    /// before their intervals.
+    pub fn diff(self, after: TrackerSnapshot) -> Vec<HistoryChange> {
+        let mut changes = Vec::new();
+        changes.extend(
+            diff_rows(self.entries, after.entries, |entry| entry.id)
+                .into_iter()
+                .map(|(before, after)| HistoryChange::Entry { before, after }),
+        );
+        changes.extend(
+            diff_rows(self.lines, after.lines, |line| line.id)
+                .into_iter()
+                .map(|(before, after)| HistoryChange::Line { before, after }),
+        );
+        changes.extend(
+            diff_rows(self.durations, after.durations, |duration| duration.id)
+                .into_iter()
+                .map(|(before, after)| HistoryChange::Duration { before, after }),
+        );
+        changes
+    }
+}
+
+fn diff_rows<T: PartialEq>(
+    before: Vec<T>,
+    after: Vec<T>,
+    id: impl Fn(&T) -> i64,
+) -> Vec<(Option<T>, Option<T>)> {
+    let mut after: BTreeMap<i64, T> = after.into_iter().map(|row| (id(&row), row)).collect();
+
+    let mut changed = Vec::new();
+    for row in before {
+        let new_row = after.remove(&id(&row));
+        if new_row.as_ref() != Some(&row) {
+            changed.push((Some(row), new_row));
+        }
+    }
+    changed.extend(after.into_values().map(|row| (None, Some(row))));
+    changed
+}
+
+/// One row as it was before and after a change, `None` where the row did not exist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "table", rename_all = "snake_case")]
+pub enum HistoryChange {
+    Entry {
+        before: Option<EntryImage>,
+        after: Option<EntryImage>,
+    },
+    Line {
+        before: Option<LineImage>,
+        after: Option<LineImage>,
+    },
+    Duration {
+        before: Option<DurationImage>,
+        after: Option<DurationImage>,
+    },
+}
+
+impl HistoryChange {
+    /// The change that puts the row back the way it was.
+    pub fn inverse(self) -> Self {
+        match self {
+            HistoryChange::Entry { before, after } => HistoryChange::Entry {
+                before: after,
+                after: before,
+            },
+            HistoryChange::Line { before, after } => HistoryChange::Line {
+                before: after,
+                after: before,
+            },
+            HistoryChange::Duration { before, after } => HistoryChange::Duration {
+                before: after,
+                after: before,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub label: String,
+    /// The `HistoryChange`s as JSON.
+    pub changes: String,
+    pub is_undone: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl HistoryEntry {
+    pub fn new(label: &str, changes: &[HistoryChange]) -> Result<Self, String> {
+        Ok(Self {
+            id: 0,
+            label: label.to_string(),
+            changes: serde_json::to_string(changes).map_err(|e| e.to_string())?,
+            is_undone: false,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn changes(&self) -> Result<Vec<HistoryChange>, String> {
+        serde_json::from_str(&self.changes)
+            .map_err(|e| format!("Invalid changes in history entry {}: {}", self.id, e))
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HistoryEntry(id: {}, label: {}, is_undone: {})",
+            self.id, self.label, self.is_undone
+        )
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domains::history::domain::model::{
+    DurationImage, EntryImage, HistoryEntry, LineImage, TrackerSnapshot,
+};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait HistoryRepositoryTrait {
+    /// Records a new change, forgetting the undone ones it replaces and all but the newest
+    /// `limit` entries.
+    fn push_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: HistoryEntry,
+        limit: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<HistoryEntry>> + Send + 'a>>;
+
+    /// Returns the newest change that has not been undone.
+    fn get_last_done<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<HistoryEntry>>> + Send + 'a>>;
+
+    /// Returns the oldest undone change, the next one to redo.
+    fn get_first_undone<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<HistoryEntry>>> + Send + 'a>>;
+
+    fn set_undone<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+        is_undone: bool,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn delete_history_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Returns all recorded changes, newest first.
+    fn get_history<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<HistoryEntry>>> + Send + 'a>>;
+
+    /// Returns the trackers with their lines and intervals, trashed ones included.
+    fn get_snapshot<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_ids: Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerSnapshot>> + Send + 'a>>;
+
+    /// Returns the trackers that have a running interval.
+    fn get_running_entry_ids<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<i64>>> + Send + 'a>>;
+
+    fn get_entry_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryImage>>> + Send + 'a>>;
+
+    fn get_line_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<LineImage>>> + Send + 'a>>;
+
+    fn get_duration_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<DurationImage>>> + Send + 'a>>;
+
+    /// Writes the row and its field values back exactly as imaged, inserting it if it is gone.
+    fn put_entry_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: EntryImage,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Writes the row, its tags and its field values back exactly as imaged, inserting it if
+    /// it is gone.
+    fn put_line_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: LineImage,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Writes the row back exactly as imaged, inserting it if it is gone.
+    fn put_duration_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: DurationImage,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Removes the tracker row for good, for undoing its creation.
+    fn remove_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Removes the line row for good, for undoing its creation.
+    fn remove_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Removes the interval row for good, for undoing its creation.
+    fn remove_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
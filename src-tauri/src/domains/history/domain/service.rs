@@ -0,0 +1,26 @@
+use crate::{domains::history::dto::history_dto::HistoryEntryDto, error::AppError};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait HistoryServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn HistoryServiceTrait>
+    where
+        Self: Sized;
+
+    /// Reverts the newest change and returns it, or `None` when there is nothing to undo.
+    /// A change whose rows were edited since is dropped from the history instead.
+    fn undo(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<HistoryEntryDto>, AppError>> + Send + '_>>;
+
+    /// Reapplies the last undone change and returns it, or `None` when there is nothing to
+    /// redo.
+    fn redo(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<HistoryEntryDto>, AppError>> + Send + '_>>;
+
+    /// Returns the recorded changes, newest first.
+    fn get_history(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<HistoryEntryDto>, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,22 @@
+use crate::domains::history::HistoryEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntryDto {
+    pub id: i64,
+    pub label: String,
+    pub is_undone: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<HistoryEntry> for HistoryEntryDto {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            id: entry.id,
+            label: entry.label,
+            is_undone: entry.is_undone,
+            created_at: entry.created_at,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    domains::history::{
+        HISTORY_LIMIT, HistoryEntry, HistoryRepositoryTrait, TrackerSnapshot,
+        infra::impl_repository::HistoryRepository,
+    },
+    error::AppError,
+};
+use sqlx::SqliteConnection;
+use std::sync::Arc;
+
+/// Records changes to trackers, their lines and intervals so they can be undone. Every
+/// service that edits those rows goes through it, within the transaction of the edit.
+///
+/// Some edits are left out on purpose, and entries that touched the same rows can no longer
+/// be undone after them:
+/// - invoicing, since invoiced intervals are frozen and must not be reverted
+/// - purging the trash, which is meant to be permanent and takes a backup first
+/// - clients, tags and custom fields themselves, which the images only refer to
+pub struct HistoryRecorder {
+    repo: Arc<dyn HistoryRepositoryTrait + Send + Sync>,
+}
+
+impl Default for HistoryRecorder {
+    fn default() -> Self {
+        Self {
+            repo: Arc::new(HistoryRepository {}),
+        }
+    }
+}
+
+impl HistoryRecorder {
+    /// Snapshots the trackers a change is about to touch, to `record` it once it is made.
+    pub async fn capture(
+        &self,
+        conn: &mut SqliteConnection,
+        entry_ids: Vec<i64>,
+    ) -> Result<TrackerSnapshot, AppError> {
+        Ok(self.repo.get_snapshot(conn, entry_ids).await?)
+    }
+
+    /// Records how the captured trackers, and any `created_ids` that did not exist yet,
+    /// changed since `before`, so the change can be undone.
+    pub async fn record(
+        &self,
+        conn: &mut SqliteConnection,
+        label: &str,
+        before: TrackerSnapshot,
+        created_ids: &[i64],
+    ) -> Result<(), AppError> {
+        let mut entry_ids = before.entry_ids.clone();
+        entry_ids.extend_from_slice(created_ids);
+        let after = self.repo.get_snapshot(conn, entry_ids).await?;
+
+        let changes = before.diff(after);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let entry = HistoryEntry::new(label, &changes).map_err(AppError::SerializationError)?;
+        self.repo.push_entry(conn, entry, HISTORY_LIMIT).await?;
+
+        Ok(())
+    }
+
+    /// The trackers to capture for a change that may stop whatever is running.
+    pub async fn with_running(
+        &self,
+        conn: &mut SqliteConnection,
+        entry_ids: &[i64],
+    ) -> Result<Vec<i64>, AppError> {
+        let mut running = self.repo.get_running_entry_ids(conn).await?;
+        running.extend_from_slice(entry_ids);
+        Ok(running)
+    }
+}
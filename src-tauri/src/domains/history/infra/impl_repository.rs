@@ -0,0 +1,637 @@
+use crate::domains::history::{
+    HistoryRepositoryTrait,
+    domain::model::{
+        DurationImage, EntryImage, FieldValueImage, HistoryEntry, LineImage, TrackerSnapshot,
+    },
+};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+use std::{collections::HashMap, future::Future};
+
+pub struct HistoryRepository;
+
+const ENTRY_COLUMNS: &str = "e.id, e.client_id, e.label, e.created_at, e.updated_at, \
+    e.is_deleted, e.deleted_at, e.hourly_rate, e.currency, e.budget_seconds, e.budget_period, \
+    e.is_archived, e.archived_at, e.sort_order, e.is_pinned, e.color";
+
+const LINE_COLUMNS: &str = "l.id, l.entry_id, l.desc, l.created_at, l.updated_at, \
+    l.is_deleted, l.deleted_at, l.hourly_rate, l.is_billable, l.estimate_seconds";
+
+const DURATION_COLUMNS: &str = "d.id, d.entry_line_id, d.started_at, d.ended_at, \
+    d.created_at, d.updated_at, d.is_deleted, d.deleted_at, d.invoice_line_id";
+
+/// Tag ids by line, for the lines whose `scope` column is one of `ids`.
+async fn line_tag_ids(
+    conn: &mut SqliteConnection,
+    scope: &str,
+    ids: &[i64],
+) -> sqlx::Result<HashMap<i64, Vec<i64>>> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT t.line_id, t.tag_id FROM tracker_entry_line_tag t \
+        JOIN tracker_entry_line l ON l.id = t.line_id \
+        WHERE l.{} IN (",
+        scope
+    ));
+    push_ids(&mut query, ids);
+    query.push(" ORDER BY t.line_id, t.tag_id");
+    let rows = query
+        .build_query_as::<(i64, i64)>()
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut tag_ids: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (line_id, tag_id) in rows {
+        tag_ids.entry(line_id).or_default().push(tag_id);
+    }
+
+    Ok(tag_ids)
+}
+
+/// Field values by tracker, for the trackers in `entry_ids`.
+async fn entry_field_values(
+    conn: &mut SqliteConnection,
+    entry_ids: &[i64],
+) -> sqlx::Result<HashMap<i64, Vec<FieldValueImage>>> {
+    let mut query = QueryBuilder::<Sqlite>::new(
+        "SELECT v.entry_id, v.field_id, v.value FROM custom_field_value v \
+        WHERE v.entry_id IN (",
+    );
+    push_ids(&mut query, entry_ids);
+    query.push(" ORDER BY v.entry_id, v.field_id");
+    let rows = query
+        .build_query_as::<(i64, i64, String)>()
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(group_field_values(rows))
+}
+
+/// Field values by line, for the lines whose `scope` column is one of `ids`.
+async fn line_field_values(
+    conn: &mut SqliteConnection,
+    scope: &str,
+    ids: &[i64],
+) -> sqlx::Result<HashMap<i64, Vec<FieldValueImage>>> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT v.line_id, v.field_id, v.value FROM custom_field_value v \
+        JOIN tracker_entry_line l ON l.id = v.line_id \
+        WHERE l.{} IN (",
+        scope
+    ));
+    push_ids(&mut query, ids);
+    query.push(" ORDER BY v.line_id, v.field_id");
+    let rows = query
+        .build_query_as::<(i64, i64, String)>()
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(group_field_values(rows))
+}
+
+fn group_field_values(rows: Vec<(i64, i64, String)>) -> HashMap<i64, Vec<FieldValueImage>> {
+    let mut values: HashMap<i64, Vec<FieldValueImage>> = HashMap::new();
+    for (owner_id, field_id, value) in rows {
+        values
+            .entry(owner_id)
+            .or_default()
+            .push(FieldValueImage { field_id, value });
+    }
+    values
+}
+
+/// Replaces the field values of the tracker or line whose id is in `column`.
+async fn put_field_values(
+    conn: &mut SqliteConnection,
+    column: &str,
+    id: i64,
+    values: Vec<FieldValueImage>,
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        "DELETE FROM custom_field_value WHERE {} = ?",
+        column
+    ))
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+    for value in values {
+        sqlx::query(&format!(
+            "INSERT INTO custom_field_value (field_id, {}, value) VALUES (?, ?, ?)",
+            column
+        ))
+        .bind(value.field_id)
+        .bind(id)
+        .bind(value.value)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn push_ids(query: &mut QueryBuilder<'_, Sqlite>, ids: &[i64]) {
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    separated.push_unseparated(")");
+}
+
+impl HistoryRepositoryTrait for HistoryRepository {
+    fn push_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: HistoryEntry,
+        limit: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<HistoryEntry>> + Send + 'a>> {
+        Box::pin(async move {
+            // A new change starts a new branch, what was undone can no longer be redone
+            sqlx::query("DELETE FROM history_entry WHERE is_undone = 1")
+                .execute(&mut *conn)
+                .await?;
+
+            let entry = sqlx::query_as::<_, HistoryEntry>(
+                r#"
+                INSERT INTO history_entry (label, changes, is_undone, created_at)
+                VALUES (?, ?, ?, ?)
+                RETURNING id, label, changes, is_undone, created_at
+                "#,
+            )
+            .bind(&entry.label)
+            .bind(&entry.changes)
+            .bind(entry.is_undone)
+            .bind(entry.created_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM history_entry
+                WHERE id NOT IN (SELECT id FROM history_entry ORDER BY id DESC LIMIT ?)
+                "#,
+            )
+            .bind(limit)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn get_last_done<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<HistoryEntry>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, HistoryEntry>(
+                r#"
+                SELECT id, label, changes, is_undone, created_at
+                FROM history_entry
+                WHERE is_undone = 0
+                ORDER BY id DESC
+                LIMIT 1
+                "#,
+            )
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn get_first_undone<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<HistoryEntry>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, HistoryEntry>(
+                r#"
+                SELECT id, label, changes, is_undone, created_at
+                FROM history_entry
+                WHERE is_undone = 1
+                ORDER BY id
+                LIMIT 1
+                "#,
+            )
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn set_undone<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+        is_undone: bool,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE history_entry
+                SET is_undone = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(is_undone)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_history_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM history_entry WHERE id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn get_history<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<HistoryEntry>>> + Send + 'a>> {
+        Box::pin(async move {
+            let entries = sqlx::query_as::<_, HistoryEntry>(
+                r#"
+                SELECT id, label, changes, is_undone, created_at
+                FROM history_entry
+                ORDER BY id DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(entries)
+        })
+    }
+
+    fn get_snapshot<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_ids: Vec<i64>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerSnapshot>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut entry_ids = entry_ids;
+            entry_ids.sort_unstable();
+            entry_ids.dedup();
+            if entry_ids.is_empty() {
+                return Ok(TrackerSnapshot::default());
+            }
+
+            let mut query = QueryBuilder::<Sqlite>::new(format!(
+                "SELECT {} FROM tracker_entry e WHERE e.id IN (",
+                ENTRY_COLUMNS
+            ));
+            push_ids(&mut query, &entry_ids);
+            query.push(" ORDER BY e.id");
+            let mut entries = query
+                .build_query_as::<EntryImage>()
+                .fetch_all(&mut *conn)
+                .await?;
+            let mut values = entry_field_values(conn, &entry_ids).await?;
+            for entry in &mut entries {
+                entry.field_values = values.remove(&entry.id).unwrap_or_default();
+            }
+
+            let mut query = QueryBuilder::<Sqlite>::new(format!(
+                "SELECT {} FROM tracker_entry_line l WHERE l.entry_id IN (",
+                LINE_COLUMNS
+            ));
+            push_ids(&mut query, &entry_ids);
+            query.push(" ORDER BY l.id");
+            let mut lines = query
+                .build_query_as::<LineImage>()
+                .fetch_all(&mut *conn)
+                .await?;
+            let mut tag_ids = line_tag_ids(conn, "entry_id", &entry_ids).await?;
+            let mut values = line_field_values(conn, "entry_id", &entry_ids).await?;
+            for line in &mut lines {
+                line.tag_ids = tag_ids.remove(&line.id).unwrap_or_default();
+                line.field_values = values.remove(&line.id).unwrap_or_default();
+            }
+
+            let mut query = QueryBuilder::<Sqlite>::new(format!(
+                "SELECT {} FROM tracker_entry_line_duration d \
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id \
+                WHERE l.entry_id IN (",
+                DURATION_COLUMNS
+            ));
+            push_ids(&mut query, &entry_ids);
+            query.push(" ORDER BY d.id");
+            let durations = query
+                .build_query_as::<DurationImage>()
+                .fetch_all(&mut *conn)
+                .await?;
+
+            Ok(TrackerSnapshot {
+                entry_ids,
+                entries,
+                lines,
+                durations,
+            })
+        })
+    }
+
+    fn get_running_entry_ids<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<i64>>> + Send + 'a>> {
+        Box::pin(async move {
+            let entry_ids = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT DISTINCT l.entry_id
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                WHERE d.ended_at IS NULL AND d.is_deleted = 0
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(entry_ids)
+        })
+    }
+
+    fn get_entry_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<EntryImage>>> + Send + 'a>> {
+        Box::pin(async move {
+            let image = sqlx::query_as::<_, EntryImage>(&format!(
+                "SELECT {} FROM tracker_entry e WHERE e.id = ?",
+                ENTRY_COLUMNS
+            ))
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            let Some(mut image) = image else {
+                return Ok(None);
+            };
+            image.field_values = entry_field_values(conn, &[image.id])
+                .await?
+                .remove(&image.id)
+                .unwrap_or_default();
+
+            Ok(Some(image))
+        })
+    }
+
+    fn get_line_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<LineImage>>> + Send + 'a>> {
+        Box::pin(async move {
+            let image = sqlx::query_as::<_, LineImage>(&format!(
+                "SELECT {} FROM tracker_entry_line l WHERE l.id = ?",
+                LINE_COLUMNS
+            ))
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            let Some(mut image) = image else {
+                return Ok(None);
+            };
+            image.tag_ids = line_tag_ids(conn, "id", &[image.id])
+                .await?
+                .remove(&image.id)
+                .unwrap_or_default();
+            image.field_values = line_field_values(conn, "id", &[image.id])
+                .await?
+                .remove(&image.id)
+                .unwrap_or_default();
+
+            Ok(Some(image))
+        })
+    }
+
+    fn get_duration_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<DurationImage>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let image = sqlx::query_as::<_, DurationImage>(&format!(
+                "SELECT {} FROM tracker_entry_line_duration d WHERE d.id = ?",
+                DURATION_COLUMNS
+            ))
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(image)
+        })
+    }
+
+    fn put_entry_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: EntryImage,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                INSERT INTO tracker_entry (id, client_id, label, created_at, updated_at,
+                    is_deleted, deleted_at, hourly_rate, currency, budget_seconds, budget_period,
+                    is_archived, archived_at, sort_order, is_pinned, color)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    client_id = excluded.client_id,
+                    label = excluded.label,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    is_deleted = excluded.is_deleted,
+                    deleted_at = excluded.deleted_at,
+                    hourly_rate = excluded.hourly_rate,
+                    currency = excluded.currency,
+                    budget_seconds = excluded.budget_seconds,
+                    budget_period = excluded.budget_period,
+                    is_archived = excluded.is_archived,
+                    archived_at = excluded.archived_at,
+                    sort_order = excluded.sort_order,
+                    is_pinned = excluded.is_pinned,
+                    color = excluded.color
+                "#,
+            )
+            .bind(image.id)
+            .bind(image.client_id)
+            .bind(&image.label)
+            .bind(image.created_at)
+            .bind(image.updated_at)
+            .bind(image.is_deleted)
+            .bind(image.deleted_at)
+            .bind(&image.hourly_rate)
+            .bind(&image.currency)
+            .bind(image.budget_seconds)
+            .bind(&image.budget_period)
+            .bind(image.is_archived)
+            .bind(image.archived_at)
+            .bind(image.sort_order)
+            .bind(image.is_pinned)
+            .bind(&image.color)
+            .execute(&mut *conn)
+            .await?;
+
+            put_field_values(conn, "entry_id", image.id, image.field_values).await?;
+
+            Ok(())
+        })
+    }
+
+    fn put_line_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: LineImage,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                INSERT INTO tracker_entry_line (id, entry_id, desc, created_at, updated_at,
+                    is_deleted, deleted_at, hourly_rate, is_billable, estimate_seconds)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    entry_id = excluded.entry_id,
+                    desc = excluded.desc,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    is_deleted = excluded.is_deleted,
+                    deleted_at = excluded.deleted_at,
+                    hourly_rate = excluded.hourly_rate,
+                    is_billable = excluded.is_billable,
+                    estimate_seconds = excluded.estimate_seconds
+                "#,
+            )
+            .bind(image.id)
+            .bind(image.entry_id)
+            .bind(&image.desc)
+            .bind(image.created_at)
+            .bind(image.updated_at)
+            .bind(image.is_deleted)
+            .bind(image.deleted_at)
+            .bind(&image.hourly_rate)
+            .bind(image.is_billable)
+            .bind(image.estimate_seconds)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query("DELETE FROM tracker_entry_line_tag WHERE line_id = ?")
+                .bind(image.id)
+                .execute(&mut *conn)
+                .await?;
+            for tag_id in image.tag_ids {
+                sqlx::query(
+                    r#"
+                    INSERT INTO tracker_entry_line_tag (line_id, tag_id)
+                    VALUES (?, ?)
+                    "#,
+                )
+                .bind(image.id)
+                .bind(tag_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            put_field_values(conn, "line_id", image.id, image.field_values).await?;
+
+            Ok(())
+        })
+    }
+
+    fn put_duration_image<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        image: DurationImage,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                INSERT INTO tracker_entry_line_duration (id, entry_line_id, started_at, ended_at,
+                    created_at, updated_at, is_deleted, deleted_at, invoice_line_id)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    entry_line_id = excluded.entry_line_id,
+                    started_at = excluded.started_at,
+                    ended_at = excluded.ended_at,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    is_deleted = excluded.is_deleted,
+                    deleted_at = excluded.deleted_at,
+                    invoice_line_id = excluded.invoice_line_id
+                "#,
+            )
+            .bind(image.id)
+            .bind(image.entry_line_id)
+            .bind(image.started_at)
+            .bind(image.ended_at)
+            .bind(image.created_at)
+            .bind(image.updated_at)
+            .bind(image.is_deleted)
+            .bind(image.deleted_at)
+            .bind(image.invoice_line_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM tracker_entry WHERE id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM tracker_entry_line WHERE id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM tracker_entry_line_duration WHERE id = ?")
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    domains::history::{
+        HistoryChange, HistoryEntry, HistoryRepositoryTrait, HistoryServiceTrait,
+        dto::history_dto::HistoryEntryDto, infra::impl_repository::HistoryRepository,
+    },
+    error::AppError,
+};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+pub struct HistoryService {
+    pool: SqlitePool,
+    repo: Arc<dyn HistoryRepositoryTrait + Send + Sync>,
+}
+
+fn stale_error(entry: &HistoryEntry, action: &str) -> AppError {
+    AppError::ValidationError(format!(
+        "'{}' can no longer be {}, the trackers it touched have changed since",
+        entry.label, action
+    ))
+}
+
+impl HistoryService {
+    /// Whether the row still looks the way `change` expects to find it.
+    async fn is_current(
+        &self,
+        conn: &mut SqliteConnection,
+        change: &HistoryChange,
+    ) -> Result<bool, AppError> {
+        let current = match change {
+            HistoryChange::Entry { before, after } => {
+                let Some(id) = before.as_ref().or(after.as_ref()).map(|image| image.id) else {
+                    return Ok(true);
+                };
+                self.repo.get_entry_image(conn, id).await? == *before
+            }
+            HistoryChange::Line { before, after } => {
+                let Some(id) = before.as_ref().or(after.as_ref()).map(|image| image.id) else {
+                    return Ok(true);
+                };
+                self.repo.get_line_image(conn, id).await? == *before
+            }
+            HistoryChange::Duration { before, after } => {
+                let Some(id) = before.as_ref().or(after.as_ref()).map(|image| image.id) else {
+                    return Ok(true);
+                };
+                self.repo.get_duration_image(conn, id).await? == *before
+            }
+        };
+
+        Ok(current)
+    }
+
+    /// Applies the changes unless any of their rows moved on since, or rows added since
+    /// still depend on them. Returns `false` then, leaving the caller to roll back.
+    async fn replay(
+        &self,
+        conn: &mut SqliteConnection,
+        changes: &[HistoryChange],
+    ) -> Result<bool, AppError> {
+        for change in changes {
+            if !self.is_current(conn, change).await? {
+                return Ok(false);
+            }
+        }
+
+        match self.write(conn, changes).await {
+            Ok(()) => Ok(true),
+            Err(e)
+                if e.as_database_error()
+                    .is_some_and(|e| e.is_foreign_key_violation()) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn write(
+        &self,
+        conn: &mut SqliteConnection,
+        changes: &[HistoryChange],
+    ) -> sqlx::Result<()> {
+        // Changes come trackers first, so parents are written before their children and
+        // removed after them
+        for change in changes {
+            match change.clone() {
+                HistoryChange::Entry {
+                    after: Some(image), ..
+                } => self.repo.put_entry_image(conn, image).await?,
+                HistoryChange::Line {
+                    after: Some(image), ..
+                } => self.repo.put_line_image(conn, image).await?,
+                HistoryChange::Duration {
+                    after: Some(image), ..
+                } => self.repo.put_duration_image(conn, image).await?,
+                _ => {}
+            }
+        }
+        for change in changes.iter().rev() {
+            match change {
+                HistoryChange::Entry {
+                    before: Some(image),
+                    after: None,
+                } => self.repo.remove_entry(conn, image.id).await?,
+                HistoryChange::Line {
+                    before: Some(image),
+                    after: None,
+                } => self.repo.remove_line(conn, image.id).await?,
+                HistoryChange::Duration {
+                    before: Some(image),
+                    after: None,
+                } => self.repo.remove_duration(conn, image.id).await?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl HistoryServiceTrait for HistoryService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn HistoryServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(HistoryRepository {}),
+        })
+    }
+
+    fn undo(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<HistoryEntryDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let Some(entry) = self.repo.get_last_done(&mut tx).await? else {
+                return Ok(None);
+            };
+
+            let changes: Vec<HistoryChange> = entry
+                .changes()
+                .map_err(AppError::SerializationError)?
+                .into_iter()
+                .map(HistoryChange::inverse)
+                .collect();
+
+            if !self.replay(&mut tx, &changes).await? {
+                log::warn!(
+                    "Dropping {} from the history, it can no longer be undone",
+                    entry
+                );
+                tx.rollback().await?;
+
+                let mut conn = self.pool.acquire().await?;
+                self.repo.delete_history_entry(&mut conn, entry.id).await?;
+
+                return Err(stale_error(&entry, "undone"));
+            }
+
+            self.repo.set_undone(&mut tx, entry.id, true).await?;
+
+            tx.commit().await?;
+
+            Ok(Some(HistoryEntryDto {
+                is_undone: true,
+                ..HistoryEntryDto::from(entry)
+            }))
+        })
+    }
+
+    fn redo(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<HistoryEntryDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let Some(entry) = self.repo.get_first_undone(&mut tx).await? else {
+                return Ok(None);
+            };
+
+            let changes = entry.changes().map_err(AppError::SerializationError)?;
+
+            if !self.replay(&mut tx, &changes).await? {
+                log::warn!(
+                    "Dropping {} from the history, it can no longer be redone",
+                    entry
+                );
+                tx.rollback().await?;
+
+                let mut conn = self.pool.acquire().await?;
+                self.repo.delete_history_entry(&mut conn, entry.id).await?;
+
+                return Err(stale_error(&entry, "redone"));
+            }
+
+            self.repo.set_undone(&mut tx, entry.id, false).await?;
+
+            tx.commit().await?;
+
+            Ok(Some(HistoryEntryDto {
+                is_undone: false,
+                ..HistoryEntryDto::from(entry)
+            }))
+        })
+    }
+
+    fn get_history(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<HistoryEntryDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let entries = self.repo.get_history(&mut conn).await?;
+
+            Ok(entries.into_iter().map(HistoryEntryDto::from).collect())
+        })
+    }
+}
@@ -0,0 +1,29 @@
+mod domain {
+    pub mod model;
+    pub mod service;
+    pub mod source;
+}
+
+pub mod dto {
+    pub mod idle_dto;
+}
+
+mod infra {
+    pub mod fake_source;
+    pub mod impl_service;
+    #[cfg(target_os = "linux")]
+    pub mod linux_source;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    DEFAULT_IDLE_THRESHOLD_MINUTES, IDLE_DETECTED_EVENT, IDLE_POLL_INTERVAL,
+    IDLE_THRESHOLD_SETTING_KEY, IdlePeriod,
+};
+pub use domain::service::IdleServiceTrait;
+pub use domain::source::IdleSourceTrait;
+pub use dto::idle_dto::*;
+pub use infra::fake_source::FakeIdleSource;
+pub use infra::impl_service::IdleService;
+#[cfg(target_os = "linux")]
+pub use infra::linux_source::LinuxIdleSource;
@@ -0,0 +1,50 @@
+use crate::domains::tracker::TrackerEntryLineDuration;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::{fmt, time::Duration};
+
+/// Event sent to the frontend when the user has been idle past the threshold while tracking.
+pub const IDLE_DETECTED_EVENT: &str = "idle-detected";
+/// Setting that holds how many idle minutes count as having walked away.
+pub const IDLE_THRESHOLD_SETTING_KEY: &str = "idle.threshold_minutes";
+pub const DEFAULT_IDLE_THRESHOLD_MINUTES: u32 = 10;
+pub const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+const MAX_IDLE_THRESHOLD_MINUTES: u32 = 24 * 60;
+
+pub fn validate_threshold(minutes: u32) -> Result<(), String> {
+    if !(1..=MAX_IDLE_THRESHOLD_MINUTES).contains(&minutes) {
+        return Err(format!(
+            "Idle threshold must be between 1 and {} minutes",
+            MAX_IDLE_THRESHOLD_MINUTES
+        ));
+    }
+
+    Ok(())
+}
+
+/// Idle time found while intervals were running, waiting for the user to decide on it.
+#[derive(Debug, Clone)]
+pub struct IdlePeriod {
+    pub idle_since: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+    /// The intervals that were running when the user went idle.
+    pub intervals: Vec<TrackerEntryLineDuration>,
+}
+
+impl IdlePeriod {
+    pub fn idle_time(&self) -> TimeDelta {
+        self.detected_at - self.idle_since
+    }
+}
+
+impl fmt::Display for IdlePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "IdlePeriod(idle_since: {}, detected_at: {}, intervals: {})",
+            self.idle_since,
+            self.detected_at,
+            self.intervals.len()
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{
+    domains::idle::dto::idle_dto::{IdlePeriodDto, IdleResolution},
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait IdleServiceTrait: Send + Sync {
+    /// Uses the idle source of the current platform.
+    fn create_service(pool: SqlitePool) -> Arc<dyn IdleServiceTrait>
+    where
+        Self: Sized;
+
+    /// Returns the idle period once the user has been idle past the threshold with
+    /// intervals running. It is only returned once, until it is resolved.
+    fn check_idle(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<IdlePeriodDto>, AppError>> + Send + '_>>;
+
+    fn get_pending_idle(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<IdlePeriodDto>, AppError>> + Send + '_>>;
+
+    fn resolve_idle(
+        &self,
+        resolution: IdleResolution,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    fn get_threshold(&self) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+
+    fn set_threshold(
+        &self,
+        minutes: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,7 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+/// Tells how long the user has not touched the keyboard or mouse.
+pub trait IdleSourceTrait: Send + Sync {
+    /// Fails when the platform cannot report idle time, e.g. outside a graphical session.
+    fn idle_time(&self) -> Pin<Box<dyn Future<Output = Result<Duration, String>> + Send + '_>>;
+}
@@ -0,0 +1,51 @@
+use crate::domains::idle::IdlePeriod;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleIntervalDto {
+    pub duration_id: i64,
+    pub line_id: i64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Payload of the idle event, and what is still waiting for a decision.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlePeriodDto {
+    pub idle_since: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+    pub idle_seconds: i64,
+    pub intervals: Vec<IdleIntervalDto>,
+}
+
+impl From<&IdlePeriod> for IdlePeriodDto {
+    fn from(period: &IdlePeriod) -> Self {
+        Self {
+            idle_since: period.idle_since,
+            detected_at: period.detected_at,
+            idle_seconds: period.idle_time().num_seconds(),
+            intervals: period
+                .intervals
+                .iter()
+                .map(|duration| IdleIntervalDto {
+                    duration_id: duration.id,
+                    line_id: duration.entry_line_id,
+                    started_at: duration.started_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// What to do with the idle time. Discarding and moving both end the running intervals
+/// where the idling began, resume the line to carry on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum IdleResolution {
+    Discard,
+    Keep,
+    /// Books the idle time, up to now, on another line instead.
+    MoveTo {
+        line_id: i64,
+    },
+}
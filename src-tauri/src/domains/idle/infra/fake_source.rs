@@ -0,0 +1,23 @@
+use crate::domains::idle::IdleSourceTrait;
+use std::{future::Future, sync::Mutex, time::Duration};
+
+/// Reports whatever idle time it was last given, for tests and platforms without a real
+/// source. Starts out never idle.
+#[derive(Default)]
+pub struct FakeIdleSource {
+    idle: Mutex<Duration>,
+}
+
+impl FakeIdleSource {
+    pub fn set_idle(&self, idle: Duration) {
+        *self.idle.lock().unwrap_or_else(|e| e.into_inner()) = idle;
+    }
+}
+
+impl IdleSourceTrait for FakeIdleSource {
+    fn idle_time(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Duration, String>> + Send + '_>> {
+        Box::pin(async move { Ok(*self.idle.lock().unwrap_or_else(|e| e.into_inner())) })
+    }
+}
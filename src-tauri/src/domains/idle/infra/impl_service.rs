@@ -0,0 +1,542 @@
+use crate::{
+    domains::{
+        history::HistoryRecorder,
+        idle::{
+            DEFAULT_IDLE_THRESHOLD_MINUTES, IDLE_THRESHOLD_SETTING_KEY, IdlePeriod,
+            IdleServiceTrait, IdleSourceTrait,
+            domain::model::validate_threshold,
+            dto::idle_dto::{IdlePeriodDto, IdleResolution},
+        },
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+        tracker::{TrackerEntryLineDuration, TrackerRepository, TrackerRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+struct IdleState {
+    pending: Option<IdlePeriod>,
+    /// Whether the last reading failed, so a broken source is only reported once.
+    source_failing: bool,
+}
+
+pub struct IdleService {
+    pool: SqlitePool,
+    source: Arc<dyn IdleSourceTrait>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+    state: Mutex<IdleState>,
+}
+
+impl IdleService {
+    pub fn with_source(
+        pool: SqlitePool,
+        source: Arc<dyn IdleSourceTrait>,
+    ) -> Arc<dyn IdleServiceTrait> {
+        Arc::new(Self {
+            pool,
+            source,
+            tracker_repo: Arc::new(TrackerRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+            history: HistoryRecorder::default(),
+            state: Mutex::new(IdleState::default()),
+        })
+    }
+
+    async fn threshold(&self, conn: &mut SqliteConnection) -> Result<u32, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, IDLE_THRESHOLD_SETTING_KEY)
+            .await?
+        else {
+            return Ok(DEFAULT_IDLE_THRESHOLD_MINUTES);
+        };
+
+        Ok(setting.value.parse().unwrap_or_else(|e| {
+            log::warn!(
+                "Invalid idle threshold '{}': {}, using {} minutes",
+                setting.value,
+                e,
+                DEFAULT_IDLE_THRESHOLD_MINUTES
+            );
+            DEFAULT_IDLE_THRESHOLD_MINUTES
+        }))
+    }
+
+    /// Ends the interval where the idling began, unless it already ended before that.
+    async fn truncate(
+        &self,
+        conn: &mut SqliteConnection,
+        duration_id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let Some(mut duration) = self
+            .tracker_repo
+            .get_line_duration(conn, duration_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if duration.ended_at.is_some_and(|current| current <= ended_at) {
+            return Ok(());
+        }
+        if duration.invoice_line_id.is_some() {
+            return Err(AppError::ValidationError(format!(
+                "Interval {} has been invoiced and can no longer be changed",
+                duration.id
+            )));
+        }
+
+        let Some(line) = self
+            .tracker_repo
+            .get_entry_line(conn, duration.entry_line_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        let siblings = self.tracker_repo.get_line_durations(conn, line).await?;
+
+        duration.ended_at = Some(ended_at);
+        duration.updated_at = Utc::now();
+        duration
+            .validate(&siblings)
+            .map_err(AppError::ValidationError)?;
+
+        self.tracker_repo
+            .update_line_duration(conn, duration)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records `[started_at, ended_at)` as a finished interval on the line.
+    async fn book(
+        &self,
+        conn: &mut SqliteConnection,
+        line_id: i64,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let line = self
+            .tracker_repo
+            .get_entry_line(conn, line_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", line_id)))?;
+
+        let duration = TrackerEntryLineDuration::new(0, line.id, started_at, Some(ended_at));
+        let siblings = self.tracker_repo.get_line_durations(conn, line).await?;
+        duration
+            .validate(&siblings)
+            .map_err(AppError::ValidationError)?;
+
+        self.tracker_repo
+            .create_line_duration(conn, duration)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl IdleServiceTrait for IdleService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn IdleServiceTrait>
+    where
+        Self: Sized,
+    {
+        #[cfg(target_os = "linux")]
+        let source = Arc::new(crate::domains::idle::LinuxIdleSource);
+        #[cfg(not(target_os = "linux"))]
+        let source = Arc::new(crate::domains::idle::FakeIdleSource::default());
+
+        Self::with_source(pool, source)
+    }
+
+    fn check_idle(
+        &self,
+        now: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Option<IdlePeriodDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut state = self.state.lock().await;
+            if state.pending.is_some() {
+                return Ok(None);
+            }
+
+            let idle = match self.source.idle_time().await {
+                Ok(idle) => {
+                    state.source_failing = false;
+                    idle
+                }
+                Err(e) => {
+                    if !state.source_failing {
+                        log::warn!("Idle time is unavailable: {}", e);
+                        state.source_failing = true;
+                    }
+                    return Ok(None);
+                }
+            };
+
+            let mut conn = self.pool.acquire().await?;
+
+            let threshold = self.threshold(&mut conn).await?;
+            if idle < std::time::Duration::from_secs(u64::from(threshold) * 60) {
+                return Ok(None);
+            }
+
+            let idle_since = TimeDelta::from_std(idle)
+                .ok()
+                .and_then(|idle| now.checked_sub_signed(idle))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+            // Intervals started while idle were started by something other than the user
+            let intervals: Vec<_> = self
+                .tracker_repo
+                .get_open_durations(&mut conn)
+                .await?
+                .into_iter()
+                .filter(|duration| duration.started_at < idle_since)
+                .collect();
+            if intervals.is_empty() {
+                return Ok(None);
+            }
+
+            let period = IdlePeriod {
+                idle_since,
+                detected_at: now,
+                intervals,
+            };
+            log::info!("Detected {}", period);
+            let dto = IdlePeriodDto::from(&period);
+            state.pending = Some(period);
+
+            Ok(Some(dto))
+        })
+    }
+
+    fn get_pending_idle(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Option<IdlePeriodDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let state = self.state.lock().await;
+
+            Ok(state.pending.as_ref().map(IdlePeriodDto::from))
+        })
+    }
+
+    fn resolve_idle(
+        &self,
+        resolution: IdleResolution,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut state = self.state.lock().await;
+            let Some(period) = state.pending.as_ref() else {
+                return Err(AppError::NotFound("No idle time is pending".to_string()));
+            };
+
+            if !matches!(resolution, IdleResolution::Keep) {
+                let mut tx = self.pool.begin().await?;
+
+                // The trackers of the idle intervals and of the line the time moves to
+                let mut line_ids: Vec<i64> = period
+                    .intervals
+                    .iter()
+                    .map(|duration| duration.entry_line_id)
+                    .collect();
+                if let IdleResolution::MoveTo { line_id } = resolution {
+                    line_ids.push(line_id);
+                }
+                let mut entry_ids = Vec::new();
+                for line_id in line_ids {
+                    if let Some(line) = self.tracker_repo.get_entry_line(&mut tx, line_id).await? {
+                        entry_ids.push(line.entry_id);
+                    }
+                }
+                let before = self.history.capture(&mut tx, entry_ids).await?;
+
+                for duration in &period.intervals {
+                    self.truncate(&mut tx, duration.id, period.idle_since)
+                        .await?;
+                }
+                let label = match resolution {
+                    IdleResolution::MoveTo { line_id } => {
+                        self.book(&mut tx, line_id, period.idle_since, Utc::now())
+                            .await?;
+                        "Move idle time"
+                    }
+                    _ => "Discard idle time",
+                };
+                self.history.record(&mut tx, label, before, &[]).await?;
+
+                tx.commit().await?;
+            }
+
+            log::info!("Resolved {} with {:?}", period, resolution);
+            state.pending = None;
+
+            Ok(())
+        })
+    }
+
+    fn get_threshold(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.threshold(&mut conn).await
+        })
+    }
+
+    fn set_threshold(
+        &self,
+        minutes: u32,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            validate_threshold(minutes).map_err(AppError::ValidationError)?;
+
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(IDLE_THRESHOLD_SETTING_KEY, minutes.to_string()),
+                )
+                .await?;
+
+            Ok(minutes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database,
+        domains::{
+            billing::{BillingService, BillingServiceTrait, ClientRateUpdateDto},
+            client::{ClientCreateDto, ClientService, ClientServiceTrait},
+            idle::FakeIdleSource,
+            invoice::{InvoiceCreateDto, InvoiceService, InvoiceServiceTrait},
+            tracker::{
+                TrackerEntryCreateDto, TrackerEntryLineCreateDto,
+                TrackerEntryLineDurationUpdateDto, TrackerService, TrackerServiceTrait,
+            },
+        },
+    };
+    use chrono::{Days, Local};
+    use std::time::Duration;
+
+    struct Fixture {
+        pool: SqlitePool,
+        source: Arc<FakeIdleSource>,
+        idle: Arc<dyn IdleServiceTrait>,
+        trackers: Arc<dyn TrackerServiceTrait>,
+    }
+
+    async fn fixture(name: &str) -> Fixture {
+        let path =
+            std::env::temp_dir().join(format!("track-it-idle-{}-{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pool = database::connect_database(&path).await.unwrap();
+
+        let source = Arc::new(FakeIdleSource::default());
+        Fixture {
+            idle: IdleService::with_source(pool.clone(), source.clone()),
+            trackers: TrackerService::create_service(pool.clone()),
+            source,
+            pool,
+        }
+    }
+
+    /// Starts a line whose interval has been running since `started_at`.
+    async fn start_line(fixture: &Fixture, started_at: DateTime<Utc>) -> (i64, i64) {
+        let entry = fixture
+            .trackers
+            .create_tracker(TrackerEntryCreateDto {
+                label: "Acme".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let line = fixture
+            .trackers
+            .start_tracking(TrackerEntryLineCreateDto {
+                entry_id: entry.id,
+                desc: "Development".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let duration_id = line.durations[0].id;
+        fixture
+            .trackers
+            .update_line_duration(TrackerEntryLineDurationUpdateDto {
+                id: duration_id,
+                started_at,
+                ended_at: None,
+            })
+            .await
+            .unwrap();
+
+        (line.id, duration_id)
+    }
+
+    async fn duration(fixture: &Fixture, id: i64) -> TrackerEntryLineDuration {
+        let mut conn = fixture.pool.acquire().await.unwrap();
+        TrackerRepository {}
+            .get_line_duration(&mut conn, id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn detects_idle_time_past_the_threshold() {
+        let fixture = fixture("threshold").await;
+        let now = Utc::now();
+        let (_, duration_id) = start_line(&fixture, now - TimeDelta::hours(1)).await;
+        fixture.idle.set_threshold(10).await.unwrap();
+
+        fixture.source.set_idle(Duration::from_secs(9 * 60));
+        assert!(fixture.idle.check_idle(now).await.unwrap().is_none());
+
+        fixture.source.set_idle(Duration::from_secs(15 * 60));
+        let period = fixture.idle.check_idle(now).await.unwrap().unwrap();
+        assert_eq!(period.idle_since, now - TimeDelta::minutes(15));
+        assert_eq!(period.idle_seconds, 15 * 60);
+        assert_eq!(period.intervals.len(), 1);
+        assert_eq!(period.intervals[0].duration_id, duration_id);
+
+        // One decision at a time, the pending period is not reported again
+        assert!(fixture.idle.check_idle(now).await.unwrap().is_none());
+        assert!(fixture.idle.get_pending_idle().await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn ignores_intervals_started_while_idle() {
+        let fixture = fixture("started").await;
+        let now = Utc::now();
+        start_line(&fixture, now - TimeDelta::minutes(5)).await;
+
+        fixture.source.set_idle(Duration::from_secs(15 * 60));
+        assert!(fixture.idle.check_idle(now).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn discarding_ends_the_interval_where_idling_began() {
+        let fixture = fixture("discard").await;
+        let now = Utc::now();
+        let (_, duration_id) = start_line(&fixture, now - TimeDelta::hours(1)).await;
+
+        fixture.source.set_idle(Duration::from_secs(20 * 60));
+        let period = fixture.idle.check_idle(now).await.unwrap().unwrap();
+        fixture
+            .idle
+            .resolve_idle(IdleResolution::Discard)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            duration(&fixture, duration_id).await.ended_at,
+            Some(period.idle_since)
+        );
+        assert!(fixture.idle.get_pending_idle().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn keeping_leaves_the_interval_running() {
+        let fixture = fixture("keep").await;
+        let now = Utc::now();
+        let (_, duration_id) = start_line(&fixture, now - TimeDelta::hours(1)).await;
+
+        fixture.source.set_idle(Duration::from_secs(20 * 60));
+        fixture.idle.check_idle(now).await.unwrap().unwrap();
+        fixture
+            .idle
+            .resolve_idle(IdleResolution::Keep)
+            .await
+            .unwrap();
+
+        assert_eq!(duration(&fixture, duration_id).await.ended_at, None);
+        assert!(fixture.idle.get_pending_idle().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn refuses_to_truncate_an_invoiced_interval() {
+        let fixture = fixture("invoiced").await;
+        let client = ClientService::create_service(fixture.pool.clone())
+            .create_client(ClientCreateDto {
+                name: "Acme".to_string(),
+            })
+            .await
+            .unwrap();
+        BillingService::create_service(fixture.pool.clone())
+            .set_client_rate(ClientRateUpdateDto {
+                client_id: client.id,
+                hourly_rate: Some(rust_decimal::Decimal::from(100)),
+                currency: Some("EUR".to_string()),
+            })
+            .await
+            .unwrap();
+        let entry = fixture
+            .trackers
+            .create_tracker(TrackerEntryCreateDto {
+                label: "Site".to_string(),
+                client_id: Some(client.id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let line = fixture
+            .trackers
+            .start_tracking(TrackerEntryLineCreateDto {
+                entry_id: entry.id,
+                desc: "Development".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let duration_id = line.durations[0].id;
+        let now = Utc::now();
+        fixture
+            .trackers
+            .update_line_duration(TrackerEntryLineDurationUpdateDto {
+                id: duration_id,
+                started_at: now - TimeDelta::minutes(30),
+                ended_at: None,
+            })
+            .await
+            .unwrap();
+
+        fixture.source.set_idle(Duration::from_secs(10 * 60));
+        fixture.idle.check_idle(now).await.unwrap().unwrap();
+
+        // The interval is stopped and billed before the idle time is dealt with
+        fixture.trackers.stop_tracking(line.id).await.unwrap();
+        let today = Local::now().date_naive();
+        InvoiceService::create_service(fixture.pool.clone())
+            .create_invoice(InvoiceCreateDto {
+                client_id: client.id,
+                from: today - Days::new(1),
+                to: today,
+                currency: None,
+            })
+            .await
+            .unwrap();
+        let invoiced = duration(&fixture, duration_id).await;
+        assert!(invoiced.invoice_line_id.is_some());
+
+        let result = fixture.idle.resolve_idle(IdleResolution::Discard).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        assert_eq!(
+            duration(&fixture, duration_id).await.ended_at,
+            invoiced.ended_at
+        );
+        assert!(fixture.idle.get_pending_idle().await.unwrap().is_some());
+    }
+}
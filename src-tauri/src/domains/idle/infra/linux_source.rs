@@ -0,0 +1,87 @@
+use crate::domains::idle::IdleSourceTrait;
+use chrono::{DateTime, Utc};
+use std::{future::Future, time::Duration};
+use tokio::process::Command;
+
+/// Reads idle time from X11 through `xprintidle`, falling back to the idle hint logind
+/// keeps for the session, which also covers Wayland desktops that report it.
+pub struct LinuxIdleSource;
+
+impl LinuxIdleSource {
+    async fn x11_idle_time() -> Result<Duration, String> {
+        let stdout = run("xprintidle", &[]).await?;
+
+        let millis = stdout
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("Unexpected xprintidle output '{}': {}", stdout.trim(), e))?;
+
+        Ok(Duration::from_millis(millis))
+    }
+
+    async fn logind_idle_time() -> Result<Duration, String> {
+        let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+        let stdout = run(
+            "loginctl",
+            &[
+                "show-session",
+                &session,
+                "--property=IdleHint",
+                "--property=IdleSinceHint",
+            ],
+        )
+        .await?;
+
+        let property = |name: &str| {
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .map(str::trim)
+        };
+
+        if property("IdleHint") != Some("yes") {
+            return Ok(Duration::ZERO);
+        }
+
+        // Microseconds since the epoch
+        let since = property("IdleSinceHint")
+            .and_then(|value| value.parse::<i64>().ok())
+            .and_then(DateTime::<Utc>::from_timestamp_micros)
+            .ok_or_else(|| "logind reports idle without saying since when".to_string())?;
+
+        Ok((Utc::now() - since).to_std().unwrap_or_default())
+    }
+}
+
+async fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl IdleSourceTrait for LinuxIdleSource {
+    fn idle_time(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Duration, String>> + Send + '_>> {
+        Box::pin(async move {
+            match Self::x11_idle_time().await {
+                Ok(idle) => Ok(idle),
+                Err(x11_error) => Self::logind_idle_time()
+                    .await
+                    .map_err(|e| format!("{}; {}", x11_error, e)),
+            }
+        })
+    }
+}
@@ -0,0 +1,20 @@
+mod domain {
+    pub mod model;
+    pub mod parse;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod import_dto;
+}
+
+mod infra {
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::ImportRecord;
+pub use domain::parse::{ParsedImport, parse_import};
+pub use domain::service::ImportServiceTrait;
+pub use dto::import_dto::*;
+pub use infra::impl_service::ImportService;
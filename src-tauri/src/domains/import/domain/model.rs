@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// One interval read from an import file, identified by the row it came from.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ImportRecord {
+    pub row: usize,
+    pub tracker: String,
+    pub line: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ImportRecord {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tracker.trim().is_empty() {
+            return Err("Tracker label must not be empty".to_string());
+        }
+
+        // Running intervals are not imported, they would keep ticking on the target machine
+        let Some(ended_at) = self.ended_at else {
+            return Err("Interval has no end time".to_string());
+        };
+
+        if ended_at < self.started_at {
+            return Err("Interval ends before it starts".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ImportRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ImportRecord(row: {}, tracker: {}, line: {}, started_at: {}, ended_at: {:?})",
+            self.row, self.tracker, self.line, self.started_at, self.ended_at
+        )
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    domains::{export::ExportFormat, import::ImportRecord},
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Records that could be read, plus `(row, message)` for every row that could not.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedImport {
+    pub records: Vec<ImportRecord>,
+    pub errors: Vec<(usize, String)>,
+}
+
+// Only the columns needed to rebuild the data are read, ids from the source are ignored
+#[derive(Deserialize)]
+struct CsvRecord {
+    tracker: String,
+    line: String,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
+// A grouped export lists its trackers under `groups` instead
+#[derive(Deserialize)]
+struct JsonDocument {
+    #[serde(default)]
+    trackers: Vec<JsonTracker>,
+    #[serde(default)]
+    groups: Vec<JsonGroup>,
+}
+
+#[derive(Deserialize)]
+struct JsonGroup {
+    trackers: Vec<JsonTracker>,
+}
+
+#[derive(Deserialize)]
+struct JsonTracker {
+    label: String,
+    lines: Vec<JsonLine>,
+}
+
+#[derive(Deserialize)]
+struct JsonLine {
+    desc: String,
+    durations: Vec<JsonDuration>,
+}
+
+#[derive(Deserialize)]
+struct JsonDuration {
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
+/// Parses a file produced by the export (or written by hand in the same shape).
+///
+/// CSV rows are numbered by their line in the file, JSON intervals by their position.
+pub fn parse_import(contents: &str, format: ExportFormat) -> Result<ParsedImport, AppError> {
+    match format {
+        ExportFormat::Csv => parse_csv(contents),
+        ExportFormat::Json => parse_json(contents),
+    }
+}
+
+fn parse_csv(contents: &str) -> Result<ParsedImport, AppError> {
+    let mut reader = csv::Reader::from_reader(contents.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::SerializationError(e.to_string()))?
+        .clone();
+
+    let mut parsed = ParsedImport::default();
+    for (index, result) in reader.records().enumerate() {
+        // The header is on line 1
+        let row = index + 2;
+
+        let record = result.and_then(|record| record.deserialize::<CsvRecord>(Some(&headers)));
+        match record {
+            Ok(record) => parsed.records.push(ImportRecord {
+                row,
+                tracker: record.tracker,
+                line: record.line,
+                started_at: record.started_at,
+                ended_at: record.ended_at,
+            }),
+            Err(e) => parsed.errors.push((row, e.to_string())),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_json(contents: &str) -> Result<ParsedImport, AppError> {
+    let document: JsonDocument =
+        serde_json::from_str(contents).map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+    let mut parsed = ParsedImport::default();
+    let grouped = document.groups.into_iter().flat_map(|group| group.trackers);
+    for tracker in document.trackers.into_iter().chain(grouped) {
+        for line in tracker.lines {
+            for duration in line.durations {
+                parsed.records.push(ImportRecord {
+                    row: parsed.records.len() + 1,
+                    tracker: tracker.label.clone(),
+                    line: line.desc.clone(),
+                    started_at: duration.started_at,
+                    ended_at: duration.ended_at,
+                });
+            }
+        }
+    }
+
+    Ok(parsed)
+}
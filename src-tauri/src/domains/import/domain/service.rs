@@ -0,0 +1,20 @@
+use crate::{
+    domains::{export::ExportFormat, import::dto::import_dto::ImportReportDto},
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait ImportServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ImportServiceTrait>
+    where
+        Self: Sized;
+
+    /// Imports the file contents in one transaction, rolled back again when `dry_run` is set.
+    fn import(
+        &self,
+        contents: String,
+        format: ExportFormat,
+        dry_run: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<ImportReportDto, AppError>> + Send + '_>>;
+}
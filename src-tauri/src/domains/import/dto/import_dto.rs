@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportRowStatus {
+    Imported,
+    Duplicate,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowResultDto {
+    pub row: usize,
+    pub status: ImportRowStatus,
+    pub tracker: Option<String>,
+    pub line: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub message: Option<String>,
+}
+
+/// Outcome of an import. With `dry_run` set nothing was written, the counts show what would be.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReportDto {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub created_trackers: usize,
+    pub created_lines: usize,
+    pub imported_intervals: usize,
+    pub duplicate_intervals: usize,
+    pub failed_rows: usize,
+    pub rows: Vec<ImportRowResultDto>,
+}
@@ -0,0 +1,269 @@
+use crate::{
+    domains::{
+        client::{ClientRepository, ClientRepositoryTrait},
+        export::ExportFormat,
+        history::HistoryRecorder,
+        import::{
+            ImportRecord, ImportServiceTrait,
+            domain::parse::parse_import,
+            dto::import_dto::{ImportReportDto, ImportRowResultDto, ImportRowStatus},
+        },
+        tracker::{
+            TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration, TrackerRepository,
+            TrackerRepositoryTrait,
+        },
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
+
+pub struct ImportService {
+    pool: SqlitePool,
+    repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    client_repo: Arc<dyn ClientRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+impl ImportServiceTrait for ImportService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ImportServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(TrackerRepository {}),
+            client_repo: Arc::new(ClientRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn import(
+        &self,
+        contents: String,
+        format: ExportFormat,
+        dry_run: bool,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<ImportReportDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let parsed = parse_import(&contents, format)?;
+
+            let mut report = ImportReportDto {
+                dry_run,
+                total_rows: parsed.records.len() + parsed.errors.len(),
+                ..Default::default()
+            };
+
+            for (row, message) in parsed.errors {
+                report.rows.push(ImportRowResultDto {
+                    row,
+                    status: ImportRowStatus::Error,
+                    tracker: None,
+                    line: None,
+                    started_at: None,
+                    ended_at: None,
+                    message: Some(message),
+                });
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            // Trackers created by the import go to the default client
+            let default_client = self.client_repo.get_default_client(&mut tx).await?;
+
+            let mut entries: HashMap<String, TrackerEntry> = HashMap::new();
+            let mut lines: HashMap<(i64, String), TrackerEntryLine> = HashMap::new();
+            // Intervals imported earlier in this run, so duplicates within the file are caught too
+            let mut seen: HashSet<(i64, DateTime<Utc>, Option<DateTime<Utc>>)> = HashSet::new();
+
+            // Existing trackers are looked up front, so the history holds them as they were
+            for record in &parsed.records {
+                if entries.contains_key(&record.tracker) {
+                    continue;
+                }
+                if let Some(entry) = self
+                    .repo
+                    .get_entry_by_label(&mut tx, record.tracker.clone())
+                    .await?
+                {
+                    entries.insert(record.tracker.clone(), entry);
+                }
+            }
+            let before = self
+                .history
+                .capture(&mut tx, entries.values().map(|entry| entry.id).collect())
+                .await?;
+            let mut created_ids = Vec::new();
+
+            for record in parsed.records {
+                if let Err(message) = record.validate() {
+                    report
+                        .rows
+                        .push(row_result(&record, ImportRowStatus::Error, Some(message)));
+                    continue;
+                }
+
+                // Trackers and lines that do not exist yet are only created once an interval
+                // is imported into them, so rows that end up skipped leave nothing behind
+                let entry = entries.get(&record.tracker).cloned();
+
+                // Archived trackers take no new time, the import must not get around that
+                if let Some(entry) = entry.as_ref().filter(|entry| entry.is_archived) {
+                    report.rows.push(row_result(
+                        &record,
+                        ImportRowStatus::Error,
+                        Some(format!(
+                            "Tracker '{}' is archived, unarchive it to import time into it",
+                            entry.label
+                        )),
+                    ));
+                    continue;
+                }
+
+                let line = match &entry {
+                    Some(entry) => {
+                        let line_key = (entry.id, record.line.clone());
+                        match lines.get(&line_key) {
+                            Some(line) => Some(line.clone()),
+                            None => {
+                                let line = self
+                                    .repo
+                                    .get_entry_line_by_desc(&mut tx, entry.id, record.line.clone())
+                                    .await?;
+                                if let Some(line) = &line {
+                                    lines.insert(line_key, line.clone());
+                                }
+                                line
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                // A line that does not exist yet has no intervals to clash with
+                let siblings = match &line {
+                    Some(line) => {
+                        let interval = (line.id, record.started_at, record.ended_at);
+                        let exists = seen.contains(&interval)
+                            || self
+                                .repo
+                                .get_matching_line_duration(
+                                    &mut tx,
+                                    line.id,
+                                    record.started_at,
+                                    record.ended_at,
+                                )
+                                .await?
+                                .is_some();
+
+                        if exists {
+                            report.duplicate_intervals += 1;
+                            report.rows.push(row_result(
+                                &record,
+                                ImportRowStatus::Duplicate,
+                                Some("Interval already exists".to_string()),
+                            ));
+                            continue;
+                        }
+
+                        // The line's intervals include the ones imported earlier in this run,
+                        // they are already written to the transaction
+                        self.repo.get_line_durations(&mut tx, line.clone()).await?
+                    }
+                    None => Vec::new(),
+                };
+
+                let duration = TrackerEntryLineDuration::new(
+                    0,
+                    line.as_ref().map_or(0, |line| line.id),
+                    record.started_at,
+                    record.ended_at,
+                );
+                if let Err(message) = duration.validate(&siblings) {
+                    report
+                        .rows
+                        .push(row_result(&record, ImportRowStatus::Error, Some(message)));
+                    continue;
+                }
+
+                let entry = match entry {
+                    Some(entry) => entry,
+                    None => {
+                        report.created_trackers += 1;
+                        let entry = TrackerEntry {
+                            client_id: default_client.id,
+                            ..TrackerEntry::new(0, record.tracker.clone())
+                        };
+                        let entry = self.repo.create_entry(&mut tx, entry).await?;
+                        created_ids.push(entry.id);
+                        entries.insert(record.tracker.clone(), entry.clone());
+                        entry
+                    }
+                };
+
+                let line = match line {
+                    Some(line) => line,
+                    None => {
+                        report.created_lines += 1;
+                        let line = TrackerEntryLine::new(0, entry.id, record.line.clone());
+                        let line = self.repo.create_entry_line(&mut tx, line).await?;
+                        lines.insert((entry.id, record.line.clone()), line.clone());
+                        line
+                    }
+                };
+
+                let interval = (line.id, record.started_at, record.ended_at);
+                let duration = TrackerEntryLineDuration {
+                    entry_line_id: line.id,
+                    ..duration
+                };
+                self.repo.create_line_duration(&mut tx, duration).await?;
+                seen.insert(interval);
+
+                report.imported_intervals += 1;
+                report
+                    .rows
+                    .push(row_result(&record, ImportRowStatus::Imported, None));
+            }
+
+            if dry_run {
+                tx.rollback().await?;
+            } else {
+                self.history
+                    .record(&mut tx, "Import", before, &created_ids)
+                    .await?;
+                tx.commit().await?;
+            }
+
+            report.failed_rows = report
+                .rows
+                .iter()
+                .filter(|row| row.status == ImportRowStatus::Error)
+                .count();
+            report.rows.sort_by_key(|row| row.row);
+
+            Ok(report)
+        })
+    }
+}
+
+fn row_result(
+    record: &ImportRecord,
+    status: ImportRowStatus,
+    message: Option<String>,
+) -> ImportRowResultDto {
+    ImportRowResultDto {
+        row: record.row,
+        status,
+        tracker: Some(record.tracker.clone()),
+        line: Some(record.line.clone()),
+        started_at: Some(record.started_at),
+        ended_at: record.ended_at,
+        message,
+    }
+}
@@ -0,0 +1,23 @@
+mod domain {
+    pub mod model;
+    pub mod render;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod invoice_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{BillableInterval, Invoice, InvoiceLine};
+pub use domain::repository::InvoiceRepositoryTrait;
+pub use domain::service::InvoiceServiceTrait;
+pub use dto::invoice_dto::*;
+pub use infra::impl_repository::InvoiceRepository;
+pub use infra::impl_service::InvoiceService;
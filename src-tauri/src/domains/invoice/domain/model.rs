@@ -0,0 +1,194 @@
+use crate::domains::billing::{billable_amount, billable_rate};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, fmt};
+
+/// A finished interval of a billable line that is not on any invoice yet.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct BillableInterval {
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub is_billable: bool,
+    /// The line's rate, else its tracker's, else its client's.
+    pub hourly_rate: Option<String>,
+    pub currency: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl BillableInterval {
+    pub fn duration_seconds(&self) -> i64 {
+        (self.ended_at - self.started_at).num_seconds()
+    }
+}
+
+impl fmt::Display for BillableInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BillableInterval(duration_id: {}, line_id: {}, started_at: {}, ended_at: {})",
+            self.duration_id, self.line_id, self.started_at, self.ended_at
+        )
+    }
+}
+
+/// An issued invoice. Everything it shows is copied in, so later renames or rate
+/// changes never alter it.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct Invoice {
+    pub id: i64,
+    pub number: String,
+    pub client_id: i64,
+    pub client_name: String,
+    pub period_from: NaiveDate,
+    pub period_to: NaiveDate,
+    pub currency: String,
+    /// Decimal string, like the rates.
+    pub total: String,
+    pub issued_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl fmt::Display for Invoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invoice(id: {}, number: {}, client_id: {}, period: {}..={}, total: {} {})",
+            self.id,
+            self.number,
+            self.client_id,
+            self.period_from,
+            self.period_to,
+            self.total,
+            self.currency
+        )
+    }
+}
+
+/// A numbered line item, one per tracker line billed on the invoice.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct InvoiceLine {
+    pub id: i64,
+    pub invoice_id: i64,
+    pub position: i64,
+    /// Cleared when the tracker line is purged from the trash.
+    pub line_id: Option<i64>,
+    pub tracker_label: String,
+    pub line_desc: String,
+    pub total_seconds: i64,
+    pub hourly_rate: String,
+    pub amount: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl fmt::Display for InvoiceLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "InvoiceLine(id: {}, invoice_id: {}, position: {}, line_id: {:?}, amount: {})",
+            self.id, self.invoice_id, self.position, self.line_id, self.amount
+        )
+    }
+}
+
+/// Line items worked out from the uninvoiced intervals, before anything is written.
+#[derive(Debug, Clone)]
+pub struct InvoiceDraft {
+    pub currency: String,
+    pub total: Decimal,
+    pub lines: Vec<InvoiceDraftLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvoiceDraftLine {
+    pub line_id: i64,
+    pub tracker_label: String,
+    pub line_desc: String,
+    pub total_seconds: i64,
+    pub hourly_rate: Decimal,
+    pub amount: Decimal,
+    pub duration_ids: Vec<i64>,
+}
+
+/// Groups the intervals into one item per tracker line, keeping the order they arrived in.
+///
+/// An invoice has a single currency, so when the intervals bill in several one must be
+/// picked with `currency`. Each interval is priced on its own, like the tracker views.
+pub fn build_draft(
+    intervals: Vec<BillableInterval>,
+    currency: Option<&str>,
+) -> Result<InvoiceDraft, String> {
+    let mut priced = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        if let Some((hourly_rate, interval_currency)) = billable_rate(
+            interval.is_billable,
+            &interval.hourly_rate,
+            &interval.currency,
+        ) && currency.is_none_or(|currency| currency == interval_currency)
+        {
+            priced.push((interval, hourly_rate, interval_currency));
+        }
+    }
+
+    let mut currencies: Vec<&str> = priced
+        .iter()
+        .map(|(_, _, currency)| currency.as_str())
+        .collect();
+    currencies.sort_unstable();
+    currencies.dedup();
+    let currency = match currencies.as_slice() {
+        [] => return Err("There is no uninvoiced billable time in this period".to_string()),
+        [currency] => currency.to_string(),
+        _ => {
+            return Err(format!(
+                "The uninvoiced time is billed in several currencies ({}), pick one",
+                currencies.join(", ")
+            ));
+        }
+    };
+
+    let mut lines: Vec<InvoiceDraftLine> = Vec::new();
+    let mut line_positions: HashMap<i64, usize> = HashMap::new();
+    for (interval, hourly_rate, _) in priced {
+        let seconds = interval.duration_seconds();
+        let position = *line_positions.entry(interval.line_id).or_insert_with(|| {
+            lines.push(InvoiceDraftLine {
+                line_id: interval.line_id,
+                tracker_label: interval.entry_label.clone(),
+                line_desc: interval.line_desc.clone(),
+                total_seconds: 0,
+                hourly_rate,
+                amount: Decimal::ZERO,
+                duration_ids: Vec::new(),
+            });
+            lines.len() - 1
+        });
+
+        let line = &mut lines[position];
+        line.total_seconds += seconds;
+        line.amount += billable_amount(hourly_rate, seconds);
+        line.duration_ids.push(interval.duration_id);
+    }
+
+    let total = lines.iter().map(|line| line.amount).sum();
+
+    Ok(InvoiceDraft {
+        currency,
+        total,
+        lines,
+    })
+}
+
+/// Invoice numbers restart every year, e.g. `2026-0007`.
+pub fn invoice_number(year: i32, sequence: i64) -> String {
+    format!("{}{:04}", invoice_number_prefix(year), sequence)
+}
+
+/// The part shared by every invoice number of the year.
+pub fn invoice_number_prefix(year: i32) -> String {
+    format!("{}-", year)
+}
@@ -0,0 +1,379 @@
+use crate::{
+    domains::invoice::dto::invoice_dto::{InvoiceFormat, InvoiceLineViewDto, InvoiceViewDto},
+    error::AppError,
+};
+use chrono::Local;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use rust_decimal::Decimal;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const ROW_HEIGHT: f32 = 16.0;
+const MAX_DESCRIPTION_CHARS: usize = 60;
+
+const REGULAR_FONT: Name = Name(b"F1");
+const BOLD_FONT: Name = Name(b"F2");
+
+/// Right edges of the numeric columns, the description runs up to the first one.
+const HOURS_COLUMN: f32 = 380.0;
+const RATE_COLUMN: f32 = 460.0;
+const AMOUNT_COLUMN: f32 = PAGE_WIDTH - MARGIN;
+
+/// Renders the invoice into the requested format without touching the database or the UI.
+pub fn render_invoice(
+    invoice: &InvoiceViewDto,
+    format: InvoiceFormat,
+) -> Result<Vec<u8>, AppError> {
+    match format {
+        InvoiceFormat::Html => Ok(render_html(invoice).into_bytes()),
+        InvoiceFormat::Pdf => Ok(render_pdf(invoice)),
+    }
+}
+
+fn render_html(invoice: &InvoiceViewDto) -> String {
+    let mut rows = String::new();
+    for line in &invoice.lines {
+        rows.push_str(&format!(
+            "      <tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            line.position,
+            escape_html(&description(line)),
+            format_hours(line.total_seconds),
+            format_rate(line.hourly_rate),
+            format_amount(line.amount),
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Invoice {number}</title>
+  <style>
+    body {{ font-family: Helvetica, Arial, sans-serif; margin: 2cm; color: #222; }}
+    h1 {{ font-size: 1.6em; margin-bottom: 0.5em; }}
+    dl {{ display: grid; grid-template-columns: max-content auto; gap: 0.2em 1em; }}
+    dt {{ font-weight: bold; }}
+    dd {{ margin: 0; }}
+    table {{ width: 100%; border-collapse: collapse; margin-top: 2em; }}
+    th, td {{ padding: 0.4em; border-bottom: 1px solid #ccc; text-align: left; }}
+    .num {{ text-align: right; }}
+    tfoot td {{ font-weight: bold; border-bottom: none; }}
+  </style>
+</head>
+<body>
+  <h1>Invoice {number}</h1>
+  <dl>
+    <dt>Client</dt><dd>{client}</dd>
+    <dt>Issued</dt><dd>{issued}</dd>
+    <dt>Period</dt><dd>{from} &ndash; {to}</dd>
+  </dl>
+  <table>
+    <thead>
+      <tr><th>#</th><th>Description</th><th class="num">Hours</th><th class="num">Rate ({currency})</th><th class="num">Amount ({currency})</th></tr>
+    </thead>
+    <tbody>
+{rows}    </tbody>
+    <tfoot>
+      <tr><td></td><td>Total</td><td class="num">{hours}</td><td></td><td class="num">{total} {currency}</td></tr>
+    </tfoot>
+  </table>
+</body>
+</html>
+"#,
+        number = escape_html(&invoice.number),
+        client = escape_html(&invoice.client_name),
+        issued = invoice.issued_at.with_timezone(&Local).format("%Y-%m-%d"),
+        from = invoice.period_from.format("%Y-%m-%d"),
+        to = invoice.period_to.format("%Y-%m-%d"),
+        currency = escape_html(&invoice.currency),
+        rows = rows,
+        hours = format_hours(total_seconds(invoice)),
+        total = format_amount(invoice.total),
+    )
+}
+
+fn render_pdf(invoice: &InvoiceViewDto) -> Vec<u8> {
+    let mut pages = vec![Content::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    let content = pages.last_mut().expect("at least one page");
+    y -= 20.0;
+    show(
+        content,
+        BOLD_FONT,
+        20.0,
+        MARGIN,
+        y,
+        &format!("Invoice {}", invoice.number),
+    );
+    y -= 30.0;
+    for (label, value) in [
+        ("Client", invoice.client_name.clone()),
+        (
+            "Issued",
+            invoice
+                .issued_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        (
+            "Period",
+            format!(
+                "{} - {}",
+                invoice.period_from.format("%Y-%m-%d"),
+                invoice.period_to.format("%Y-%m-%d")
+            ),
+        ),
+    ] {
+        show(content, BOLD_FONT, 11.0, MARGIN, y, label);
+        show(content, REGULAR_FONT, 11.0, MARGIN + 60.0, y, &value);
+        y -= ROW_HEIGHT;
+    }
+    y -= ROW_HEIGHT;
+    table_header(content, &invoice.currency, y);
+    y -= ROW_HEIGHT;
+
+    for line in &invoice.lines {
+        // Leave room for the total below the last row
+        if y < MARGIN + 2.0 * ROW_HEIGHT {
+            pages.push(Content::new());
+            y = PAGE_HEIGHT - MARGIN - 10.0;
+            table_header(
+                pages.last_mut().expect("page just pushed"),
+                &invoice.currency,
+                y,
+            );
+            y -= ROW_HEIGHT;
+        }
+
+        let content = pages.last_mut().expect("at least one page");
+        show(
+            content,
+            REGULAR_FONT,
+            10.0,
+            MARGIN,
+            y,
+            &line.position.to_string(),
+        );
+        show(
+            content,
+            REGULAR_FONT,
+            10.0,
+            MARGIN + 24.0,
+            y,
+            &truncate(&description(line), MAX_DESCRIPTION_CHARS),
+        );
+        show_right(
+            content,
+            REGULAR_FONT,
+            10.0,
+            HOURS_COLUMN,
+            y,
+            &format_hours(line.total_seconds),
+        );
+        show_right(
+            content,
+            REGULAR_FONT,
+            10.0,
+            RATE_COLUMN,
+            y,
+            &format_rate(line.hourly_rate),
+        );
+        show_right(
+            content,
+            REGULAR_FONT,
+            10.0,
+            AMOUNT_COLUMN,
+            y,
+            &format_amount(line.amount),
+        );
+        y -= ROW_HEIGHT;
+    }
+
+    let content = pages.last_mut().expect("at least one page");
+    rule(content, y + ROW_HEIGHT - 4.0);
+    show(content, BOLD_FONT, 10.0, MARGIN + 24.0, y, "Total");
+    show_right(
+        content,
+        BOLD_FONT,
+        10.0,
+        HOURS_COLUMN,
+        y,
+        &format_hours(total_seconds(invoice)),
+    );
+    show_right(
+        content,
+        BOLD_FONT,
+        10.0,
+        AMOUNT_COLUMN,
+        y,
+        &format!("{} {}", format_amount(invoice.total), invoice.currency),
+    );
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let regular_font_id = Ref::new(3);
+    let bold_font_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let page_ids: Vec<Ref> = (0..pages.len() as i32)
+        .map(|index| Ref::new(6 + 2 * index))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+    pdf.document_info(info_id)
+        .title(TextStr(&format!("Invoice {}", invoice.number)));
+
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        let content_id = Ref::new(page_id.get() + 1);
+
+        let mut page = pdf.page(*page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(REGULAR_FONT, regular_font_id)
+            .pair(BOLD_FONT, bold_font_id);
+        page.finish();
+
+        pdf.stream(content_id, &content.finish());
+    }
+
+    // Both are standard fonts every reader ships, so nothing needs embedding
+    pdf.type1_font(regular_font_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_font_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    pdf.finish()
+}
+
+fn table_header(content: &mut Content, currency: &str, y: f32) {
+    show(content, BOLD_FONT, 10.0, MARGIN, y, "#");
+    show(content, BOLD_FONT, 10.0, MARGIN + 24.0, y, "Description");
+    show_right(content, BOLD_FONT, 10.0, HOURS_COLUMN, y, "Hours");
+    show_right(
+        content,
+        BOLD_FONT,
+        10.0,
+        RATE_COLUMN,
+        y,
+        &format!("Rate ({})", currency),
+    );
+    show_right(
+        content,
+        BOLD_FONT,
+        10.0,
+        AMOUNT_COLUMN,
+        y,
+        &format!("Amount ({})", currency),
+    );
+    rule(content, y - 5.0);
+}
+
+fn rule(content: &mut Content, y: f32) {
+    content
+        .set_line_width(0.5)
+        .move_to(MARGIN, y)
+        .line_to(PAGE_WIDTH - MARGIN, y)
+        .stroke();
+}
+
+fn show(content: &mut Content, font: Name, size: f32, x: f32, y: f32, text: &str) {
+    content
+        .begin_text()
+        .set_font(font, size)
+        .next_line(x, y)
+        .show(Str(&win_ansi(text)))
+        .end_text();
+}
+
+/// Shows `text` so that it ends at `right`.
+fn show_right(content: &mut Content, font: Name, size: f32, right: f32, y: f32, text: &str) {
+    show(content, font, size, right - text_width(text, size), y, text);
+}
+
+/// Approximate Helvetica advance widths, exact for the digits and separators used in
+/// numbers, which are the only right-aligned texts.
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => 556,
+            '.' | ',' | ':' | ' ' | '(' | ')' => 278,
+            '-' => 333,
+            'A'..='Z' => 667,
+            _ => 556,
+        })
+        .sum();
+
+    units as f32 * size / 1000.0
+}
+
+/// Encodes the text for the standard fonts, characters they cannot show become `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '€' => 0x80,
+            '–' => 0x96,
+            '—' => 0x97,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn description(line: &InvoiceLineViewDto) -> String {
+    format!("{} – {}", line.tracker_label, line.line_desc)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+fn total_seconds(invoice: &InvoiceViewDto) -> i64 {
+    invoice.lines.iter().map(|line| line.total_seconds).sum()
+}
+
+/// Formats a duration as hours and minutes, e.g. `12:05`.
+fn format_hours(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn format_amount(amount: Decimal) -> String {
+    format!("{:.2}", amount)
+}
+
+/// Shows at least cents but keeps any finer precision the rate was set with.
+fn format_rate(rate: Decimal) -> String {
+    let rate = rate.normalize();
+    if rate.scale() < 2 {
+        format!("{:.2}", rate)
+    } else {
+        rate.to_string()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::domains::invoice::domain::model::{BillableInterval, Invoice, InvoiceLine};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait InvoiceRepositoryTrait {
+    /// Returns the finished, uninvoiced intervals of the client's billable lines that
+    /// started in `[from, to)`.
+    fn get_uninvoiced_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<BillableInterval>>> + Send + 'a>>;
+
+    /// Counts the invoices whose number starts with `prefix`.
+    fn count_invoices_with_prefix<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        prefix: String,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>>;
+
+    fn create_invoice<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        invoice: Invoice,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Invoice>> + Send + 'a>>;
+
+    fn create_invoice_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: InvoiceLine,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<InvoiceLine>> + Send + 'a>>;
+
+    /// Links the intervals to the invoice line that billed them.
+    fn mark_invoiced<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        duration_ids: Vec<i64>,
+        invoice_line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn get_invoice<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<Invoice>>> + Send + 'a>>;
+
+    fn get_all_invoices<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<Invoice>>> + Send + 'a>>;
+
+    fn get_invoice_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        invoice_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<InvoiceLine>>> + Send + 'a>>;
+
+    fn get_lines_for_all_invoices<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<InvoiceLine>>> + Send + 'a>>;
+}
@@ -0,0 +1,40 @@
+use crate::{
+    domains::invoice::dto::invoice_dto::{InvoiceCreateDto, InvoiceFormat, InvoiceViewDto},
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait InvoiceServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn InvoiceServiceTrait>
+    where
+        Self: Sized;
+
+    /// Shows what `create_invoice` would bill without writing anything.
+    fn preview_invoice(
+        &self,
+        dto: InvoiceCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>>;
+
+    /// Freezes the client's uninvoiced time in the period into a numbered invoice and
+    /// marks the intervals as invoiced.
+    fn create_invoice(
+        &self,
+        dto: InvoiceCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>>;
+
+    fn get_invoices(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<InvoiceViewDto>, AppError>> + Send + '_>>;
+
+    fn get_invoice(
+        &self,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>>;
+
+    fn render_invoice(
+        &self,
+        id: i64,
+        format: InvoiceFormat,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, AppError>> + Send + '_>>;
+}
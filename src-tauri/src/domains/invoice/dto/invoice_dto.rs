@@ -0,0 +1,117 @@
+use crate::{
+    domains::invoice::{Invoice, InvoiceLine},
+    error::AppError,
+};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InvoiceFormat {
+    Html,
+    Pdf,
+}
+
+impl InvoiceFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            InvoiceFormat::Html => "html",
+            InvoiceFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Selects what to invoice. Dates are inclusive local calendar days and intervals count
+/// towards the day they started on. `currency` is only needed when the client's
+/// uninvoiced time is billed in several currencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceCreateDto {
+    pub client_id: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub currency: Option<String>,
+}
+
+impl Default for InvoiceCreateDto {
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+
+        Self {
+            client_id: 0,
+            from: today,
+            to: today,
+            currency: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceLineViewDto {
+    pub id: i64,
+    pub position: i64,
+    pub line_id: Option<i64>,
+    pub tracker_label: String,
+    pub line_desc: String,
+    pub total_seconds: i64,
+    pub hourly_rate: Decimal,
+    pub amount: Decimal,
+}
+
+impl TryFrom<InvoiceLine> for InvoiceLineViewDto {
+    type Error = AppError;
+
+    fn try_from(line: InvoiceLine) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: line.id,
+            position: line.position,
+            line_id: line.line_id,
+            tracker_label: line.tracker_label,
+            line_desc: line.line_desc,
+            total_seconds: line.total_seconds,
+            hourly_rate: parse_decimal(&line.hourly_rate)?,
+            amount: parse_decimal(&line.amount)?,
+        })
+    }
+}
+
+/// An invoice with its line items. Previews have an id of 0 and no number yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceViewDto {
+    pub id: i64,
+    pub number: String,
+    pub client_id: i64,
+    pub client_name: String,
+    pub period_from: NaiveDate,
+    pub period_to: NaiveDate,
+    pub currency: String,
+    pub total: Decimal,
+    pub issued_at: DateTime<Utc>,
+    pub lines: Vec<InvoiceLineViewDto>,
+}
+
+impl TryFrom<Invoice> for InvoiceViewDto {
+    type Error = AppError;
+
+    fn try_from(invoice: Invoice) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: invoice.id,
+            number: invoice.number,
+            client_id: invoice.client_id,
+            client_name: invoice.client_name,
+            period_from: invoice.period_from,
+            period_to: invoice.period_to,
+            currency: invoice.currency,
+            total: parse_decimal(&invoice.total)?,
+            issued_at: invoice.issued_at,
+            lines: Vec::new(),
+        })
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal, AppError> {
+    Decimal::from_str(value).map_err(|e| {
+        AppError::SerializationError(format!("Invalid amount '{}' on invoice: {}", value, e))
+    })
+}
@@ -0,0 +1,248 @@
+use crate::domains::invoice::{
+    InvoiceRepositoryTrait,
+    domain::model::{BillableInterval, Invoice, InvoiceLine},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+use std::future::Future;
+
+pub struct InvoiceRepository;
+
+impl InvoiceRepositoryTrait for InvoiceRepository {
+    fn get_uninvoiced_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        client_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<BillableInterval>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let intervals = sqlx::query_as::<_, BillableInterval>(
+                r#"
+                SELECT d.id AS duration_id, e.id AS entry_id, e.label AS entry_label,
+                    l.id AS line_id, l.desc AS line_desc, l.is_billable,
+                    coalesce(l.hourly_rate, e.hourly_rate, c.hourly_rate) AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency,
+                    d.started_at, d.ended_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                JOIN client c ON c.id = e.client_id
+                WHERE d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                    AND e.client_id = ? AND l.is_billable = 1
+                    AND d.ended_at IS NOT NULL AND d.invoice_line_id IS NULL
+                    AND julianday(d.started_at) >= julianday(?)
+                    AND julianday(d.started_at) < julianday(?)
+                ORDER BY e.label, e.id, l.created_at, l.id, d.started_at
+                "#,
+            )
+            .bind(client_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(intervals)
+        })
+    }
+
+    fn count_invoices_with_prefix<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        prefix: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>> {
+        Box::pin(async move {
+            let count: i64 = sqlx::query_scalar(
+                r#"
+                SELECT count(*)
+                FROM invoice
+                WHERE substr(number, 1, length(?)) = ?
+                "#,
+            )
+            .bind(&prefix)
+            .bind(&prefix)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(count)
+        })
+    }
+
+    fn create_invoice<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        invoice: Invoice,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Invoice>> + Send + 'a>> {
+        Box::pin(async move {
+            let invoice = sqlx::query_as::<_, Invoice>(
+                r#"
+                INSERT INTO invoice (number, client_id, client_name, period_from, period_to,
+                    currency, total, issued_at, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                RETURNING id, number, client_id, client_name, period_from, period_to,
+                    currency, total, issued_at, created_at, updated_at
+                "#,
+            )
+            .bind(&invoice.number)
+            .bind(invoice.client_id)
+            .bind(&invoice.client_name)
+            .bind(invoice.period_from)
+            .bind(invoice.period_to)
+            .bind(&invoice.currency)
+            .bind(&invoice.total)
+            .bind(invoice.issued_at)
+            .bind(invoice.created_at)
+            .bind(invoice.updated_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(invoice)
+        })
+    }
+
+    fn create_invoice_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: InvoiceLine,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<InvoiceLine>> + Send + 'a>> {
+        Box::pin(async move {
+            let line = sqlx::query_as::<_, InvoiceLine>(
+                r#"
+                INSERT INTO invoice_line (invoice_id, position, line_id, tracker_label, line_desc,
+                    total_seconds, hourly_rate, amount, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                RETURNING id, invoice_id, position, line_id, tracker_label, line_desc,
+                    total_seconds, hourly_rate, amount, created_at
+                "#,
+            )
+            .bind(line.invoice_id)
+            .bind(line.position)
+            .bind(line.line_id)
+            .bind(&line.tracker_label)
+            .bind(&line.line_desc)
+            .bind(line.total_seconds)
+            .bind(&line.hourly_rate)
+            .bind(&line.amount)
+            .bind(line.created_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(line)
+        })
+    }
+
+    fn mark_invoiced<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        duration_ids: Vec<i64>,
+        invoice_line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            if duration_ids.is_empty() {
+                return Ok(());
+            }
+
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "UPDATE tracker_entry_line_duration SET invoice_line_id = ",
+            );
+            query.push_bind(invoice_line_id);
+            query.push(" WHERE invoice_line_id IS NULL AND id IN (");
+            let mut ids = query.separated(", ");
+            for duration_id in duration_ids {
+                ids.push_bind(duration_id);
+            }
+            ids.push_unseparated(")");
+
+            query.build().execute(&mut *conn).await?;
+
+            Ok(())
+        })
+    }
+
+    fn get_invoice<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<Invoice>>> + Send + 'a>> {
+        Box::pin(async move {
+            let invoice = sqlx::query_as::<_, Invoice>(
+                r#"
+                SELECT id, number, client_id, client_name, period_from, period_to,
+                    currency, total, issued_at, created_at, updated_at
+                FROM invoice
+                WHERE id = ?
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(invoice)
+        })
+    }
+
+    fn get_all_invoices<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<Invoice>>> + Send + 'a>> {
+        Box::pin(async move {
+            let invoices = sqlx::query_as::<_, Invoice>(
+                r#"
+                SELECT id, number, client_id, client_name, period_from, period_to,
+                    currency, total, issued_at, created_at, updated_at
+                FROM invoice
+                ORDER BY issued_at DESC, id DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(invoices)
+        })
+    }
+
+    fn get_invoice_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        invoice_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<InvoiceLine>>> + Send + 'a>> {
+        Box::pin(async move {
+            let lines = sqlx::query_as::<_, InvoiceLine>(
+                r#"
+                SELECT id, invoice_id, position, line_id, tracker_label, line_desc,
+                    total_seconds, hourly_rate, amount, created_at
+                FROM invoice_line
+                WHERE invoice_id = ?
+                ORDER BY position
+                "#,
+            )
+            .bind(invoice_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(lines)
+        })
+    }
+
+    fn get_lines_for_all_invoices<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<InvoiceLine>>> + Send + 'a>> {
+        Box::pin(async move {
+            let lines = sqlx::query_as::<_, InvoiceLine>(
+                r#"
+                SELECT id, invoice_id, position, line_id, tracker_label, line_desc,
+                    total_seconds, hourly_rate, amount, created_at
+                FROM invoice_line
+                ORDER BY invoice_id, position
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(lines)
+        })
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{
+    domains::{
+        billing::validate_currency,
+        client::{Client, ClientRepository, ClientRepositoryTrait},
+        invoice::{
+            Invoice, InvoiceLine, InvoiceRepositoryTrait, InvoiceServiceTrait,
+            domain::{
+                model::{InvoiceDraft, build_draft, invoice_number, invoice_number_prefix},
+                render::render_invoice,
+            },
+            dto::invoice_dto::{
+                InvoiceCreateDto, InvoiceFormat, InvoiceLineViewDto, InvoiceViewDto,
+            },
+            infra::impl_repository::InvoiceRepository,
+        },
+        report::start_of_day,
+    },
+    error::AppError,
+};
+use chrono::{Datelike, Days, Local, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+pub struct InvoiceService {
+    pool: SqlitePool,
+    repo: Arc<dyn InvoiceRepositoryTrait + Send + Sync>,
+    client_repo: Arc<dyn ClientRepositoryTrait + Send + Sync>,
+}
+
+impl InvoiceService {
+    /// Works out the line items for the request without writing anything.
+    async fn draft(
+        &self,
+        conn: &mut SqliteConnection,
+        dto: &InvoiceCreateDto,
+    ) -> Result<(Client, InvoiceDraft), AppError> {
+        if dto.from > dto.to {
+            return Err(AppError::ValidationError(
+                "Invoice period must not start after it ends".to_string(),
+            ));
+        }
+        let currency = dto
+            .currency
+            .as_deref()
+            .map(validate_currency)
+            .transpose()
+            .map_err(AppError::ValidationError)?;
+
+        let client = self
+            .client_repo
+            .get_client(conn, dto.client_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Client with id {} not found", dto.client_id))
+            })?;
+
+        let day_after = dto.to.checked_add_days(Days::new(1)).ok_or_else(|| {
+            AppError::ValidationError("Invoice end date is out of range".to_string())
+        })?;
+        let from = start_of_day(dto.from, &Local);
+        let to = start_of_day(day_after, &Local);
+
+        let intervals = self
+            .repo
+            .get_uninvoiced_intervals(conn, client.id, from, to)
+            .await?;
+        let draft =
+            build_draft(intervals, currency.as_deref()).map_err(AppError::ValidationError)?;
+
+        Ok((client, draft))
+    }
+
+    async fn invoice_view(
+        &self,
+        conn: &mut SqliteConnection,
+        invoice: Invoice,
+    ) -> Result<InvoiceViewDto, AppError> {
+        let lines = self.repo.get_invoice_lines(conn, invoice.id).await?;
+
+        let mut invoice_dto = InvoiceViewDto::try_from(invoice)?;
+        invoice_dto.lines = lines
+            .into_iter()
+            .map(InvoiceLineViewDto::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(invoice_dto)
+    }
+}
+
+impl InvoiceServiceTrait for InvoiceService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn InvoiceServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(InvoiceRepository {}),
+            client_repo: Arc::new(ClientRepository {}),
+        })
+    }
+
+    fn preview_invoice(
+        &self,
+        dto: InvoiceCreateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let (client, draft) = self.draft(&mut conn, &dto).await?;
+
+            Ok(InvoiceViewDto {
+                id: 0,
+                number: String::new(),
+                client_id: client.id,
+                client_name: client.name,
+                period_from: dto.from,
+                period_to: dto.to,
+                currency: draft.currency,
+                total: draft.total,
+                issued_at: Utc::now(),
+                lines: draft
+                    .lines
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, line)| InvoiceLineViewDto {
+                        id: 0,
+                        position: index as i64 + 1,
+                        line_id: Some(line.line_id),
+                        tracker_label: line.tracker_label,
+                        line_desc: line.line_desc,
+                        total_seconds: line.total_seconds,
+                        hourly_rate: line.hourly_rate,
+                        amount: line.amount,
+                    })
+                    .collect(),
+            })
+        })
+    }
+
+    fn create_invoice(
+        &self,
+        dto: InvoiceCreateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let (client, draft) = self.draft(&mut tx, &dto).await?;
+
+            let issued_at = Utc::now();
+            let year = issued_at.with_timezone(&Local).year();
+            let sequence = self
+                .repo
+                .count_invoices_with_prefix(&mut tx, invoice_number_prefix(year))
+                .await?
+                + 1;
+
+            let invoice = Invoice {
+                id: 0,
+                number: invoice_number(year, sequence),
+                client_id: client.id,
+                client_name: client.name,
+                period_from: dto.from,
+                period_to: dto.to,
+                currency: draft.currency,
+                total: draft.total.to_string(),
+                issued_at,
+                created_at: issued_at,
+                updated_at: issued_at,
+            };
+            let invoice = self.repo.create_invoice(&mut tx, invoice).await?;
+
+            for (index, line) in draft.lines.into_iter().enumerate() {
+                let invoice_line = InvoiceLine {
+                    id: 0,
+                    invoice_id: invoice.id,
+                    position: index as i64 + 1,
+                    line_id: Some(line.line_id),
+                    tracker_label: line.tracker_label,
+                    line_desc: line.line_desc,
+                    total_seconds: line.total_seconds,
+                    hourly_rate: line.hourly_rate.to_string(),
+                    amount: line.amount.to_string(),
+                    created_at: issued_at,
+                };
+                let invoice_line = self.repo.create_invoice_line(&mut tx, invoice_line).await?;
+
+                self.repo
+                    .mark_invoiced(&mut tx, line.duration_ids, invoice_line.id)
+                    .await?;
+            }
+
+            let invoice_dto = self.invoice_view(&mut tx, invoice).await?;
+
+            tx.commit().await?;
+
+            log::info!(
+                "Issued invoice {} over {} {}",
+                invoice_dto.number,
+                invoice_dto.total,
+                invoice_dto.currency
+            );
+
+            Ok(invoice_dto)
+        })
+    }
+
+    fn get_invoices(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<InvoiceViewDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let invoices = self.repo.get_all_invoices(&mut conn).await?;
+            let lines = self.repo.get_lines_for_all_invoices(&mut conn).await?;
+
+            let mut lines_by_invoice: HashMap<i64, Vec<InvoiceLineViewDto>> = HashMap::new();
+            for line in lines {
+                lines_by_invoice
+                    .entry(line.invoice_id)
+                    .or_default()
+                    .push(InvoiceLineViewDto::try_from(line)?);
+            }
+
+            invoices
+                .into_iter()
+                .map(|invoice| {
+                    let mut invoice_dto = InvoiceViewDto::try_from(invoice)?;
+                    invoice_dto.lines =
+                        lines_by_invoice.remove(&invoice_dto.id).unwrap_or_default();
+                    Ok(invoice_dto)
+                })
+                .collect()
+        })
+    }
+
+    fn get_invoice(
+        &self,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<InvoiceViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let invoice =
+                self.repo.get_invoice(&mut conn, id).await?.ok_or_else(|| {
+                    AppError::NotFound(format!("Invoice with id {} not found", id))
+                })?;
+
+            self.invoice_view(&mut conn, invoice).await
+        })
+    }
+
+    fn render_invoice(
+        &self,
+        id: i64,
+        format: InvoiceFormat,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<u8>, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let invoice = self.get_invoice(id).await?;
+
+            render_invoice(&invoice, format)
+        })
+    }
+}
@@ -0,0 +1,24 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod recovery_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    DanglingInterval, HEARTBEAT_INTERVAL, HEARTBEAT_SETTING_KEY, POLICY_SETTING_KEY, RecoveryPolicy,
+};
+pub use domain::repository::RecoveryRepositoryTrait;
+pub use domain::service::RecoveryServiceTrait;
+pub use dto::recovery_dto::*;
+pub use infra::impl_repository::RecoveryRepository;
+pub use infra::impl_service::RecoveryService;
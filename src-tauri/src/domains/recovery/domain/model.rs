@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Setting that holds the last time the running app proved it was alive.
+pub const HEARTBEAT_SETTING_KEY: &str = "session.heartbeat";
+/// Setting that holds the `RecoveryPolicy` applied on startup.
+pub const POLICY_SETTING_KEY: &str = "recovery.policy";
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// What to do with intervals a previous session left open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryPolicy {
+    #[default]
+    CloseAtHeartbeat,
+    KeepRunning,
+    Ask,
+}
+
+impl RecoveryPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecoveryPolicy::CloseAtHeartbeat => "close_at_heartbeat",
+            RecoveryPolicy::KeepRunning => "keep_running",
+            RecoveryPolicy::Ask => "ask",
+        }
+    }
+}
+
+impl FromStr for RecoveryPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "close_at_heartbeat" => Ok(RecoveryPolicy::CloseAtHeartbeat),
+            "keep_running" => Ok(RecoveryPolicy::KeepRunning),
+            "ask" => Ok(RecoveryPolicy::Ask),
+            _ => Err(format!("Unknown recovery policy '{}'", value)),
+        }
+    }
+}
+
+/// An open interval joined with the line and tracker it belongs to.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct DanglingInterval {
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub started_at: DateTime<Utc>,
+}
+
+impl DanglingInterval {
+    /// Closing at the heartbeat never produces an interval that ends before it started.
+    pub fn end_at_heartbeat(&self, heartbeat: DateTime<Utc>) -> DateTime<Utc> {
+        heartbeat.max(self.started_at)
+    }
+}
+
+impl fmt::Display for DanglingInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DanglingInterval(duration_id: {}, entry_id: {}, line_id: {}, started_at: {})",
+            self.duration_id, self.entry_id, self.line_id, self.started_at
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use crate::domains::recovery::domain::model::DanglingInterval;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait RecoveryRepositoryTrait {
+    /// Returns live open intervals that started no later than `heartbeat`, or every open
+    /// interval when no heartbeat was ever recorded.
+    fn get_open_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        heartbeat: Option<DateTime<Utc>>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<DanglingInterval>>> + Send + 'a>>;
+}
@@ -0,0 +1,43 @@
+use crate::{
+    domains::recovery::{
+        domain::model::RecoveryPolicy,
+        dto::recovery_dto::{RecoveryCaseDto, RecoveryReportDto, RecoveryResolveDto},
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait RecoveryServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn RecoveryServiceTrait>
+    where
+        Self: Sized;
+
+    fn record_heartbeat(&self) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Applies the configured policy to intervals left open by the previous session.
+    ///
+    /// Must run before the first heartbeat of the new session is recorded.
+    fn recover(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<RecoveryReportDto, AppError>> + Send + '_>>;
+
+    fn get_pending_cases(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<RecoveryCaseDto>, AppError>> + Send + '_>>;
+
+    /// Resolves one pending case and returns the ones still waiting for the user.
+    fn resolve_case(
+        &self,
+        dto: RecoveryResolveDto,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<RecoveryCaseDto>, AppError>> + Send + '_>>;
+
+    fn get_policy(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<RecoveryPolicy, AppError>> + Send + '_>>;
+
+    fn set_policy(
+        &self,
+        policy: RecoveryPolicy,
+    ) -> Pin<Box<dyn Future<Output = Result<RecoveryPolicy, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,53 @@
+use crate::domains::recovery::{DanglingInterval, RecoveryPolicy};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An interval left open by a previous session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCaseDto {
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub started_at: DateTime<Utc>,
+    pub last_heartbeat: Option<DateTime<Utc>>,
+}
+
+impl RecoveryCaseDto {
+    pub fn new(interval: DanglingInterval, last_heartbeat: Option<DateTime<Utc>>) -> Self {
+        Self {
+            duration_id: interval.duration_id,
+            entry_id: interval.entry_id,
+            entry_label: interval.entry_label,
+            line_id: interval.line_id,
+            line_desc: interval.line_desc,
+            started_at: interval.started_at,
+            last_heartbeat,
+        }
+    }
+}
+
+/// Outcome of the startup recovery step.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecoveryReportDto {
+    pub policy: RecoveryPolicy,
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    pub closed: Vec<RecoveryCaseDto>,
+    pub kept: Vec<RecoveryCaseDto>,
+    pub pending: Vec<RecoveryCaseDto>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RecoveryResolution {
+    CloseAtHeartbeat,
+    CloseAt { ended_at: DateTime<Utc> },
+    KeepRunning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryResolveDto {
+    pub duration_id: i64,
+    pub resolution: RecoveryResolution,
+}
@@ -0,0 +1,37 @@
+use crate::domains::recovery::{RecoveryRepositoryTrait, domain::model::DanglingInterval};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct RecoveryRepository;
+
+impl RecoveryRepositoryTrait for RecoveryRepository {
+    fn get_open_intervals<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        heartbeat: Option<DateTime<Utc>>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<DanglingInterval>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let intervals = sqlx::query_as::<_, DanglingInterval>(
+                r#"
+                SELECT d.id AS duration_id, e.id AS entry_id, e.label AS entry_label,
+                    l.id AS line_id, l.desc AS line_desc, d.started_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE d.ended_at IS NULL
+                    AND d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                    AND (? IS NULL OR julianday(d.started_at) <= julianday(?))
+                ORDER BY d.started_at
+                "#,
+            )
+            .bind(heartbeat)
+            .bind(heartbeat)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(intervals)
+        })
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{
+    domains::{
+        history::HistoryRecorder,
+        recovery::{
+            DanglingInterval, HEARTBEAT_SETTING_KEY, POLICY_SETTING_KEY, RecoveryPolicy,
+            RecoveryRepositoryTrait, RecoveryServiceTrait,
+            dto::recovery_dto::{
+                RecoveryCaseDto, RecoveryReportDto, RecoveryResolution, RecoveryResolveDto,
+            },
+            infra::impl_repository::RecoveryRepository,
+        },
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+        tracker::{TrackerRepository, TrackerRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Cases found on startup under the `Ask` policy, waiting for the user to decide.
+#[derive(Default)]
+struct PendingRecovery {
+    last_heartbeat: Option<DateTime<Utc>>,
+    intervals: Vec<DanglingInterval>,
+}
+
+impl PendingRecovery {
+    fn cases(&self) -> Vec<RecoveryCaseDto> {
+        self.intervals
+            .iter()
+            .cloned()
+            .map(|interval| RecoveryCaseDto::new(interval, self.last_heartbeat))
+            .collect()
+    }
+}
+
+pub struct RecoveryService {
+    pool: SqlitePool,
+    repo: Arc<dyn RecoveryRepositoryTrait + Send + Sync>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+    pending: Mutex<PendingRecovery>,
+}
+
+impl RecoveryService {
+    async fn policy(&self, conn: &mut SqliteConnection) -> Result<RecoveryPolicy, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, POLICY_SETTING_KEY)
+            .await?
+        else {
+            return Ok(RecoveryPolicy::default());
+        };
+
+        Ok(setting.value.parse().unwrap_or_else(|e| {
+            log::warn!("{}, using the default policy", e);
+            RecoveryPolicy::default()
+        }))
+    }
+
+    async fn last_heartbeat(
+        &self,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let setting = self
+            .settings_repo
+            .get_setting(conn, HEARTBEAT_SETTING_KEY)
+            .await?;
+
+        Ok(setting.and_then(|setting| {
+            DateTime::parse_from_rfc3339(&setting.value)
+                .map(|heartbeat| heartbeat.with_timezone(&Utc))
+                .map_err(|e| log::warn!("Invalid heartbeat '{}': {}", setting.value, e))
+                .ok()
+        }))
+    }
+
+    /// Closes the interval unless it was stopped in the meantime.
+    async fn close_interval(
+        &self,
+        conn: &mut SqliteConnection,
+        duration_id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let Some(mut duration) = self
+            .tracker_repo
+            .get_line_duration(conn, duration_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if duration.ended_at.is_some() {
+            return Ok(());
+        }
+
+        let line = self
+            .tracker_repo
+            .get_entry_line(conn, duration.entry_line_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Line with id {} not found", duration.entry_line_id))
+            })?;
+        let entry_id = line.entry_id;
+        let siblings = self.tracker_repo.get_line_durations(conn, line).await?;
+
+        duration.ended_at = Some(ended_at);
+        duration.updated_at = Utc::now();
+        duration
+            .validate(&siblings)
+            .map_err(AppError::ValidationError)?;
+
+        let before = self.history.capture(conn, vec![entry_id]).await?;
+        self.tracker_repo
+            .update_line_duration(conn, duration)
+            .await?;
+        self.history
+            .record(conn, "Close interrupted interval", before, &[])
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl RecoveryServiceTrait for RecoveryService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn RecoveryServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(RecoveryRepository {}),
+            tracker_repo: Arc::new(TrackerRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+            history: HistoryRecorder::default(),
+            pending: Mutex::new(PendingRecovery::default()),
+        })
+    }
+
+    fn record_heartbeat(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(HEARTBEAT_SETTING_KEY, Utc::now().to_rfc3339()),
+                )
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn recover(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<RecoveryReportDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let policy = self.policy(&mut tx).await?;
+            let last_heartbeat = self.last_heartbeat(&mut tx).await?;
+            let intervals = self
+                .repo
+                .get_open_intervals(&mut tx, last_heartbeat)
+                .await?;
+
+            let mut report = RecoveryReportDto {
+                policy,
+                last_heartbeat,
+                ..Default::default()
+            };
+
+            let mut pending = Vec::new();
+            for interval in intervals {
+                match (policy, last_heartbeat) {
+                    (RecoveryPolicy::KeepRunning, _) => {
+                        report
+                            .kept
+                            .push(RecoveryCaseDto::new(interval, last_heartbeat));
+                    }
+                    // Without a heartbeat there is nothing to close at, so the user decides
+                    (RecoveryPolicy::CloseAtHeartbeat, Some(heartbeat)) => {
+                        let ended_at = interval.end_at_heartbeat(heartbeat);
+                        match self
+                            .close_interval(&mut tx, interval.duration_id, ended_at)
+                            .await
+                        {
+                            Ok(()) => report
+                                .closed
+                                .push(RecoveryCaseDto::new(interval, last_heartbeat)),
+                            Err(AppError::ValidationError(e)) => {
+                                log::warn!("Could not close {}: {}", interval, e);
+                                pending.push(interval);
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    (RecoveryPolicy::CloseAtHeartbeat, None) | (RecoveryPolicy::Ask, _) => {
+                        pending.push(interval);
+                    }
+                }
+            }
+
+            tx.commit().await?;
+
+            let mut guard = self.pending.lock().await;
+            *guard = PendingRecovery {
+                last_heartbeat,
+                intervals: pending,
+            };
+            report.pending = guard.cases();
+
+            Ok(report)
+        })
+    }
+
+    fn get_pending_cases(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<RecoveryCaseDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move { Ok(self.pending.lock().await.cases()) })
+    }
+
+    fn resolve_case(
+        &self,
+        dto: RecoveryResolveDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<RecoveryCaseDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut guard = self.pending.lock().await;
+
+            let index = guard
+                .intervals
+                .iter()
+                .position(|interval| interval.duration_id == dto.duration_id)
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "Recovery case for duration {} not found",
+                        dto.duration_id
+                    ))
+                })?;
+            let interval = &guard.intervals[index];
+
+            let ended_at = match dto.resolution {
+                RecoveryResolution::KeepRunning => None,
+                RecoveryResolution::CloseAtHeartbeat => {
+                    let heartbeat = guard.last_heartbeat.ok_or_else(|| {
+                        AppError::ValidationError(
+                            "No heartbeat was recorded by the previous session".to_string(),
+                        )
+                    })?;
+                    Some(interval.end_at_heartbeat(heartbeat))
+                }
+                RecoveryResolution::CloseAt { ended_at } => {
+                    if ended_at > Utc::now() {
+                        return Err(AppError::ValidationError(
+                            "Interval must not end in the future".to_string(),
+                        ));
+                    }
+                    Some(ended_at)
+                }
+            };
+
+            if let Some(ended_at) = ended_at {
+                let mut tx = self.pool.begin().await?;
+                self.close_interval(&mut tx, interval.duration_id, ended_at)
+                    .await?;
+                tx.commit().await?;
+            }
+
+            guard.intervals.remove(index);
+
+            Ok(guard.cases())
+        })
+    }
+
+    fn get_policy(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<RecoveryPolicy, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.policy(&mut conn).await
+        })
+    }
+
+    fn set_policy(
+        &self,
+        policy: RecoveryPolicy,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<RecoveryPolicy, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(POLICY_SETTING_KEY, policy.as_str().to_string()),
+                )
+                .await?;
+
+            Ok(policy)
+        })
+    }
+}
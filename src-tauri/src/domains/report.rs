@@ -0,0 +1,21 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod report_dto;
+}
+
+mod infra {
+    mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{ReportInterval, start_of_day};
+pub use domain::repository::ReportRepositoryTrait;
+pub use domain::service::ReportServiceTrait;
+pub use dto::report_dto::*;
+pub use infra::impl_service::ReportService;
@@ -0,0 +1,89 @@
+use crate::domains::billing::billable_rate;
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use rust_decimal::Decimal;
+use std::fmt;
+
+/// A tracked interval joined with the line and tracker it belongs to.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct ReportInterval {
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub is_billable: bool,
+    /// The line's rate, else its tracker's, else its client's.
+    pub hourly_rate: Option<String>,
+    pub currency: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ReportInterval {
+    /// Returns the rate and currency the interval bills at, if it is billable and both are known.
+    pub fn billable_rate(&self) -> Option<(Decimal, String)> {
+        billable_rate(self.is_billable, &self.hourly_rate, &self.currency)
+    }
+
+    /// Clamps the interval to `[from, to)`, treating a running interval as ending at `now`.
+    pub fn clamp(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = self.started_at.max(from);
+        let end = self.ended_at.unwrap_or(now).min(to);
+
+        (start < end).then_some((start, end))
+    }
+
+    /// Splits the clamped interval at every local midnight and returns the time spent on each day.
+    pub fn split_by_day<Tz: TimeZone>(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Vec<(NaiveDate, TimeDelta)> {
+        let Some((mut start, end)) = self.clamp(from, to, now) else {
+            return Vec::new();
+        };
+
+        let mut days = Vec::new();
+        while start < end {
+            let day = start.with_timezone(tz).date_naive();
+            let next_midnight = day
+                .checked_add_days(Days::new(1))
+                .map(|next_day| start_of_day(next_day, tz))
+                .unwrap_or(end);
+            let segment_end = next_midnight.min(end);
+
+            days.push((day, segment_end - start));
+            start = segment_end;
+        }
+
+        days
+    }
+}
+
+impl fmt::Display for ReportInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ReportInterval(duration_id: {}, entry_id: {}, line_id: {}, started_at: {}, ended_at: {:?})",
+            self.duration_id, self.entry_id, self.line_id, self.started_at, self.ended_at
+        )
+    }
+}
+
+/// Returns the first instant of `date` in the given time zone as UTC.
+///
+/// Falls back to the first valid hour when a DST change skips midnight.
+pub fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> DateTime<Utc> {
+    (0..24)
+        .filter_map(|hour| NaiveTime::from_hms_opt(hour, 0, 0))
+        .find_map(|time| tz.from_local_datetime(&date.and_time(time)).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+}
@@ -0,0 +1,15 @@
+use crate::domains::report::domain::model::ReportInterval;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait ReportRepositoryTrait {
+    /// Returns every live interval that overlaps `[from, to)`, including running ones.
+    fn get_intervals_in_range<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<ReportInterval>>> + Send + 'a>>;
+}
@@ -0,0 +1,36 @@
+use crate::{
+    domains::report::dto::report_dto::{
+        ReportDto, ReportPeriod, ReportPeriodTotalDto, ReportQueryDto, ReportTagTotalDto,
+        ReportTrackerTotalDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait ReportServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ReportServiceTrait>
+    where
+        Self: Sized;
+
+    fn get_report(
+        &self,
+        dto: ReportQueryDto,
+    ) -> Pin<Box<dyn Future<Output = Result<ReportDto, AppError>> + Send + '_>>;
+
+    fn get_period_totals(
+        &self,
+        dto: ReportQueryDto,
+        period: ReportPeriod,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ReportPeriodTotalDto>, AppError>> + Send + '_>>;
+
+    fn get_tracker_totals(
+        &self,
+        dto: ReportQueryDto,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ReportTrackerTotalDto>, AppError>> + Send + '_>>;
+
+    fn get_tag_totals(
+        &self,
+        dto: ReportQueryDto,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ReportTagTotalDto>, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,87 @@
+use chrono::{Local, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+/// Inclusive range of local calendar days to report on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportQueryDto {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl Default for ReportQueryDto {
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+
+        Self {
+            from: today,
+            to: today,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportPeriodTotalDto {
+    pub period: ReportPeriod,
+    pub start: NaiveDate,
+    pub label: String,
+    pub total_seconds: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportLineTotalDto {
+    pub line_id: i64,
+    pub desc: String,
+    pub total_seconds: i64,
+    pub is_billable: bool,
+    pub currency: Option<String>,
+    /// Price of the finished intervals, `None` when the line is not billed.
+    pub amount: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportTrackerTotalDto {
+    pub entry_id: i64,
+    pub label: String,
+    pub total_seconds: i64,
+    pub currency: Option<String>,
+    /// Sum of the line amounts, `None` when no line is billed.
+    pub amount: Option<Decimal>,
+    pub lines: Vec<ReportLineTotalDto>,
+}
+
+/// Time spent on lines carrying the tag, a line with several tags counts towards each.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportTagTotalDto {
+    pub tag_id: i64,
+    pub name: String,
+    pub total_seconds: i64,
+}
+
+/// Billed amount in one currency, trackers billing in different currencies are never summed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportAmountDto {
+    pub currency: String,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDto {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_seconds: i64,
+    pub days: Vec<ReportPeriodTotalDto>,
+    pub weeks: Vec<ReportPeriodTotalDto>,
+    pub months: Vec<ReportPeriodTotalDto>,
+    pub trackers: Vec<ReportTrackerTotalDto>,
+    pub tags: Vec<ReportTagTotalDto>,
+    pub amounts: Vec<ReportAmountDto>,
+}
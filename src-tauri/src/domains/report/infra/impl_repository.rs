@@ -0,0 +1,44 @@
+use crate::domains::report::{ReportRepositoryTrait, domain::model::ReportInterval};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct ReportRepository;
+
+impl ReportRepositoryTrait for ReportRepository {
+    fn get_intervals_in_range<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<ReportInterval>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            // julianday() normalises the stored formats, older rows use SQLite's default timestamp
+            let intervals = sqlx::query_as::<_, ReportInterval>(
+                r#"
+                SELECT d.id AS duration_id, e.id AS entry_id, e.label AS entry_label,
+                    l.id AS line_id, l.desc AS line_desc, l.is_billable,
+                    coalesce(l.hourly_rate, e.hourly_rate, c.hourly_rate) AS hourly_rate,
+                    CASE WHEN e.hourly_rate IS NOT NULL THEN e.currency
+                        ELSE c.currency END AS currency,
+                    d.started_at, d.ended_at
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                LEFT JOIN client c ON c.id = e.client_id
+                WHERE d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                    AND julianday(d.started_at) < julianday(?)
+                    AND (d.ended_at IS NULL OR julianday(d.ended_at) > julianday(?))
+                ORDER BY d.started_at
+                "#,
+            )
+            .bind(to)
+            .bind(from)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(intervals)
+        })
+    }
+}
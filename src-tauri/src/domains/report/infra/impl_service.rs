@@ -0,0 +1,325 @@
+use crate::{
+    domains::{
+        billing::billable_amount,
+        report::{
+            ReportInterval, ReportRepositoryTrait, ReportServiceTrait,
+            domain::model::start_of_day,
+            dto::report_dto::{
+                ReportAmountDto, ReportDto, ReportLineTotalDto, ReportPeriod, ReportPeriodTotalDto,
+                ReportQueryDto, ReportTagTotalDto, ReportTrackerTotalDto,
+            },
+            infra::impl_repository::ReportRepository,
+        },
+        tag::{LineTag, TagRepository, TagRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeDelta, TimeZone, Utc, Weekday};
+use rust_decimal::Decimal;
+use sqlx::SqlitePool;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
+
+/// Time and amount per line keyed by line id.
+type LineTotals = BTreeMap<i64, LineTotal>;
+
+struct LineTotal {
+    desc: String,
+    spent: TimeDelta,
+    is_billable: bool,
+    currency: Option<String>,
+    amount: Option<Decimal>,
+}
+
+pub struct ReportService {
+    pool: SqlitePool,
+    repo: Arc<dyn ReportRepositoryTrait + Send + Sync>,
+    tag_repo: Arc<dyn TagRepositoryTrait + Send + Sync>,
+}
+
+impl ReportService {
+    async fn build_report(&self, dto: ReportQueryDto) -> Result<ReportDto, AppError> {
+        if dto.from > dto.to {
+            return Err(AppError::ValidationError(
+                "Report start date must not be after its end date".to_string(),
+            ));
+        }
+
+        let (from, to) = range_bounds(&dto, &Local)?;
+
+        let mut conn = self.pool.acquire().await?;
+        let intervals = self
+            .repo
+            .get_intervals_in_range(&mut conn, from, to)
+            .await?;
+        let line_tags = self.tag_repo.get_tags_for_all_lines(&mut conn).await?;
+
+        Ok(aggregate(
+            &dto,
+            &intervals,
+            &line_tags,
+            from,
+            to,
+            Utc::now(),
+            &Local,
+        ))
+    }
+}
+
+impl ReportServiceTrait for ReportService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ReportServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(ReportRepository {}),
+            tag_repo: Arc::new(TagRepository {}),
+        })
+    }
+
+    fn get_report(
+        &self,
+        dto: ReportQueryDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<ReportDto, AppError>> + Send + '_>> {
+        Box::pin(async move { self.build_report(dto).await })
+    }
+
+    fn get_period_totals(
+        &self,
+        dto: ReportQueryDto,
+        period: ReportPeriod,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<ReportPeriodTotalDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let report = self.build_report(dto).await?;
+
+            Ok(match period {
+                ReportPeriod::Day => report.days,
+                ReportPeriod::Week => report.weeks,
+                ReportPeriod::Month => report.months,
+            })
+        })
+    }
+
+    fn get_tracker_totals(
+        &self,
+        dto: ReportQueryDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<ReportTrackerTotalDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let report = self.build_report(dto).await?;
+
+            Ok(report.trackers)
+        })
+    }
+
+    fn get_tag_totals(
+        &self,
+        dto: ReportQueryDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ReportTagTotalDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let report = self.build_report(dto).await?;
+
+            Ok(report.tags)
+        })
+    }
+}
+
+/// Converts the inclusive local date range into a half-open UTC range.
+fn range_bounds<Tz: TimeZone>(
+    dto: &ReportQueryDto,
+    tz: &Tz,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+    let day_after = dto
+        .to
+        .checked_add_days(Days::new(1))
+        .ok_or_else(|| AppError::ValidationError("Report end date is out of range".to_string()))?;
+
+    Ok((start_of_day(dto.from, tz), start_of_day(day_after, tz)))
+}
+
+fn aggregate<Tz: TimeZone>(
+    dto: &ReportQueryDto,
+    intervals: &[ReportInterval],
+    line_tags: &[LineTag],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    now: DateTime<Utc>,
+    tz: &Tz,
+) -> ReportDto {
+    let mut days: BTreeMap<NaiveDate, TimeDelta> = BTreeMap::new();
+    let mut trackers: BTreeMap<i64, (String, LineTotals)> = BTreeMap::new();
+
+    for interval in intervals {
+        let Some((start, end)) = interval.clamp(from, to, now) else {
+            continue;
+        };
+
+        let (_, lines) = trackers
+            .entry(interval.entry_id)
+            .or_insert_with(|| (interval.entry_label.clone(), BTreeMap::new()));
+        let line = lines.entry(interval.line_id).or_insert_with(|| LineTotal {
+            desc: interval.line_desc.clone(),
+            spent: TimeDelta::zero(),
+            is_billable: interval.is_billable,
+            currency: interval.currency.clone(),
+            amount: None,
+        });
+
+        for (day, spent) in interval.split_by_day(from, to, now, tz) {
+            *days.entry(day).or_insert_with(TimeDelta::zero) += spent;
+            line.spent += spent;
+        }
+
+        // Priced once per interval, not once per day, and only once finished, like the
+        // tracker views, so a running interval adds its time but no amount yet
+        if let Some((hourly_rate, _)) = interval.billable_rate() {
+            let amount = line.amount.get_or_insert(Decimal::ZERO);
+            if interval.ended_at.is_some() {
+                *amount += billable_amount(hourly_rate, (end - start).num_seconds());
+            }
+        }
+    }
+
+    let total = days
+        .values()
+        .fold(TimeDelta::zero(), |acc, spent| acc + *spent);
+
+    let line_spent: HashMap<i64, TimeDelta> = trackers
+        .values()
+        .flat_map(|(_, lines)| lines.iter())
+        .map(|(line_id, line)| (*line_id, line.spent))
+        .collect();
+    let mut tags: BTreeMap<i64, (String, TimeDelta)> = BTreeMap::new();
+    for line_tag in line_tags {
+        if let Some(spent) = line_spent.get(&line_tag.line_id) {
+            let (_, tag_spent) = tags
+                .entry(line_tag.tag_id)
+                .or_insert_with(|| (line_tag.name.clone(), TimeDelta::zero()));
+            *tag_spent += *spent;
+        }
+    }
+
+    let mut tag_dtos: Vec<ReportTagTotalDto> = tags
+        .into_iter()
+        .map(|(tag_id, (name, spent))| ReportTagTotalDto {
+            tag_id,
+            name,
+            total_seconds: spent.num_seconds(),
+        })
+        .collect();
+    tag_dtos.sort_by_key(|tag| Reverse(tag.total_seconds));
+
+    let mut tracker_dtos: Vec<ReportTrackerTotalDto> = trackers
+        .into_iter()
+        .map(|(entry_id, (label, lines))| {
+            let spent = lines
+                .values()
+                .fold(TimeDelta::zero(), |acc, line| acc + line.spent);
+            let mut line_dtos: Vec<ReportLineTotalDto> = lines
+                .into_iter()
+                .map(|(line_id, line)| ReportLineTotalDto {
+                    line_id,
+                    desc: line.desc,
+                    total_seconds: line.spent.num_seconds(),
+                    is_billable: line.is_billable,
+                    currency: line.currency,
+                    amount: line.amount,
+                })
+                .collect();
+            line_dtos.sort_by_key(|line| Reverse(line.total_seconds));
+
+            // Lines never override the currency, so a tracker bills in a single one
+            let currency = line_dtos.iter().find_map(|line| line.currency.clone());
+            let amount = line_dtos
+                .iter()
+                .filter_map(|line| line.amount)
+                .reduce(|total, amount| total + amount);
+
+            ReportTrackerTotalDto {
+                entry_id,
+                label,
+                total_seconds: spent.num_seconds(),
+                currency,
+                amount,
+                lines: line_dtos,
+            }
+        })
+        .collect();
+    tracker_dtos.sort_by_key(|tracker| Reverse(tracker.total_seconds));
+
+    let mut amounts: BTreeMap<String, Decimal> = BTreeMap::new();
+    for tracker in &tracker_dtos {
+        if let (Some(currency), Some(amount)) = (&tracker.currency, tracker.amount) {
+            *amounts.entry(currency.clone()).or_insert(Decimal::ZERO) += amount;
+        }
+    }
+
+    ReportDto {
+        from: dto.from,
+        to: dto.to,
+        total_seconds: total.num_seconds(),
+        days: period_totals(&days, ReportPeriod::Day),
+        weeks: period_totals(&days, ReportPeriod::Week),
+        months: period_totals(&days, ReportPeriod::Month),
+        trackers: tracker_dtos,
+        tags: tag_dtos,
+        amounts: amounts
+            .into_iter()
+            .map(|(currency, amount)| ReportAmountDto { currency, amount })
+            .collect(),
+    }
+}
+
+/// Rolls the per-day totals up into the requested period.
+fn period_totals(
+    days: &BTreeMap<NaiveDate, TimeDelta>,
+    period: ReportPeriod,
+) -> Vec<ReportPeriodTotalDto> {
+    let mut periods: BTreeMap<NaiveDate, TimeDelta> = BTreeMap::new();
+    for (day, spent) in days {
+        *periods
+            .entry(period_start(*day, period))
+            .or_insert_with(TimeDelta::zero) += *spent;
+    }
+
+    periods
+        .into_iter()
+        .map(|(start, spent)| ReportPeriodTotalDto {
+            period,
+            start,
+            label: period_label(start, period),
+            total_seconds: spent.num_seconds(),
+        })
+        .collect()
+}
+
+fn period_start(day: NaiveDate, period: ReportPeriod) -> NaiveDate {
+    match period {
+        ReportPeriod::Day => day,
+        ReportPeriod::Week => {
+            let week = day.iso_week();
+            NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon).unwrap_or(day)
+        }
+        ReportPeriod::Month => day.with_day(1).unwrap_or(day),
+    }
+}
+
+fn period_label(start: NaiveDate, period: ReportPeriod) -> String {
+    match period {
+        ReportPeriod::Day => start.format("%Y-%m-%d").to_string(),
+        ReportPeriod::Week => {
+            let week = start.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        ReportPeriod::Month => start.format("%Y-%m").to_string(),
+    }
+}
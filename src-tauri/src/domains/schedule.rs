@@ -0,0 +1,25 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod schedule_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    FORCED_STOP_EVENT, ForcedStop, ForcedStopEntry, ForcedStopReason, SCHEDULE_POLL_INTERVAL,
+    STOP_RULES_SETTING_KEY, StopRules,
+};
+pub use domain::repository::ScheduleRepositoryTrait;
+pub use domain::service::ScheduleServiceTrait;
+pub use dto::schedule_dto::*;
+pub use infra::impl_repository::ScheduleRepository;
+pub use infra::impl_service::ScheduleService;
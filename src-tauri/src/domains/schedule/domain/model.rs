@@ -0,0 +1,294 @@
+use chrono::{DateTime, Days, NaiveTime, TimeDelta, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Setting that holds the `StopRules` as JSON.
+pub const STOP_RULES_SETTING_KEY: &str = "schedule.stop_rules";
+/// Event sent to the frontend with the intervals the scheduler has just stopped.
+pub const FORCED_STOP_EVENT: &str = "tracking-force-stopped";
+pub const SCHEDULE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+const MAX_DURATION_LIMIT_MINUTES: u32 = 7 * 24 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForcedStopReason {
+    DailyCutoff,
+    MaxDuration,
+}
+
+impl ForcedStopReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForcedStopReason::DailyCutoff => "daily_cutoff",
+            ForcedStopReason::MaxDuration => "max_duration",
+        }
+    }
+}
+
+impl FromStr for ForcedStopReason {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "daily_cutoff" => Ok(ForcedStopReason::DailyCutoff),
+            "max_duration" => Ok(ForcedStopReason::MaxDuration),
+            _ => Err(format!("Unknown forced stop reason '{}'", value)),
+        }
+    }
+}
+
+/// When running intervals are stopped without the user doing it. Both rules are off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StopRules {
+    /// Local time of day at which every running interval is stopped.
+    pub daily_cutoff: Option<NaiveTime>,
+    /// Longest a single interval may run.
+    pub max_duration_minutes: Option<u32>,
+}
+
+impl StopRules {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(minutes) = self.max_duration_minutes
+            && !(1..=MAX_DURATION_LIMIT_MINUTES).contains(&minutes)
+        {
+            return Err(format!(
+                "Maximum duration must be between 1 and {} minutes",
+                MAX_DURATION_LIMIT_MINUTES
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The first moment a rule stops an interval started at `started_at`, with the rule
+    /// responsible. Cutoffs are read in `tz`.
+    pub fn stop_for<Tz: TimeZone>(
+        &self,
+        started_at: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Option<(ForcedStopReason, DateTime<Utc>)> {
+        let cutoff = self
+            .daily_cutoff
+            .and_then(|cutoff| next_cutoff(cutoff, started_at, tz))
+            .map(|at| (ForcedStopReason::DailyCutoff, at));
+        let max_duration = self
+            .max_duration_minutes
+            .map(|minutes| started_at + TimeDelta::minutes(minutes.into()))
+            .map(|at| (ForcedStopReason::MaxDuration, at));
+
+        match (cutoff, max_duration) {
+            (Some(cutoff), Some(max_duration)) if max_duration.1 < cutoff.1 => Some(max_duration),
+            (Some(cutoff), _) => Some(cutoff),
+            (None, max_duration) => max_duration,
+        }
+    }
+}
+
+/// The first cutoff strictly after `after`. Today's may already have passed, and a day
+/// can lack the time entirely when the clocks go forward.
+fn next_cutoff<Tz: TimeZone>(
+    cutoff: NaiveTime,
+    after: DateTime<Utc>,
+    tz: &Tz,
+) -> Option<DateTime<Utc>> {
+    let date = after.with_timezone(tz).date_naive();
+
+    (0..=2)
+        .filter_map(|days| {
+            let date = date.checked_add_days(Days::new(days))?;
+            tz.from_local_datetime(&date.and_time(cutoff)).earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .find(|at| *at > after)
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct ForcedStop {
+    pub id: i64,
+    pub duration_id: i64,
+    pub reason: String,
+    pub stopped_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ForcedStop {
+    pub fn new(duration_id: i64, reason: ForcedStopReason, stopped_at: DateTime<Utc>) -> Self {
+        Self {
+            id: 0,
+            duration_id,
+            reason: reason.as_str().to_string(),
+            stopped_at,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl fmt::Display for ForcedStop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ForcedStop(id: {}, duration_id: {}, reason: {}, stopped_at: {})",
+            self.id, self.duration_id, self.reason, self.stopped_at
+        )
+    }
+}
+
+/// A forced stop joined with the interval, line and tracker it stopped.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct ForcedStopEntry {
+    pub id: i64,
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub reason: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime};
+
+    /// UTC+1 that moves to UTC+2 at 01:00 UTC on 2026-03-29, so local 02:00 to 03:00 never
+    /// happens that day.
+    #[derive(Debug, Clone)]
+    struct SpringForward;
+
+    impl SpringForward {
+        fn switch() -> NaiveDateTime {
+            utc(2026, 3, 29, 1, 0).naive_utc()
+        }
+
+        fn offset(hours: i32) -> FixedOffset {
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for SpringForward {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            SpringForward
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let before = *local - TimeDelta::hours(1) < Self::switch();
+            let after = *local - TimeDelta::hours(2) >= Self::switch();
+
+            match (before, after) {
+                (true, true) => MappedLocalTime::Ambiguous(Self::offset(1), Self::offset(2)),
+                (true, false) => MappedLocalTime::Single(Self::offset(1)),
+                (false, true) => MappedLocalTime::Single(Self::offset(2)),
+                (false, false) => MappedLocalTime::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            if *utc < Self::switch() {
+                Self::offset(1)
+            } else {
+                Self::offset(2)
+            }
+        }
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn cutoff_at(hour: u32, minute: u32) -> StopRules {
+        StopRules {
+            daily_cutoff: NaiveTime::from_hms_opt(hour, minute, 0),
+            max_duration_minutes: None,
+        }
+    }
+
+    #[test]
+    fn stops_at_the_cutoff_later_the_same_day() {
+        let stop = cutoff_at(18, 0).stop_for(utc(2026, 3, 10, 9, 0), &SpringForward::offset(1));
+
+        assert_eq!(
+            stop,
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 10, 17, 0)))
+        );
+    }
+
+    #[test]
+    fn interval_started_after_the_cutoff_runs_until_the_next_one() {
+        let tz = SpringForward::offset(1);
+
+        let stop = cutoff_at(18, 0).stop_for(utc(2026, 3, 10, 18, 30), &tz);
+        assert_eq!(
+            stop,
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 11, 17, 0)))
+        );
+
+        // Started right on the cutoff, which is not after it
+        let stop = cutoff_at(18, 0).stop_for(utc(2026, 3, 10, 17, 0), &tz);
+        assert_eq!(
+            stop,
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 11, 17, 0)))
+        );
+    }
+
+    #[test]
+    fn cutoff_skipped_by_the_clocks_going_forward_moves_to_the_next_day() {
+        let stop = cutoff_at(2, 30).stop_for(utc(2026, 3, 28, 23, 0), &SpringForward);
+
+        assert_eq!(
+            stop,
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 30, 0, 30)))
+        );
+    }
+
+    #[test]
+    fn cutoff_after_the_clocks_went_forward_uses_the_new_offset() {
+        let stop = cutoff_at(18, 0).stop_for(utc(2026, 3, 29, 9, 0), &SpringForward);
+
+        assert_eq!(
+            stop,
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 29, 16, 0)))
+        );
+    }
+
+    #[test]
+    fn earlier_rule_wins() {
+        let tz = SpringForward::offset(1);
+        let rules = StopRules {
+            max_duration_minutes: Some(60),
+            ..cutoff_at(18, 0)
+        };
+
+        assert_eq!(
+            rules.stop_for(utc(2026, 3, 10, 9, 0), &tz),
+            Some((ForcedStopReason::MaxDuration, utc(2026, 3, 10, 10, 0)))
+        );
+        assert_eq!(
+            rules.stop_for(utc(2026, 3, 10, 16, 30), &tz),
+            Some((ForcedStopReason::DailyCutoff, utc(2026, 3, 10, 17, 0)))
+        );
+    }
+
+    #[test]
+    fn no_rules_never_stop() {
+        let stop = StopRules::default().stop_for(utc(2026, 3, 10, 9, 0), &SpringForward);
+
+        assert_eq!(stop, None);
+    }
+}
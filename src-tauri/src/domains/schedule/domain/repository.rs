@@ -0,0 +1,20 @@
+use crate::domains::schedule::domain::model::{ForcedStop, ForcedStopEntry};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait ScheduleRepositoryTrait {
+    fn create_forced_stop<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        forced_stop: ForcedStop,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<ForcedStop>> + Send + 'a>>;
+
+    /// Returns the forced stops since `since`, or all of them, newest first.
+    fn get_forced_stops<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        since: Option<DateTime<Utc>>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<ForcedStopEntry>>> + Send + 'a>>;
+}
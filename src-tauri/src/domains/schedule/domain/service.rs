@@ -0,0 +1,33 @@
+use crate::{
+    domains::schedule::{domain::model::StopRules, dto::schedule_dto::ForcedStopDto},
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait ScheduleServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ScheduleServiceTrait>
+    where
+        Self: Sized;
+
+    /// Stops every running interval a rule applies to by `now`, at the moment the rule
+    /// fired rather than when this runs, and records why.
+    fn enforce_rules(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ForcedStopDto>, AppError>> + Send + '_>>;
+
+    fn get_rules(&self) -> Pin<Box<dyn Future<Output = Result<StopRules, AppError>> + Send + '_>>;
+
+    fn set_rules(
+        &self,
+        rules: StopRules,
+    ) -> Pin<Box<dyn Future<Output = Result<StopRules, AppError>> + Send + '_>>;
+
+    /// Returns the forced stops since `since`, or all of them, newest first.
+    fn get_forced_stops(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ForcedStopDto>, AppError>> + Send + '_>>;
+}
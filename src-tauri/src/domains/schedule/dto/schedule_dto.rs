@@ -0,0 +1,37 @@
+use crate::domains::schedule::{ForcedStopEntry, ForcedStopReason};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForcedStopDto {
+    pub id: i64,
+    pub duration_id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub line_id: i64,
+    pub line_desc: String,
+    pub reason: ForcedStopReason,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: DateTime<Utc>,
+}
+
+impl From<ForcedStopEntry> for ForcedStopDto {
+    fn from(entry: ForcedStopEntry) -> Self {
+        let reason = entry.reason.parse().unwrap_or_else(|e| {
+            log::warn!("{}, treating it as a daily cutoff", e);
+            ForcedStopReason::DailyCutoff
+        });
+
+        Self {
+            id: entry.id,
+            duration_id: entry.duration_id,
+            entry_id: entry.entry_id,
+            entry_label: entry.entry_label,
+            line_id: entry.line_id,
+            line_desc: entry.line_desc,
+            reason,
+            started_at: entry.started_at,
+            stopped_at: entry.stopped_at,
+        }
+    }
+}
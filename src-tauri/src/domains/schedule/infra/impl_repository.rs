@@ -0,0 +1,63 @@
+use crate::domains::schedule::{
+    ScheduleRepositoryTrait,
+    domain::model::{ForcedStop, ForcedStopEntry},
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct ScheduleRepository;
+
+impl ScheduleRepositoryTrait for ScheduleRepository {
+    fn create_forced_stop<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        forced_stop: ForcedStop,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<ForcedStop>> + Send + 'a>> {
+        Box::pin(async move {
+            let forced_stop = sqlx::query_as::<_, ForcedStop>(
+                r#"
+                INSERT INTO forced_stop (duration_id, reason, stopped_at, created_at)
+                VALUES (?, ?, ?, ?)
+                RETURNING id, duration_id, reason, stopped_at, created_at
+                "#,
+            )
+            .bind(forced_stop.duration_id)
+            .bind(&forced_stop.reason)
+            .bind(forced_stop.stopped_at)
+            .bind(forced_stop.created_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(forced_stop)
+        })
+    }
+
+    fn get_forced_stops<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        since: Option<DateTime<Utc>>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<ForcedStopEntry>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let forced_stops = sqlx::query_as::<_, ForcedStopEntry>(
+                r#"
+                SELECT f.id, f.duration_id, e.id AS entry_id, e.label AS entry_label,
+                    l.id AS line_id, l.desc AS line_desc, f.reason, d.started_at, f.stopped_at
+                FROM forced_stop f
+                JOIN tracker_entry_line_duration d ON d.id = f.duration_id
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE ? IS NULL OR julianday(f.stopped_at) >= julianday(?)
+                ORDER BY f.stopped_at DESC, f.id DESC
+                "#,
+            )
+            .bind(since)
+            .bind(since)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(forced_stops)
+        })
+    }
+}
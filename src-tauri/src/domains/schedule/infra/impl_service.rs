@@ -0,0 +1,175 @@
+use crate::{
+    domains::{
+        recovery::{RecoveryRepository, RecoveryRepositoryTrait},
+        schedule::{
+            ForcedStop, STOP_RULES_SETTING_KEY, ScheduleRepositoryTrait, ScheduleServiceTrait,
+            StopRules, dto::schedule_dto::ForcedStopDto,
+            infra::impl_repository::ScheduleRepository,
+        },
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+        tracker::{TrackerService, TrackerServiceTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Local, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+pub struct ScheduleService {
+    pool: SqlitePool,
+    repo: Arc<dyn ScheduleRepositoryTrait + Send + Sync>,
+    recovery_repo: Arc<dyn RecoveryRepositoryTrait + Send + Sync>,
+    tracker_service: Arc<dyn TrackerServiceTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+}
+
+impl ScheduleService {
+    async fn rules(&self, conn: &mut SqliteConnection) -> Result<StopRules, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, STOP_RULES_SETTING_KEY)
+            .await?
+        else {
+            return Ok(StopRules::default());
+        };
+
+        Ok(serde_json::from_str(&setting.value).unwrap_or_else(|e| {
+            log::warn!(
+                "Invalid stop rules '{}': {}, no rules apply",
+                setting.value,
+                e
+            );
+            StopRules::default()
+        }))
+    }
+}
+
+impl ScheduleServiceTrait for ScheduleService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn ScheduleServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            tracker_service: TrackerService::create_service(pool.clone()),
+            pool,
+            repo: Arc::new(ScheduleRepository {}),
+            recovery_repo: Arc::new(RecoveryRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+        })
+    }
+
+    fn enforce_rules(
+        &self,
+        now: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ForcedStopDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let (rules, intervals) = {
+                let mut conn = self.pool.acquire().await?;
+
+                let rules = self.rules(&mut conn).await?;
+                if rules == StopRules::default() {
+                    return Ok(Vec::new());
+                }
+
+                let intervals = self
+                    .recovery_repo
+                    .get_open_intervals(&mut conn, None)
+                    .await?;
+                (rules, intervals)
+            };
+
+            let mut stopped = Vec::new();
+            for interval in intervals {
+                let Some((reason, stopped_at)) = rules.stop_for(interval.started_at, &Local) else {
+                    continue;
+                };
+                if stopped_at > now {
+                    continue;
+                }
+
+                // Stopped like any other stop, so it lands in the history and can be undone
+                match self
+                    .tracker_service
+                    .stop_interval_at(interval.duration_id, stopped_at)
+                    .await
+                {
+                    Ok(Some(_)) => {}
+                    Ok(None) => continue,
+                    Err(AppError::ValidationError(e)) => {
+                        log::warn!("Could not stop {}: {}", interval, e);
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+
+                let mut conn = self.pool.acquire().await?;
+                let forced_stop = self
+                    .repo
+                    .create_forced_stop(
+                        &mut conn,
+                        ForcedStop::new(interval.duration_id, reason, stopped_at),
+                    )
+                    .await?;
+                log::info!("Stopped {} for {}", interval, reason.as_str());
+
+                stopped.push(ForcedStopDto {
+                    id: forced_stop.id,
+                    duration_id: interval.duration_id,
+                    entry_id: interval.entry_id,
+                    entry_label: interval.entry_label,
+                    line_id: interval.line_id,
+                    line_desc: interval.line_desc,
+                    reason,
+                    started_at: interval.started_at,
+                    stopped_at,
+                });
+            }
+
+            Ok(stopped)
+        })
+    }
+
+    fn get_rules(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<StopRules, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.rules(&mut conn).await
+        })
+    }
+
+    fn set_rules(
+        &self,
+        rules: StopRules,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<StopRules, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            rules.validate().map_err(AppError::ValidationError)?;
+            let value = serde_json::to_string(&rules)
+                .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(&mut conn, AppSetting::new(STOP_RULES_SETTING_KEY, value))
+                .await?;
+
+            Ok(rules)
+        })
+    }
+
+    fn get_forced_stops(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ForcedStopDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let forced_stops = self.repo.get_forced_stops(&mut conn, since).await?;
+
+            Ok(forced_stops.into_iter().map(ForcedStopDto::from).collect())
+        })
+    }
+}
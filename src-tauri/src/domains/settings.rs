@@ -0,0 +1,13 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+}
+
+mod infra {
+    pub mod impl_repository;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::AppSetting;
+pub use domain::repository::SettingsRepositoryTrait;
+pub use infra::impl_repository::SettingsRepository;
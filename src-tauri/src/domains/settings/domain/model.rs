@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// A persisted application setting, values are stored as text and parsed by their owner.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct AppSetting {
+    pub key: String,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AppSetting {
+    pub fn new(key: &str, value: String) -> Self {
+        Self {
+            key: key.to_string(),
+            value,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl fmt::Display for AppSetting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AppSetting(key: {}, value: {}, updated_at: {})",
+            self.key, self.value, self.updated_at
+        )
+    }
+}
@@ -0,0 +1,19 @@
+use crate::domains::settings::domain::model::AppSetting;
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait SettingsRepositoryTrait {
+    fn get_setting<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        key: &'a str,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<AppSetting>>> + Send + 'a>>;
+
+    /// Inserts the setting or overwrites the value stored under its key.
+    fn set_setting<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        setting: AppSetting,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<AppSetting>> + Send + 'a>>;
+}
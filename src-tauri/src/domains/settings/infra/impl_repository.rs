@@ -0,0 +1,52 @@
+use crate::domains::settings::{SettingsRepositoryTrait, domain::model::AppSetting};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct SettingsRepository;
+
+impl SettingsRepositoryTrait for SettingsRepository {
+    fn get_setting<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        key: &'a str,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<AppSetting>>> + Send + 'a>> {
+        Box::pin(async move {
+            let setting = sqlx::query_as::<_, AppSetting>(
+                r#"
+                SELECT key, value, updated_at
+                FROM app_setting
+                WHERE key = ?
+                "#,
+            )
+            .bind(key)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(setting)
+        })
+    }
+
+    fn set_setting<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        setting: AppSetting,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<AppSetting>> + Send + 'a>> {
+        Box::pin(async move {
+            let setting = sqlx::query_as::<_, AppSetting>(
+                r#"
+                INSERT INTO app_setting (key, value, updated_at)
+                VALUES (?, ?, ?)
+                ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+                RETURNING key, value, updated_at
+                "#,
+            )
+            .bind(&setting.key)
+            .bind(&setting.value)
+            .bind(setting.updated_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(setting)
+        })
+    }
+}
@@ -0,0 +1,22 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod tag_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{LineTag, Tag};
+pub use domain::repository::TagRepositoryTrait;
+pub use domain::service::TagServiceTrait;
+pub use dto::tag_dto::*;
+pub use infra::impl_repository::TagRepository;
+pub use infra::impl_service::TagService;
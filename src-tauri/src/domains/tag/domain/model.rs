@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+const MAX_NAME_LENGTH: usize = 50;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Tag {
+    pub fn new(id: i64, name: String) -> Self {
+        Self {
+            id,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Trims the name in place and checks that it is usable.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        self.name = self.name.trim().to_string();
+
+        if self.name.is_empty() {
+            return Err("Tag name must not be empty".to_string());
+        }
+        if self.name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "Tag name must not be longer than {} characters",
+                MAX_NAME_LENGTH
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Tag {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tag(id: {}, name: {}, created_at: {}, updated_at: {})",
+            self.id, self.name, self.created_at, self.updated_at
+        )
+    }
+}
+
+/// A tag attached to a line.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct LineTag {
+    pub line_id: i64,
+    pub tag_id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl fmt::Display for LineTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LineTag(line_id: {}, tag_id: {}, name: {})",
+            self.line_id, self.tag_id, self.name
+        )
+    }
+}
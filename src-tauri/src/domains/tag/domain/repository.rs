@@ -0,0 +1,79 @@
+use crate::domains::tag::domain::model::{LineTag, Tag};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait TagRepositoryTrait {
+    /* Tags */
+    fn create_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Tag>> + Send + 'a>>;
+
+    fn get_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<Tag>>> + Send + 'a>>;
+
+    /// Names are compared case-insensitively.
+    fn get_tag_by_name<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        name: String,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<Tag>>> + Send + 'a>>;
+
+    fn get_all_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<Tag>>> + Send + 'a>>;
+
+    fn update_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Tag>> + Send + 'a>>;
+
+    /// Removes the tag for good, its line links go with it.
+    fn delete_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /* Line links */
+    fn get_tags_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineTag>>> + Send + 'a>>;
+
+    fn get_tags_for_all_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineTag>>> + Send + 'a>>;
+
+    /// Returns the ids of lines that carry every one of the given tags.
+    fn get_line_ids_with_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag_ids: Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<i64>>> + Send + 'a>>;
+
+    /// Replaces the tags of the line with the given set.
+    fn set_line_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        tag_ids: Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Re-points every link of `source` to `target`, lines carrying both keep a single link.
+    fn move_line_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        source: Tag,
+        target: Tag,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+}
@@ -0,0 +1,45 @@
+use crate::{
+    domains::tag::dto::tag_dto::{
+        LineTagsUpdateDto, TagCreateDto, TagDeleteDto, TagMergeDto, TagUpdateDto, TagViewDto,
+    },
+    error::AppError,
+};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait TagServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TagServiceTrait>
+    where
+        Self: Sized;
+
+    fn create_tag(
+        &self,
+        dto: TagCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>>;
+
+    fn get_tags(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TagViewDto>, AppError>> + Send + '_>>;
+
+    fn rename_tag(
+        &self,
+        dto: TagUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>>;
+
+    /// Moves every line of the source tag to the target tag and deletes the source.
+    fn merge_tags(
+        &self,
+        dto: TagMergeDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>>;
+
+    fn delete_tag(
+        &self,
+        dto: TagDeleteDto,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Replaces the tags of a line and returns the new set.
+    fn set_line_tags(
+        &self,
+        dto: LineTagsUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TagViewDto>, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,63 @@
+use crate::domains::tag::{LineTag, Tag};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagViewDto {
+    pub id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Tag> for TagViewDto {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+impl From<LineTag> for TagViewDto {
+    fn from(line_tag: LineTag) -> Self {
+        Self {
+            id: line_tag.tag_id,
+            name: line_tag.name,
+            created_at: line_tag.created_at,
+            updated_at: line_tag.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagCreateDto {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagUpdateDto {
+    pub id: i64,
+    pub name: String,
+}
+
+/// Folds `source_id` into `target_id`, the source tag is removed afterwards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagMergeDto {
+    pub source_id: i64,
+    pub target_id: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagDeleteDto {
+    pub id: i64,
+}
+
+/// Replaces the tags of a line.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineTagsUpdateDto {
+    pub line_id: i64,
+    pub tag_ids: Vec<i64>,
+}
@@ -0,0 +1,295 @@
+use crate::domains::tag::{
+    TagRepositoryTrait,
+    domain::model::{LineTag, Tag},
+};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+use std::{collections::HashSet, future::Future};
+
+pub struct TagRepository;
+
+impl TagRepositoryTrait for TagRepository {
+    fn create_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Tag>> + Send + 'a>> {
+        Box::pin(async move {
+            let tag = sqlx::query_as::<_, Tag>(
+                r#"
+                INSERT INTO tag (name, created_at, updated_at)
+                VALUES (?, ?, ?)
+                RETURNING id, name, created_at, updated_at
+                "#,
+            )
+            .bind(&tag.name)
+            .bind(tag.created_at)
+            .bind(tag.updated_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(tag)
+        })
+    }
+
+    fn get_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<Tag>>> + Send + 'a>> {
+        Box::pin(async move {
+            let tag = sqlx::query_as::<_, Tag>(
+                r#"
+                SELECT id, name, created_at, updated_at
+                FROM tag
+                WHERE id = ?
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(tag)
+        })
+    }
+
+    fn get_tag_by_name<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        name: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<Tag>>> + Send + 'a>> {
+        Box::pin(async move {
+            // The column is declared with NOCASE collation
+            let tag = sqlx::query_as::<_, Tag>(
+                r#"
+                SELECT id, name, created_at, updated_at
+                FROM tag
+                WHERE name = ?
+                "#,
+            )
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(tag)
+        })
+    }
+
+    fn get_all_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<Tag>>> + Send + 'a>> {
+        Box::pin(async move {
+            let tags = sqlx::query_as::<_, Tag>(
+                r#"
+                SELECT id, name, created_at, updated_at
+                FROM tag
+                ORDER BY name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(tags)
+        })
+    }
+
+    fn update_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Tag>> + Send + 'a>> {
+        Box::pin(async move {
+            let tag = sqlx::query_as::<_, Tag>(
+                r#"
+                UPDATE tag
+                SET name = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, name, created_at, updated_at
+                "#,
+            )
+            .bind(&tag.name)
+            .bind(tag.updated_at)
+            .bind(tag.id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(tag)
+        })
+    }
+
+    fn delete_tag<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag: Tag,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_tag
+                WHERE tag_id = ?
+                "#,
+            )
+            .bind(tag.id)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM tag
+                WHERE id = ?
+                "#,
+            )
+            .bind(tag.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn get_tags_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineTag>>> + Send + 'a>> {
+        Box::pin(async move {
+            let tags = sqlx::query_as::<_, LineTag>(
+                r#"
+                SELECT lt.line_id, t.id AS tag_id, t.name, t.created_at, t.updated_at
+                FROM tracker_entry_line_tag lt
+                JOIN tag t ON t.id = lt.tag_id
+                WHERE lt.line_id = ?
+                ORDER BY t.name
+                "#,
+            )
+            .bind(line_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(tags)
+        })
+    }
+
+    fn get_tags_for_all_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<LineTag>>> + Send + 'a>> {
+        Box::pin(async move {
+            let tags = sqlx::query_as::<_, LineTag>(
+                r#"
+                SELECT lt.line_id, t.id AS tag_id, t.name, t.created_at, t.updated_at
+                FROM tracker_entry_line_tag lt
+                JOIN tag t ON t.id = lt.tag_id
+                ORDER BY t.name
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(tags)
+        })
+    }
+
+    fn get_line_ids_with_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        tag_ids: Vec<i64>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<i64>>> + Send + 'a>> {
+        Box::pin(async move {
+            if tag_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            // Duplicates in the input must not inflate the number of tags a line needs
+            let tag_count = tag_ids.iter().collect::<HashSet<_>>().len() as i64;
+
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "SELECT line_id FROM tracker_entry_line_tag WHERE tag_id IN (",
+            );
+            let mut ids = query.separated(", ");
+            for tag_id in tag_ids {
+                ids.push_bind(tag_id);
+            }
+            ids.push_unseparated(")");
+
+            query
+                .push(" GROUP BY line_id HAVING count(DISTINCT tag_id) = ")
+                .push_bind(tag_count);
+
+            let line_ids = query
+                .build_query_scalar::<i64>()
+                .fetch_all(&mut *conn)
+                .await?;
+
+            Ok(line_ids)
+        })
+    }
+
+    fn set_line_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+        tag_ids: Vec<i64>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_tag
+                WHERE line_id = ?
+                "#,
+            )
+            .bind(line_id)
+            .execute(&mut *conn)
+            .await?;
+
+            for tag_id in tag_ids {
+                sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO tracker_entry_line_tag (line_id, tag_id)
+                    VALUES (?, ?)
+                    "#,
+                )
+                .bind(line_id)
+                .bind(tag_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn move_line_tags<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        source: Tag,
+        target: Tag,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO tracker_entry_line_tag (line_id, tag_id, created_at)
+                SELECT line_id, ?, created_at
+                FROM tracker_entry_line_tag
+                WHERE tag_id = ?
+                "#,
+            )
+            .bind(target.id)
+            .bind(source.id)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_tag
+                WHERE tag_id = ?
+                "#,
+            )
+            .bind(source.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
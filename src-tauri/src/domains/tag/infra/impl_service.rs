@@ -0,0 +1,196 @@
+use crate::{
+    domains::{
+        history::HistoryRecorder,
+        tag::{
+            Tag, TagRepositoryTrait, TagServiceTrait,
+            dto::tag_dto::{
+                LineTagsUpdateDto, TagCreateDto, TagDeleteDto, TagMergeDto, TagUpdateDto,
+                TagViewDto,
+            },
+            infra::impl_repository::TagRepository,
+        },
+        tracker::{TrackerRepository, TrackerRepositoryTrait},
+    },
+    error::AppError,
+};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+pub struct TagService {
+    pool: SqlitePool,
+    repo: Arc<dyn TagRepositoryTrait + Send + Sync>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+impl TagService {
+    async fn get_tag(&self, conn: &mut SqliteConnection, id: i64) -> Result<Tag, AppError> {
+        self.repo
+            .get_tag(conn, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Tag with id {} not found", id)))
+    }
+
+    /// Rejects a name that is already used by another tag.
+    async fn ensure_unique(&self, conn: &mut SqliteConnection, tag: &Tag) -> Result<(), AppError> {
+        if let Some(existing) = self.repo.get_tag_by_name(conn, tag.name.clone()).await?
+            && existing.id != tag.id
+        {
+            return Err(AppError::ValidationError(format!(
+                "Tag '{}' already exists",
+                existing.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl TagServiceTrait for TagService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TagServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(TagRepository {}),
+            tracker_repo: Arc::new(TrackerRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn create_tag(
+        &self,
+        dto: TagCreateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tag = Tag::new(0, dto.name);
+            tag.normalize().map_err(AppError::ValidationError)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            self.ensure_unique(&mut tx, &tag).await?;
+            let created = self.repo.create_tag(&mut tx, tag).await?;
+
+            tx.commit().await?;
+
+            Ok(created.into())
+        })
+    }
+
+    fn get_tags(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<TagViewDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let tags = self.repo.get_all_tags(&mut conn).await?;
+
+            Ok(tags.into_iter().map(TagViewDto::from).collect())
+        })
+    }
+
+    fn rename_tag(
+        &self,
+        dto: TagUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let mut tag = self.get_tag(&mut tx, dto.id).await?;
+            tag.name = dto.name;
+            tag.updated_at = Utc::now();
+            tag.normalize().map_err(AppError::ValidationError)?;
+
+            self.ensure_unique(&mut tx, &tag).await?;
+            let updated = self.repo.update_tag(&mut tx, tag).await?;
+
+            tx.commit().await?;
+
+            Ok(updated.into())
+        })
+    }
+
+    fn merge_tags(
+        &self,
+        dto: TagMergeDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TagViewDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            if dto.source_id == dto.target_id {
+                return Err(AppError::ValidationError(
+                    "Cannot merge a tag into itself".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let source = self.get_tag(&mut tx, dto.source_id).await?;
+            let target = self.get_tag(&mut tx, dto.target_id).await?;
+
+            self.repo
+                .move_line_tags(&mut tx, source.clone(), target.clone())
+                .await?;
+            self.repo.delete_tag(&mut tx, source).await?;
+
+            tx.commit().await?;
+
+            Ok(target.into())
+        })
+    }
+
+    fn delete_tag(
+        &self,
+        dto: TagDeleteDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let tag = self.get_tag(&mut tx, dto.id).await?;
+            self.repo.delete_tag(&mut tx, tag).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_line_tags(
+        &self,
+        dto: LineTagsUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<TagViewDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .tracker_repo
+                .get_entry_line(&mut tx, dto.line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", dto.line_id))
+                })?;
+
+            let mut tag_ids = dto.tag_ids;
+            tag_ids.sort_unstable();
+            tag_ids.dedup();
+            for tag_id in &tag_ids {
+                self.get_tag(&mut tx, *tag_id).await?;
+            }
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo
+                .set_line_tags(&mut tx, dto.line_id, tag_ids)
+                .await?;
+            self.history
+                .record(&mut tx, "Set line tags", before, &[])
+                .await?;
+            let tags = self.repo.get_tags_for_line(&mut tx, dto.line_id).await?;
+
+            tx.commit().await?;
+
+            Ok(tags.into_iter().map(TagViewDto::from).collect())
+        })
+    }
+}
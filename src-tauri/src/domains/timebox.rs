@@ -0,0 +1,25 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod timebox_dto;
+}
+
+mod infra {
+    pub mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{
+    TIMEBOX_PHASE_EVENT, TIMEBOX_POLL_INTERVAL, TimeboxBreak, TimeboxPhase, TimeboxPlan,
+    TimeboxSession,
+};
+pub use domain::repository::TimeboxRepositoryTrait;
+pub use domain::service::TimeboxServiceTrait;
+pub use dto::timebox_dto::*;
+pub use infra::impl_repository::TimeboxRepository;
+pub use infra::impl_service::TimeboxService;
@@ -0,0 +1,221 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Event sent to the frontend whenever a timebox moves to its next phase or ends.
+pub const TIMEBOX_PHASE_EVENT: &str = "timebox-phase";
+/// Longest the timer sleeps, so a timebox started in the meantime is picked up quickly.
+pub const TIMEBOX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const MAX_PHASE_MINUTES: u32 = 24 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeboxPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl TimeboxPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeboxPhase::Work => "work",
+            TimeboxPhase::ShortBreak => "short_break",
+            TimeboxPhase::LongBreak => "long_break",
+        }
+    }
+
+    pub fn is_break(&self) -> bool {
+        *self != TimeboxPhase::Work
+    }
+}
+
+impl FromStr for TimeboxPhase {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "work" => Ok(TimeboxPhase::Work),
+            "short_break" => Ok(TimeboxPhase::ShortBreak),
+            "long_break" => Ok(TimeboxPhase::LongBreak),
+            _ => Err(format!("Unknown timebox phase '{}'", value)),
+        }
+    }
+}
+
+/// How long a timebox runs: a single stretch of work, or Pomodoro cycles of work and
+/// breaks that go on until the timebox is cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimeboxPlan {
+    Target {
+        minutes: u32,
+    },
+    Pomodoro {
+        work_minutes: u32,
+        short_break_minutes: u32,
+        long_break_minutes: u32,
+        /// Every n-th break is a long one.
+        long_break_every: u32,
+    },
+}
+
+impl Default for TimeboxPlan {
+    fn default() -> Self {
+        TimeboxPlan::Pomodoro {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_every: 4,
+        }
+    }
+}
+
+impl TimeboxPlan {
+    pub fn validate(&self) -> Result<(), String> {
+        let phases = match *self {
+            TimeboxPlan::Target { minutes } => vec![("Target", minutes)],
+            TimeboxPlan::Pomodoro {
+                work_minutes,
+                short_break_minutes,
+                long_break_minutes,
+                long_break_every,
+            } => {
+                if long_break_every == 0 {
+                    return Err("Long breaks must come every one or more breaks".to_string());
+                }
+                vec![
+                    ("Work", work_minutes),
+                    ("Short break", short_break_minutes),
+                    ("Long break", long_break_minutes),
+                ]
+            }
+        };
+
+        for (name, minutes) in phases {
+            if !(1..=MAX_PHASE_MINUTES).contains(&minutes) {
+                return Err(format!(
+                    "{} must last between 1 and {} minutes",
+                    name, MAX_PHASE_MINUTES
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn phase_length(&self, phase: TimeboxPhase) -> TimeDelta {
+        let minutes = match (*self, phase) {
+            (TimeboxPlan::Target { minutes }, _) => minutes,
+            (TimeboxPlan::Pomodoro { work_minutes, .. }, TimeboxPhase::Work) => work_minutes,
+            (
+                TimeboxPlan::Pomodoro {
+                    short_break_minutes,
+                    ..
+                },
+                TimeboxPhase::ShortBreak,
+            ) => short_break_minutes,
+            (
+                TimeboxPlan::Pomodoro {
+                    long_break_minutes, ..
+                },
+                TimeboxPhase::LongBreak,
+            ) => long_break_minutes,
+        };
+
+        TimeDelta::minutes(minutes.into())
+    }
+}
+
+/// The running timebox. It only lives in memory, on the Rust side, so reloading the
+/// webview does not touch it.
+#[derive(Debug, Clone)]
+pub struct TimeboxSession {
+    pub entry_id: i64,
+    pub line_id: i64,
+    pub plan: TimeboxPlan,
+    pub phase: TimeboxPhase,
+    pub phase_started_at: DateTime<Utc>,
+    pub phase_ends_at: DateTime<Utc>,
+    pub completed_work_phases: u32,
+    /// The break being taken, `None` during work.
+    pub break_id: Option<i64>,
+}
+
+impl TimeboxSession {
+    pub fn new(entry_id: i64, line_id: i64, plan: TimeboxPlan, started_at: DateTime<Utc>) -> Self {
+        Self {
+            entry_id,
+            line_id,
+            plan,
+            phase: TimeboxPhase::Work,
+            phase_started_at: started_at,
+            phase_ends_at: started_at + plan.phase_length(TimeboxPhase::Work),
+            completed_work_phases: 0,
+            break_id: None,
+        }
+    }
+
+    /// The phase after the current one, `None` once a target has been reached.
+    ///
+    /// Call after counting the work phase that just ended.
+    pub fn next_phase(&self) -> Option<TimeboxPhase> {
+        match (self.plan, self.phase) {
+            (TimeboxPlan::Target { .. }, _) => None,
+            (TimeboxPlan::Pomodoro { .. }, phase) if phase.is_break() => Some(TimeboxPhase::Work),
+            (
+                TimeboxPlan::Pomodoro {
+                    long_break_every, ..
+                },
+                _,
+            ) => {
+                if self.completed_work_phases.is_multiple_of(long_break_every) {
+                    Some(TimeboxPhase::LongBreak)
+                } else {
+                    Some(TimeboxPhase::ShortBreak)
+                }
+            }
+        }
+    }
+
+    /// Moves to `phase`, timing it from `started_at`.
+    pub fn enter(&mut self, phase: TimeboxPhase, started_at: DateTime<Utc>) {
+        self.phase = phase;
+        self.phase_started_at = started_at;
+        self.phase_ends_at = started_at + self.plan.phase_length(phase);
+    }
+}
+
+impl fmt::Display for TimeboxSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TimeboxSession(line_id: {}, phase: {}, phase_ends_at: {}, completed_work_phases: {})",
+            self.line_id,
+            self.phase.as_str(),
+            self.phase_ends_at,
+            self.completed_work_phases
+        )
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct TimeboxBreak {
+    pub id: i64,
+    pub line_id: i64,
+    pub phase: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub planned_ended_at: DateTime<Utc>,
+}
+
+impl fmt::Display for TimeboxBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TimeboxBreak(id: {}, line_id: {}, phase: {}, started_at: {}, ended_at: {:?})",
+            self.id, self.line_id, self.phase, self.started_at, self.ended_at
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use crate::domains::timebox::domain::model::TimeboxBreak;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait TimeboxRepositoryTrait {
+    fn create_break<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        timebox_break: TimeboxBreak,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TimeboxBreak>> + Send + 'a>>;
+
+    fn end_break<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn get_breaks_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TimeboxBreak>>> + Send + 'a>>;
+
+    /// Ends every open break where it was planned to end, or at `now` if that is earlier,
+    /// and returns how many there were.
+    fn end_open_breaks<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        now: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>>;
+}
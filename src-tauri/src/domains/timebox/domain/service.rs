@@ -0,0 +1,49 @@
+use crate::{
+    domains::timebox::dto::timebox_dto::{
+        TimeboxBreakDto, TimeboxPhaseChangeDto, TimeboxStartDto, TimeboxStatusDto,
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait TimeboxServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TimeboxServiceTrait>
+    where
+        Self: Sized;
+
+    /// Starts tracking a new line and times its first work phase. Only one timebox runs
+    /// at a time.
+    fn start_timebox(
+        &self,
+        dto: TimeboxStartDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TimeboxStatusDto, AppError>> + Send + '_>>;
+
+    fn get_timebox(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TimeboxStatusDto>, AppError>> + Send + '_>>;
+
+    /// Stops the line, or ends the break, and drops the timebox.
+    fn cancel_timebox(&self) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Moves to the next phase if the current one is over at `now`, stopping the line for
+    /// a break and resuming it afterwards.
+    fn advance_timebox(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TimeboxPhaseChangeDto>, AppError>> + Send + '_>>;
+
+    /// When the current phase ends, `None` without a running timebox.
+    fn next_deadline(&self) -> Pin<Box<dyn Future<Output = Option<DateTime<Utc>>> + Send + '_>>;
+
+    fn get_breaks(
+        &self,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TimeboxBreakDto>, AppError>> + Send + '_>>;
+
+    /// Ends the breaks a previous run left open, at their planned end at the latest.
+    fn close_dangling_breaks(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,72 @@
+use crate::domains::timebox::{TimeboxBreak, TimeboxPhase, TimeboxPlan, TimeboxSession};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeboxStartDto {
+    pub entry_id: i64,
+    pub desc: String,
+    pub plan: TimeboxPlan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeboxStatusDto {
+    pub entry_id: i64,
+    pub line_id: i64,
+    pub plan: TimeboxPlan,
+    pub phase: TimeboxPhase,
+    pub phase_started_at: DateTime<Utc>,
+    pub phase_ends_at: DateTime<Utc>,
+    pub completed_work_phases: u32,
+}
+
+impl From<&TimeboxSession> for TimeboxStatusDto {
+    fn from(session: &TimeboxSession) -> Self {
+        Self {
+            entry_id: session.entry_id,
+            line_id: session.line_id,
+            plan: session.plan,
+            phase: session.phase,
+            phase_started_at: session.phase_started_at,
+            phase_ends_at: session.phase_ends_at,
+            completed_work_phases: session.completed_work_phases,
+        }
+    }
+}
+
+/// Payload of the phase event. `current` is `None` once the timebox has finished.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeboxPhaseChangeDto {
+    pub line_id: i64,
+    pub previous: TimeboxPhase,
+    pub current: Option<TimeboxStatusDto>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeboxBreakDto {
+    pub id: i64,
+    pub line_id: i64,
+    pub phase: TimeboxPhase,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub planned_ended_at: DateTime<Utc>,
+}
+
+impl From<TimeboxBreak> for TimeboxBreakDto {
+    fn from(timebox_break: TimeboxBreak) -> Self {
+        let phase = timebox_break.phase.parse().unwrap_or_else(|e| {
+            log::warn!("{}, treating it as a short break", e);
+            TimeboxPhase::ShortBreak
+        });
+
+        Self {
+            id: timebox_break.id,
+            line_id: timebox_break.line_id,
+            phase,
+            started_at: timebox_break.started_at,
+            ended_at: timebox_break.ended_at,
+            planned_ended_at: timebox_break.planned_ended_at,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::domains::timebox::{TimeboxRepositoryTrait, domain::model::TimeboxBreak};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct TimeboxRepository;
+
+impl TimeboxRepositoryTrait for TimeboxRepository {
+    fn create_break<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        timebox_break: TimeboxBreak,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TimeboxBreak>> + Send + 'a>> {
+        Box::pin(async move {
+            let timebox_break = sqlx::query_as::<_, TimeboxBreak>(
+                r#"
+                INSERT INTO timebox_break (line_id, phase, started_at, ended_at, planned_ended_at)
+                VALUES (?, ?, ?, ?, ?)
+                RETURNING id, line_id, phase, started_at, ended_at, planned_ended_at
+                "#,
+            )
+            .bind(timebox_break.line_id)
+            .bind(&timebox_break.phase)
+            .bind(timebox_break.started_at)
+            .bind(timebox_break.ended_at)
+            .bind(timebox_break.planned_ended_at)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(timebox_break)
+        })
+    }
+
+    fn end_break<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE timebox_break
+                SET ended_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(ended_at)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn get_breaks_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TimeboxBreak>>> + Send + 'a>> {
+        Box::pin(async move {
+            let breaks = sqlx::query_as::<_, TimeboxBreak>(
+                r#"
+                SELECT id, line_id, phase, started_at, ended_at, planned_ended_at
+                FROM timebox_break
+                WHERE line_id = ?
+                ORDER BY started_at
+                "#,
+            )
+            .bind(line_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(breaks)
+        })
+    }
+
+    fn end_open_breaks<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        now: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>> {
+        Box::pin(async move {
+            let result = sqlx::query(
+                r#"
+                UPDATE timebox_break
+                SET ended_at = CASE
+                    WHEN julianday(planned_ended_at) < julianday(?) THEN planned_ended_at
+                    ELSE ?
+                END
+                WHERE ended_at IS NULL
+                "#,
+            )
+            .bind(now)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(result.rows_affected())
+        })
+    }
+}
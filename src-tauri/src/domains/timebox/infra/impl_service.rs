@@ -0,0 +1,285 @@
+use crate::{
+    domains::{
+        timebox::{
+            TimeboxBreak, TimeboxPhase, TimeboxRepositoryTrait, TimeboxServiceTrait,
+            TimeboxSession,
+            dto::timebox_dto::{
+                TimeboxBreakDto, TimeboxPhaseChangeDto, TimeboxStartDto, TimeboxStatusDto,
+            },
+            infra::impl_repository::TimeboxRepository,
+        },
+        tracker::{TrackerEntryLineCreateDto, TrackerService, TrackerServiceTrait},
+    },
+    error::AppError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::{future::Future, sync::Arc};
+use tokio::sync::Mutex;
+
+pub struct TimeboxService {
+    pool: SqlitePool,
+    repo: Arc<dyn TimeboxRepositoryTrait + Send + Sync>,
+    tracker_service: Arc<dyn TrackerServiceTrait>,
+    session: Mutex<Option<TimeboxSession>>,
+}
+
+impl TimeboxService {
+    /// Ends the work phase: stops the line and opens a break, or finishes the timebox.
+    async fn end_work(
+        &self,
+        session: &mut TimeboxSession,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        session.completed_work_phases += 1;
+
+        // The line may already have been stopped by hand, the break starts anyway
+        let stopped_at = match self.tracker_service.stop_tracking(session.line_id).await {
+            Ok(line) => line
+                .durations
+                .iter()
+                .filter_map(|duration| duration.ended_at)
+                .max()
+                .unwrap_or(now),
+            Err(e) => {
+                log::warn!("Failed to stop line {} for a break: {}", session.line_id, e);
+                now
+            }
+        };
+
+        let Some(phase) = session.next_phase() else {
+            return Ok(false);
+        };
+        session.enter(phase, stopped_at);
+
+        let mut tx = self.pool.begin().await?;
+        let timebox_break = self
+            .repo
+            .create_break(
+                &mut tx,
+                TimeboxBreak {
+                    id: 0,
+                    line_id: session.line_id,
+                    phase: phase.as_str().to_string(),
+                    started_at: session.phase_started_at,
+                    ended_at: None,
+                    planned_ended_at: session.phase_ends_at,
+                },
+            )
+            .await?;
+        tx.commit().await?;
+
+        session.break_id = Some(timebox_break.id);
+
+        Ok(true)
+    }
+
+    /// Ends the break: records when it ended and resumes the line.
+    async fn end_break(
+        &self,
+        session: &mut TimeboxSession,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        if let Some(break_id) = session.break_id.take() {
+            let mut tx = self.pool.begin().await?;
+            self.repo.end_break(&mut tx, break_id, now).await?;
+            tx.commit().await?;
+        }
+
+        // Without its line there is nothing left to time
+        let resumed_at = match self.tracker_service.resume_tracking(session.line_id).await {
+            Ok(line) => line
+                .durations
+                .iter()
+                .filter(|duration| duration.ended_at.is_none())
+                .map(|duration| duration.started_at)
+                .max()
+                .unwrap_or(now),
+            Err(e) => {
+                log::warn!(
+                    "Failed to resume line {} after a break: {}",
+                    session.line_id,
+                    e
+                );
+                return Ok(false);
+            }
+        };
+        session.enter(TimeboxPhase::Work, resumed_at);
+
+        Ok(true)
+    }
+}
+
+impl TimeboxServiceTrait for TimeboxService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TimeboxServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            tracker_service: TrackerService::create_service(pool.clone()),
+            pool,
+            repo: Arc::new(TimeboxRepository {}),
+            session: Mutex::new(None),
+        })
+    }
+
+    fn start_timebox(
+        &self,
+        dto: TimeboxStartDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TimeboxStatusDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            dto.plan.validate().map_err(AppError::ValidationError)?;
+
+            let mut session = self.session.lock().await;
+            if session.is_some() {
+                return Err(AppError::ValidationError(
+                    "A timebox is already running".to_string(),
+                ));
+            }
+
+            let now = Utc::now();
+            let line = self
+                .tracker_service
+                .start_tracking(TrackerEntryLineCreateDto {
+                    entry_id: dto.entry_id,
+                    desc: dto.desc,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .await?;
+            let started_at = line
+                .durations
+                .iter()
+                .map(|duration| duration.started_at)
+                .max()
+                .unwrap_or(now);
+
+            let started = TimeboxSession::new(line.entry_id, line.id, dto.plan, started_at);
+            log::info!("Started {}", started);
+            let status = TimeboxStatusDto::from(&started);
+            *session = Some(started);
+
+            Ok(status)
+        })
+    }
+
+    fn get_timebox(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<TimeboxStatusDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let session = self.session.lock().await;
+
+            Ok(session.as_ref().map(TimeboxStatusDto::from))
+        })
+    }
+
+    fn cancel_timebox(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut session = self.session.lock().await;
+            let Some(cancelled) = session.take() else {
+                return Err(AppError::NotFound("No timebox is running".to_string()));
+            };
+
+            match cancelled.break_id {
+                Some(break_id) => {
+                    let mut tx = self.pool.begin().await?;
+                    self.repo.end_break(&mut tx, break_id, Utc::now()).await?;
+                    tx.commit().await?;
+                }
+                None => {
+                    if let Err(e) = self.tracker_service.stop_tracking(cancelled.line_id).await {
+                        log::warn!("Failed to stop line {}: {}", cancelled.line_id, e);
+                    }
+                }
+            }
+
+            log::info!("Cancelled {}", cancelled);
+
+            Ok(())
+        })
+    }
+
+    fn advance_timebox(
+        &self,
+        now: DateTime<Utc>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<TimeboxPhaseChangeDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut guard = self.session.lock().await;
+            let Some(session) = guard.as_mut() else {
+                return Ok(None);
+            };
+            if now < session.phase_ends_at {
+                return Ok(None);
+            }
+
+            let previous = session.phase;
+            let line_id = session.line_id;
+            let running = if previous.is_break() {
+                self.end_break(session, now).await?
+            } else {
+                self.end_work(session, now).await?
+            };
+
+            let current = if running {
+                log::info!("Timebox moved on to {}", session);
+                Some(TimeboxStatusDto::from(&*session))
+            } else {
+                log::info!("Finished {}", session);
+                *guard = None;
+                None
+            };
+
+            Ok(Some(TimeboxPhaseChangeDto {
+                line_id,
+                previous,
+                current,
+                changed_at: now,
+            }))
+        })
+    }
+
+    fn next_deadline(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Option<DateTime<Utc>>> + Send + '_>> {
+        Box::pin(async move {
+            let session = self.session.lock().await;
+
+            session.as_ref().map(|session| session.phase_ends_at)
+        })
+    }
+
+    fn get_breaks(
+        &self,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<TimeboxBreakDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let breaks = self.repo.get_breaks_for_line(&mut conn, line_id).await?;
+
+            Ok(breaks.into_iter().map(TimeboxBreakDto::from).collect())
+        })
+    }
+
+    fn close_dangling_breaks(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u64, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let closed = self.repo.end_open_breaks(&mut tx, Utc::now()).await?;
+
+            tx.commit().await?;
+
+            Ok(closed)
+        })
+    }
+}
@@ -9,13 +9,16 @@ pub mod dto {
 }
 
 mod infra {
-    mod impl_repository;
+    pub mod impl_repository;
     pub mod impl_service;
 }
 
 // Re-export commonly used items for convenience
-pub use domain::model::{TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration};
+pub use domain::model::{
+    SINGLE_ACTIVE_SETTING_KEY, TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration,
+};
 pub use domain::repository::TrackerRepositoryTrait;
 pub use domain::service::TrackerServiceTrait;
 pub use dto::tracker_dto::*;
+pub use infra::impl_repository::TrackerRepository;
 pub use infra::impl_service::TrackerService;
@@ -1,13 +1,23 @@
 use chrono::{DateTime, Utc};
 use std::fmt;
 
+/// Setting that, when `true`, lets only one line run at a time.
+pub const SINGLE_ACTIVE_SETTING_KEY: &str = "tracking.single_active";
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
 pub struct TrackerEntry {
     pub id: i64,
+    pub client_id: i64,
     pub label: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub is_deleted: bool,
+    pub is_archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Position in the manual order, pinned trackers are listed first regardless.
+    pub sort_order: i64,
+    pub is_pinned: bool,
+    pub color: Option<String>,
 }
 
 impl TrackerEntry {
@@ -24,10 +34,16 @@ impl Default for TrackerEntry {
     fn default() -> Self {
         Self {
             id: 0,
+            client_id: 0,
             label: String::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             is_deleted: false,
+            is_archived: false,
+            archived_at: None,
+            sort_order: 0,
+            is_pinned: false,
+            color: None,
         }
     }
 }
@@ -36,12 +52,33 @@ impl fmt::Display for TrackerEntry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "TrackerEntry(id: {}, label: {}, created_at: {}, updated_at: {}, is_deleted: {})",
-            self.id, self.label, self.created_at, self.updated_at, self.is_deleted
+            "TrackerEntry(id: {}, client_id: {}, label: {}, created_at: {}, updated_at: {}, is_deleted: {}, is_archived: {}, sort_order: {}, is_pinned: {})",
+            self.id,
+            self.client_id,
+            self.label,
+            self.created_at,
+            self.updated_at,
+            self.is_deleted,
+            self.is_archived,
+            self.sort_order,
+            self.is_pinned
         )
     }
 }
 
+/// Checks a tracker colour and returns it lower-cased, e.g. `#1e90ff`.
+pub fn validate_color(color: &str) -> Result<String, String> {
+    let color = color.trim();
+    let is_hex = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex {
+        return Err(format!("Colour '{}' is not of the form #rrggbb", color));
+    }
+
+    Ok(color.to_ascii_lowercase())
+}
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
 pub struct TrackerEntryLine {
     pub id: i64,
@@ -95,6 +132,8 @@ pub struct TrackerEntryLineDuration {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub is_deleted: bool,
+    /// The invoice line the interval was billed on, invoiced intervals can no longer be edited.
+    pub invoice_line_id: Option<i64>,
 }
 
 impl TrackerEntryLineDuration {
@@ -112,6 +151,38 @@ impl TrackerEntryLineDuration {
             ..Default::default()
         }
     }
+
+    /// Whether the two intervals share any time. Running intervals extend indefinitely,
+    /// intervals that only touch at an end point do not overlap.
+    pub fn overlaps(&self, other: &TrackerEntryLineDuration) -> bool {
+        let self_end = self.ended_at.unwrap_or(DateTime::<Utc>::MAX_UTC);
+        let other_end = other.ended_at.unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        self.started_at < other_end && other.started_at < self_end
+    }
+
+    /// Checks that the interval is well formed and does not overlap any other interval
+    /// on the same line. `siblings` may contain the interval itself, it is skipped by id.
+    pub fn validate(&self, siblings: &[TrackerEntryLineDuration]) -> Result<(), String> {
+        if let Some(ended_at) = self.ended_at
+            && ended_at < self.started_at
+        {
+            return Err("Interval ends before it starts".to_string());
+        }
+
+        if let Some(other) = siblings
+            .iter()
+            .filter(|other| other.id != self.id && other.entry_line_id == self.entry_line_id)
+            .find(|other| self.overlaps(other))
+        {
+            return Err(format!(
+                "Interval overlaps interval {} on the same line",
+                other.id
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for TrackerEntryLineDuration {
@@ -124,6 +195,7 @@ impl Default for TrackerEntryLineDuration {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             is_deleted: false,
+            invoice_line_id: None,
         }
     }
 }
@@ -132,14 +204,15 @@ impl fmt::Display for TrackerEntryLineDuration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "TrackerEntryLineDuration(id: {}, entry_line_id: {}, started_at: {}, ended_at: {:?}, created_at: {}, updated_at: {}, is_deleted: {})",
+            "TrackerEntryLineDuration(id: {}, entry_line_id: {}, started_at: {}, ended_at: {:?}, created_at: {}, updated_at: {}, is_deleted: {}, invoice_line_id: {:?})",
             self.id,
             self.entry_line_id,
             self.started_at,
             self.ended_at,
             self.created_at,
             self.updated_at,
-            self.is_deleted
+            self.is_deleted,
+            self.invoice_line_id
         )
     }
 }
@@ -1,103 +1,212 @@
 use crate::domains::tracker::domain::model::{
     TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration,
 };
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
 use std::future::Future;
 use std::pin::Pin;
 
+/// Methods run on a caller-supplied connection, so the service can pass either a pooled
+/// connection or an open `sqlx::Transaction` (`&mut tx`) to group several calls atomically.
 pub trait TrackerRepositoryTrait {
     /* Tracker entries */
-    fn create_entry(
-        &self,
-        pool: SqlitePool,
+    fn create_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>>;
 
-    fn get_entry(
-        &self,
-        pool: SqlitePool,
+    fn get_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         id: i64,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + '_>>;
-
-    fn get_all_entries(
-        &self,
-        pool: SqlitePool,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntry>>> + Send + '_>>;
-
-    fn update_entry(
-        &self,
-        pool: SqlitePool,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + 'a>>;
+
+    fn get_entry_by_label<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        label: String,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + 'a>>;
+
+    /// Returns the live trackers in manual order, archived ones only when asked for.
+    fn get_all_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        include_archived: bool,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntry>>> + Send + 'a>>;
+
+    fn update_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + '_>>;
-
-    fn delete_entry(
-        &self,
-        pool: SqlitePool,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>>;
+
+    /// Archives the tracker when `archived_at` is set and unarchives it otherwise.
+    fn set_entry_archived<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        archived_at: Option<DateTime<Utc>>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>>;
+
+    fn set_entry_sort_order<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        sort_order: i64,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn set_entry_pinned<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        is_pinned: bool,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>>;
+
+    fn set_entry_color<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        color: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>>;
+
+    fn delete_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>>;
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
 
-    fn create_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn create_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + 'a>>;
 
-    fn get_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn get_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         id: i64,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + '_>>;
-
-    fn get_all_entry_lines(
-        &self,
-        pool: SqlitePool,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + '_>>;
-
-    fn get_lines_for_entry(
-        &self,
-        pool: SqlitePool,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + 'a>>;
+
+    fn get_entry_line_by_desc<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        desc: String,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + 'a>>;
+
+    fn get_all_entry_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>;
+
+    fn get_lines_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>;
+
+    fn get_lines_for_all_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>;
 
-    fn update_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn update_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + 'a>>;
 
-    fn delete_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn delete_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>>;
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
 
-    fn delete_lines_for_entry(
-        &self,
-        pool: SqlitePool,
+    fn delete_lines_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>>;
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
 
-    fn create_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn create_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + 'a>>;
 
-    fn get_line_durations(
-        &self,
-        pool: SqlitePool,
-        line: TrackerEntryLine,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + '_>>;
+    fn get_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLineDuration>>> + Send + 'a>>;
 
-    fn update_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn get_line_durations<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrackerEntryLine,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>>;
+
+    /// Finds a live interval on the line that starts and ends within a millisecond of the given times.
+    fn get_matching_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_line_id: i64,
+        started_at: DateTime<Utc>,
+        ended_at: Option<DateTime<Utc>>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLineDuration>>> + Send + 'a>>;
+
+    fn get_durations_for_all_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>>;
+
+    /// Returns the running intervals of every live line, archived trackers included.
+    fn get_open_durations<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>>;
+
+    fn update_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + 'a>>;
+
+    /// Re-points the live intervals of `source` to `target`, trashed ones stay behind.
+    fn move_durations_to_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        source: TrackerEntryLine,
+        target: TrackerEntryLine,
+        updated_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn delete_durations_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrackerEntryLine,
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn delete_durations_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrackerEntry,
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
 
-    fn delete_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn delete_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>>;
+        deleted_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
 }
@@ -1,11 +1,17 @@
 use crate::{
-    domains::tracker::dto::tracker_dto::{
-        TrackerEntryCreateDto, TrackerEntryDeleteDto, TrackerEntryLineCreateDto,
-        TrackerEntryLineDeleteDto, TrackerEntryLineUpdateDto, TrackerEntryLineViewDto,
-        TrackerEntryViewDto,
+    domains::{
+        client::ClientTrackersDto,
+        tracker::dto::tracker_dto::{
+            TrackerEntryCreateDto, TrackerEntryDeleteDto, TrackerEntryLineCreateDto,
+            TrackerEntryLineDeleteDto, TrackerEntryLineDurationCreateDto,
+            TrackerEntryLineDurationDeleteDto, TrackerEntryLineDurationUpdateDto,
+            TrackerEntryLineUpdateDto, TrackerEntryLineViewDto, TrackerEntryUpdateDto,
+            TrackerEntryViewDto, TrackerQueryDto, TrackerSwitchDto,
+        },
     },
     error::AppError,
 };
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 use std::{future::Future, pin::Pin, sync::Arc};
 
@@ -19,10 +25,29 @@ pub trait TrackerServiceTrait: Send + Sync {
         dto: TrackerEntryCreateDto,
     ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
 
+    /// Returns the live trackers in the requested order, archived ones only when asked for.
     fn get_trackers(
         &self,
+        query: TrackerQueryDto,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<TrackerEntryViewDto>, AppError>> + Send + '_>>;
 
+    /// Returns every client with its unarchived trackers, clients without trackers included.
+    fn get_trackers_by_client(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ClientTrackersDto>, AppError>> + Send + '_>>;
+
+    /// Returns the live lines that carry every one of the given tags.
+    fn get_lines_by_tags(
+        &self,
+        tag_ids: Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TrackerEntryLineViewDto>, AppError>> + Send + '_>>;
+
+    fn update_tracker(
+        &self,
+        dto: TrackerEntryUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
+
+    /// In single-active mode every other running line is stopped at the instant this one starts.
     fn start_tracking(
         &self,
         dto: TrackerEntryLineCreateDto,
@@ -33,11 +58,82 @@ pub trait TrackerServiceTrait: Send + Sync {
         line_id: i64,
     ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
 
+    /// Ends the running interval at `ended_at`, for stops made on the user's behalf. Returns
+    /// `None` when the interval is no longer running.
+    fn stop_interval_at(
+        &self,
+        duration_id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TrackerEntryLineViewDto>, AppError>> + Send + '_>>;
+
+    /// In single-active mode every other running line is stopped at the instant this one resumes.
     fn resume_tracking(
         &self,
         line_id: i64,
     ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
 
+    /// Stops `from_line_id` and starts `to_line_id` at the same instant, so no time falls
+    /// between the two intervals.
+    fn switch_tracking(
+        &self,
+        from_line_id: i64,
+        to_line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerSwitchDto, AppError>> + Send + '_>>;
+
+    fn get_single_active(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, AppError>> + Send + '_>>;
+
+    /// Applies from the next start, lines that are already running keep running.
+    fn set_single_active(
+        &self,
+        enabled: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, AppError>> + Send + '_>>;
+
+    fn stop_all_tracking(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TrackerEntryLineViewDto>, AppError>> + Send + '_>>;
+
+    fn add_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationCreateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
+
+    fn update_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationUpdateDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
+
+    fn delete_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationDeleteDto,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
+
+    /// Cuts the interval in two at `at`, which must fall strictly inside it. A running
+    /// interval keeps running as its second half.
+    fn split_line_duration(
+        &self,
+        duration_id: i64,
+        at: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
+
+    /// Moves every interval of `source_line_id` to `target_line_id` and sends the source
+    /// line to the trash. Both lines must belong to the same tracker.
+    fn merge_lines(
+        &self,
+        source_line_id: i64,
+        target_line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>>;
+
+    /// Moves every line of `source_entry_id` to `target_entry_id`, merging lines with exactly
+    /// the same description, and sends the source tracker to the trash. The source's rate,
+    /// budget and field values go to the target where it has none, differing ones are refused.
+    fn merge_trackers(
+        &self,
+        source_entry_id: i64,
+        target_entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
+
     fn update_tracked(
         &self,
         dto: TrackerEntryLineUpdateDto,
@@ -52,4 +148,35 @@ pub trait TrackerServiceTrait: Send + Sync {
         &self,
         dto: TrackerEntryDeleteDto,
     ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Hides the tracker from the tracker list while keeping its time in reports.
+    fn archive_tracker(
+        &self,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
+
+    fn unarchive_tracker(
+        &self,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
+
+    /// Saves `entry_ids` as the new manual order in one go. It must list every unarchived
+    /// tracker, archived trackers left out are kept after them.
+    fn reorder_trackers(
+        &self,
+        entry_ids: Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<TrackerEntryViewDto>, AppError>> + Send + '_>>;
+
+    fn set_tracker_pinned(
+        &self,
+        entry_id: i64,
+        is_pinned: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
+
+    /// Clearing the colour falls back to the default one.
+    fn set_tracker_color(
+        &self,
+        entry_id: i64,
+        color: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>;
 }
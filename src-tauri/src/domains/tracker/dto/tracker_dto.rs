@@ -1,9 +1,17 @@
-use crate::domains::tracker::{TrackerEntry, TrackerEntryLine};
+use crate::domains::{
+    billing::{EntryRate, LineRate, billable_amount, parse_rate},
+    custom_field::CustomFieldValueViewDto,
+    tag::TagViewDto,
+    tracker::{TrackerEntry, TrackerEntryLine},
+};
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerEntryCreateDto {
+    /// Falls back to the default client when not set.
+    pub client_id: Option<i64>,
     pub label: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -12,6 +20,7 @@ pub struct TrackerEntryCreateDto {
 impl Default for TrackerEntryCreateDto {
     fn default() -> Self {
         Self {
+            client_id: None,
             label: String::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -22,6 +31,7 @@ impl Default for TrackerEntryCreateDto {
 impl From<TrackerEntry> for TrackerEntryCreateDto {
     fn from(entry: TrackerEntry) -> Self {
         Self {
+            client_id: Some(entry.client_id),
             label: entry.label,
             created_at: entry.created_at,
             updated_at: entry.updated_at,
@@ -32,9 +42,24 @@ impl From<TrackerEntry> for TrackerEntryCreateDto {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerEntryViewDto {
     pub id: i64,
+    pub client_id: i64,
     pub label: String,
+    /// Set on the tracker itself, `None` when it inherits from its client.
+    pub rate_override: Option<Decimal>,
+    pub currency_override: Option<String>,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+    /// Sum of the line amounts, `None` when no line is billed.
+    pub amount: Option<Decimal>,
+    pub is_archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub sort_order: i64,
+    pub is_pinned: bool,
+    pub color: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Values of the tracker-scoped custom fields that are set.
+    pub fields: Vec<CustomFieldValueViewDto>,
     pub lines: Vec<TrackerEntryLineViewDto>,
 }
 
@@ -42,9 +67,21 @@ impl Default for TrackerEntryViewDto {
     fn default() -> Self {
         Self {
             id: 0,
+            client_id: 0,
             label: String::new(),
+            rate_override: None,
+            currency_override: None,
+            hourly_rate: None,
+            currency: None,
+            amount: None,
+            is_archived: false,
+            archived_at: None,
+            sort_order: 0,
+            is_pinned: false,
+            color: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            fields: Vec::new(),
             lines: Vec::new(),
         }
     }
@@ -54,17 +91,115 @@ impl From<TrackerEntry> for TrackerEntryViewDto {
     fn from(entry: TrackerEntry) -> Self {
         Self {
             id: entry.id,
+            client_id: entry.client_id,
             label: entry.label,
+            rate_override: None,
+            currency_override: None,
+            hourly_rate: None,
+            currency: None,
+            amount: None,
+            is_archived: entry.is_archived,
+            archived_at: entry.archived_at,
+            sort_order: entry.sort_order,
+            is_pinned: entry.is_pinned,
+            color: entry.color,
             created_at: entry.created_at,
             updated_at: entry.updated_at,
+            fields: Vec::new(),
             lines: Vec::new(),
         }
     }
 }
 
+impl TrackerEntryViewDto {
+    /// Fills in the tracker's rate and totals the amounts of its lines.
+    ///
+    /// Lines must already be billed with `TrackerEntryLineViewDto::apply_rate`.
+    pub fn apply_rate(&mut self, rate: &EntryRate) {
+        self.rate_override = parse_rate(&rate.rate_override);
+        self.currency_override = rate.currency_override.clone();
+        self.hourly_rate = parse_rate(&rate.hourly_rate);
+        self.currency = rate.currency.clone();
+        self.amount = self
+            .lines
+            .iter()
+            .filter_map(|line| line.amount)
+            .reduce(|total, amount| total + amount);
+    }
+
+    /// Time tracked on all lines, counting running intervals up to `now`.
+    pub fn total_seconds(&self, now: DateTime<Utc>) -> i64 {
+        self.durations(now)
+            .map(|(started_at, ended_at)| (ended_at - started_at).num_seconds())
+            .sum()
+    }
+
+    /// When time was last tracked, `now` while an interval is running.
+    pub fn last_tracked_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.durations(now).map(|(_, ended_at)| ended_at).max()
+    }
+
+    fn durations(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> + '_ {
+        self.lines
+            .iter()
+            .flat_map(|line| &line.durations)
+            .map(move |duration| (duration.started_at, duration.ended_at.unwrap_or(now)))
+    }
+}
+
+/// Order of the tracker list. Pinned trackers always come first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackerSort {
+    /// The order last saved with `reorder_trackers`.
+    #[default]
+    Manual,
+    /// Most recently tracked first, trackers without any time last.
+    Recent,
+    Name,
+    /// Most time tracked first.
+    Total,
+}
+
+impl TrackerSort {
+    /// Sorts stably, so ties keep the manual order the trackers were loaded in.
+    pub fn sort(&self, trackers: &mut [TrackerEntryViewDto], now: DateTime<Utc>) {
+        match self {
+            TrackerSort::Manual => {
+                trackers.sort_by_key(|tracker| (!tracker.is_pinned, tracker.sort_order))
+            }
+            TrackerSort::Recent => trackers.sort_by_cached_key(|tracker| {
+                (
+                    !tracker.is_pinned,
+                    std::cmp::Reverse(tracker.last_tracked_at(now)),
+                )
+            }),
+            TrackerSort::Name => trackers
+                .sort_by_cached_key(|tracker| (!tracker.is_pinned, tracker.label.to_lowercase())),
+            TrackerSort::Total => trackers.sort_by_cached_key(|tracker| {
+                (
+                    !tracker.is_pinned,
+                    std::cmp::Reverse(tracker.total_seconds(now)),
+                )
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerQueryDto {
+    pub include_archived: bool,
+    pub sort: TrackerSort,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerEntryUpdateDto {
     pub id: i64,
+    /// Moves the tracker to another client when set.
+    pub client_id: Option<i64>,
     pub label: String,
     pub updated_at: DateTime<Utc>,
 }
@@ -73,6 +208,7 @@ impl Default for TrackerEntryUpdateDto {
     fn default() -> Self {
         Self {
             id: 0,
+            client_id: None,
             label: String::new(),
             updated_at: Utc::now(),
         }
@@ -83,6 +219,7 @@ impl From<TrackerEntry> for TrackerEntryUpdateDto {
     fn from(entry: TrackerEntry) -> Self {
         Self {
             id: entry.id,
+            client_id: Some(entry.client_id),
             label: entry.label,
             updated_at: entry.updated_at,
         }
@@ -130,13 +267,31 @@ impl From<TrackerEntryLine> for TrackerEntryLineCreateDto {
     }
 }
 
+/// Result of switching lines: the line that started and every line that was stopped for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerSwitchDto {
+    pub started: TrackerEntryLineViewDto,
+    /// The line switched away from comes first.
+    pub stopped: Vec<TrackerEntryLineViewDto>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerEntryLineViewDto {
     pub id: i64,
     pub entry_id: i64,
     pub desc: String,
+    pub is_billable: bool,
+    /// Set on the line itself, `None` when it inherits from its tracker or client.
+    pub rate_override: Option<Decimal>,
+    pub hourly_rate: Option<Decimal>,
+    pub currency: Option<String>,
+    /// Sum of the finished interval amounts, `None` when the line is not billed.
+    pub amount: Option<Decimal>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub tags: Vec<TagViewDto>,
+    /// Values of the line-scoped custom fields that are set.
+    pub fields: Vec<CustomFieldValueViewDto>,
     pub durations: Vec<TrackerEntryLineDurationViewDto>,
 }
 
@@ -146,8 +301,15 @@ impl Default for TrackerEntryLineViewDto {
             id: 0,
             entry_id: 0,
             desc: String::new(),
+            is_billable: true,
+            rate_override: None,
+            hourly_rate: None,
+            currency: None,
+            amount: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            fields: Vec::new(),
             durations: Vec::new(),
         }
     }
@@ -159,13 +321,50 @@ impl From<TrackerEntryLine> for TrackerEntryLineViewDto {
             id: line.id,
             entry_id: line.entry_id,
             desc: line.desc,
+            is_billable: true,
+            rate_override: None,
+            hourly_rate: None,
+            currency: None,
+            amount: None,
             created_at: line.created_at,
             updated_at: line.updated_at,
+            tags: Vec::new(),
+            fields: Vec::new(),
             durations: Vec::new(),
         }
     }
 }
 
+impl TrackerEntryLineViewDto {
+    /// Fills in the line's billing settings and prices each finished interval.
+    ///
+    /// Intervals are rounded one by one, so the line amount is exactly the sum of its intervals.
+    pub fn apply_rate(&mut self, rate: &LineRate) {
+        self.is_billable = rate.is_billable;
+        self.rate_override = parse_rate(&rate.rate_override);
+        self.hourly_rate = parse_rate(&rate.hourly_rate);
+        self.currency = rate.currency.clone();
+
+        let billable_rate = rate.billable_rate().map(|(hourly_rate, _)| hourly_rate);
+        for duration in &mut self.durations {
+            duration.amount = billable_rate.and_then(|hourly_rate| {
+                let ended_at = duration.ended_at?;
+                Some(billable_amount(
+                    hourly_rate,
+                    (ended_at - duration.started_at).num_seconds(),
+                ))
+            });
+        }
+
+        self.amount = billable_rate.map(|_| {
+            self.durations
+                .iter()
+                .filter_map(|duration| duration.amount)
+                .sum()
+        });
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerEntryLineUpdateDto {
     pub id: i64,
@@ -213,6 +412,9 @@ pub struct TrackerEntryLineDurationViewDto {
     pub entry_line_id: i64,
     pub started_at: DateTime<Utc>,
     pub ended_at: Option<DateTime<Utc>>,
+    /// Billed amount, `None` while running or when the line is not billed.
+    pub amount: Option<Decimal>,
+    pub invoice_line_id: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -224,6 +426,8 @@ impl Default for TrackerEntryLineDurationViewDto {
             entry_line_id: 0,
             started_at: Utc::now(),
             ended_at: None,
+            amount: None,
+            invoice_line_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -239,8 +443,65 @@ impl From<TrackerEntryLineDuration> for TrackerEntryLineDurationViewDto {
             entry_line_id: duration.entry_line_id,
             started_at: duration.started_at,
             ended_at: duration.ended_at,
+            amount: None,
+            invoice_line_id: duration.invoice_line_id,
             created_at: duration.created_at,
             updated_at: duration.updated_at,
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerEntryLineDurationCreateDto {
+    pub entry_line_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl Default for TrackerEntryLineDurationCreateDto {
+    fn default() -> Self {
+        Self {
+            entry_line_id: 0,
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerEntryLineDurationUpdateDto {
+    pub id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl Default for TrackerEntryLineDurationUpdateDto {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            started_at: Utc::now(),
+            ended_at: None,
+        }
+    }
+}
+
+impl From<TrackerEntryLineDuration> for TrackerEntryLineDurationUpdateDto {
+    fn from(duration: TrackerEntryLineDuration) -> Self {
+        Self {
+            id: duration.id,
+            started_at: duration.started_at,
+            ended_at: duration.ended_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerEntryLineDurationDeleteDto {
+    pub id: i64,
+}
+
+impl From<TrackerEntryLineDuration> for TrackerEntryLineDurationDeleteDto {
+    fn from(duration: TrackerEntryLineDuration) -> Self {
+        Self { id: duration.id }
+    }
+}
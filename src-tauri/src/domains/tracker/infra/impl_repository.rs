@@ -2,127 +2,272 @@ use crate::domains::tracker::{
     TrackerRepositoryTrait,
     domain::model::{TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration},
 };
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
 use std::future::Future;
 
 pub struct TrackerRepository;
 
 impl TrackerRepositoryTrait for TrackerRepository {
-    fn create_entry(
-        &self,
-        pool: SqlitePool,
+    fn create_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + '_>> {
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>> {
         Box::pin(async move {
             let entry = sqlx::query_as::<_, TrackerEntry>(
                 r#"
-                INSERT INTO tracker_entry (label, created_at, updated_at, is_deleted)
-                VALUES (?, ?, ?, ?)
-                RETURNING id, label, created_at, updated_at, is_deleted"#,
+                INSERT INTO tracker_entry (client_id, label, created_at, updated_at, is_deleted,
+                    sort_order)
+                VALUES (?, ?, ?, ?, ?,
+                    (SELECT coalesce(min(sort_order), 0) - 1 FROM tracker_entry))
+                RETURNING id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color"#,
             )
+            .bind(entry.client_id)
             .bind(&entry.label)
             .bind(entry.created_at)
             .bind(entry.updated_at)
             .bind(entry.is_deleted)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(entry)
         })
     }
 
-    fn get_entry(
-        &self,
-        pool: SqlitePool,
+    fn get_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         id: i64,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + '_>>
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + 'a>>
     {
         Box::pin(async move {
             let entry = sqlx::query_as::<_, TrackerEntry>(
                 r#"
-                SELECT id, label, created_at, updated_at, is_deleted
+                SELECT id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
                 FROM tracker_entry
                 WHERE id = ? AND is_deleted = 0
                 "#,
             )
             .bind(id)
-            .fetch_optional(&pool)
+            .fetch_optional(&mut *conn)
             .await?;
 
             Ok(entry)
         })
     }
 
-    fn get_all_entries(
-        &self,
-        pool: SqlitePool,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntry>>> + Send + '_>> {
+    fn get_entry_by_label<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        label: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntry>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, TrackerEntry>(
+                r#"
+                SELECT id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
+                FROM tracker_entry
+                WHERE label = ? AND is_deleted = 0
+                ORDER BY created_at
+                LIMIT 1
+                "#,
+            )
+            .bind(label)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn get_all_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        include_archived: bool,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntry>>> + Send + 'a>> {
         Box::pin(async move {
             let entries = sqlx::query_as::<_, TrackerEntry>(
                 r#"
-                SELECT id, label, created_at, updated_at, is_deleted
+                SELECT id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
                 FROM tracker_entry
-                WHERE is_deleted = 0
-                ORDER BY created_at DESC
+                WHERE is_deleted = 0 AND (? OR is_archived = 0)
+                ORDER BY is_pinned DESC, sort_order, created_at DESC
                 "#,
             )
-            .fetch_all(&pool)
+            .bind(include_archived)
+            .fetch_all(&mut *conn)
             .await?;
 
             Ok(entries)
         })
     }
 
-    fn update_entry(
-        &self,
-        pool: SqlitePool,
+    fn update_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + '_>> {
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>> {
         Box::pin(async move {
             let entry = sqlx::query_as::<_, TrackerEntry>(
                 r#"
                 UPDATE tracker_entry
-                SET label = ?, updated_at = ?
+                SET client_id = ?, label = ?, updated_at = ?
                 WHERE id = ?
-                RETURNING id, label, created_at, updated_at, is_deleted
+                RETURNING id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
                 "#,
             )
+            .bind(entry.client_id)
             .bind(&entry.label)
             .bind(entry.updated_at)
             .bind(entry.id)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn set_entry_archived<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        archived_at: Option<DateTime<Utc>>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>> {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, TrackerEntry>(
+                r#"
+                UPDATE tracker_entry
+                SET is_archived = ?, archived_at = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
+                "#,
+            )
+            .bind(archived_at.is_some())
+            .bind(archived_at)
+            .bind(updated_at)
+            .bind(entry_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn set_entry_sort_order<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        sort_order: i64,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry
+                SET sort_order = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(sort_order)
+            .bind(updated_at)
+            .bind(entry_id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_entry_pinned<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        is_pinned: bool,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>> {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, TrackerEntry>(
+                r#"
+                UPDATE tracker_entry
+                SET is_pinned = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
+                "#,
+            )
+            .bind(is_pinned)
+            .bind(updated_at)
+            .bind(entry_id)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(entry)
         })
     }
 
-    fn delete_entry(
-        &self,
-        pool: SqlitePool,
+    fn set_entry_color<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        color: Option<String>,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntry>> + Send + 'a>> {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, TrackerEntry>(
+                r#"
+                UPDATE tracker_entry
+                SET color = ?, updated_at = ?
+                WHERE id = ?
+                RETURNING id, client_id, label, created_at, updated_at, is_deleted, is_archived,
+                    archived_at, sort_order, is_pinned, color
+                "#,
+            )
+            .bind(color)
+            .bind(updated_at)
+            .bind(entry_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn delete_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>> {
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
         Box::pin(async move {
             sqlx::query(
                 r#"
                 UPDATE tracker_entry
-                SET is_deleted = 1
+                SET is_deleted = 1, deleted_at = ?
                 WHERE id = ? AND is_deleted = 0
                 "#,
             )
+            .bind(deleted_at)
             .bind(entry.id)
-            .execute(&pool)
+            .execute(&mut *conn)
             .await?;
 
             Ok(())
         })
     }
 
-    fn create_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn create_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + '_>> {
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + 'a>> {
         Box::pin(async move {
             let lines = sqlx::query_as::<_, TrackerEntryLine>(
                 r#"
@@ -136,18 +281,18 @@ impl TrackerRepositoryTrait for TrackerRepository {
             .bind(line.created_at)
             .bind(line.updated_at)
             .bind(line.is_deleted)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(lines)
         })
     }
 
-    fn get_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn get_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         id: i64,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + '_>>
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + 'a>>
     {
         Box::pin(async move {
             let line = sqlx::query_as::<_, TrackerEntryLine>(
@@ -158,17 +303,43 @@ impl TrackerRepositoryTrait for TrackerRepository {
                 "#,
             )
             .bind(id)
-            .fetch_optional(&pool)
+            .fetch_optional(&mut *conn)
             .await?;
 
             Ok(line)
         })
     }
 
-    fn get_all_entry_lines(
-        &self,
-        pool: SqlitePool,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + '_>>
+    fn get_entry_line_by_desc<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+        desc: String,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLine>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let line = sqlx::query_as::<_, TrackerEntryLine>(
+                r#"
+                SELECT id, entry_id, desc, created_at, updated_at, is_deleted
+                FROM tracker_entry_line
+                WHERE entry_id = ? AND desc = ? AND is_deleted = 0
+                ORDER BY created_at
+                LIMIT 1
+                "#,
+            )
+            .bind(entry_id)
+            .bind(desc)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(line)
+        })
+    }
+
+    fn get_all_entry_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>
     {
         Box::pin(async move {
             let lines = sqlx::query_as::<_, TrackerEntryLine>(
@@ -179,18 +350,18 @@ impl TrackerRepositoryTrait for TrackerRepository {
                 ORDER BY created_at DESC
                 "#,
             )
-            .fetch_all(&pool)
+            .fetch_all(&mut *conn)
             .await?;
 
             Ok(lines)
         })
     }
 
-    fn get_lines_for_entry(
-        &self,
-        pool: SqlitePool,
+    fn get_lines_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + '_>>
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>
     {
         Box::pin(async move {
             let lines = sqlx::query_as::<_, TrackerEntryLine>(
@@ -202,91 +373,119 @@ impl TrackerRepositoryTrait for TrackerRepository {
                 "#,
             )
             .bind(entry.id)
-            .fetch_all(&pool)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(lines)
+        })
+    }
+
+    fn get_lines_for_all_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLine>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let lines = sqlx::query_as::<_, TrackerEntryLine>(
+                r#"
+                SELECT l.id, l.entry_id, l.desc, l.created_at, l.updated_at, l.is_deleted
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE l.is_deleted = 0 AND e.is_deleted = 0
+                ORDER BY l.created_at DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
             .await?;
 
             Ok(lines)
         })
     }
 
-    fn update_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn update_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + '_>> {
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLine>> + Send + 'a>> {
         Box::pin(async move {
             let line = sqlx::query_as::<_, TrackerEntryLine>(
                 r#"
                 UPDATE tracker_entry_line
-                SET desc = ?, updated_at = ?
+                SET entry_id = ?, desc = ?, updated_at = ?
                 WHERE id = ?
                 RETURNING id, entry_id, desc, created_at, updated_at, is_deleted
                 "#,
             )
+            .bind(line.entry_id)
             .bind(line.desc)
             .bind(line.updated_at)
             .bind(line.id)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(line)
         })
     }
 
-    fn delete_entry_line(
-        &self,
-        pool: SqlitePool,
+    fn delete_entry_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>> {
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
         Box::pin(async move {
             sqlx::query(
                 r#"
                 UPDATE tracker_entry_line
-                SET is_deleted = 1
+                SET is_deleted = 1, deleted_at = ?
                 WHERE id = ? AND is_deleted = 0
                 "#,
             )
+            .bind(deleted_at)
             .bind(line.id)
-            .execute(&pool)
+            .execute(&mut *conn)
             .await?;
 
             Ok(())
         })
     }
 
-    fn delete_lines_for_entry(
-        &self,
-        pool: SqlitePool,
+    fn delete_lines_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         entry: TrackerEntry,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>> {
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
         Box::pin(async move {
             sqlx::query(
                 r#"
                 UPDATE tracker_entry_line
-                SET is_deleted = 1
+                SET is_deleted = 1, deleted_at = ?
                 WHERE entry_id = ? AND is_deleted = 0
                 "#,
             )
+            .bind(deleted_at)
             .bind(entry.id)
-            .execute(&pool)
+            .execute(&mut *conn)
             .await?;
 
             Ok(())
         })
     }
 
-    fn create_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn create_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + '_>>
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + 'a>>
     {
         Box::pin(async move {
             let duration = sqlx::query_as::<_, TrackerEntryLineDuration>(
                 r#"
                 INSERT INTO tracker_entry_line_duration (entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted)
                 VALUES (?, ?, ?, ?, ?, ?)
-                RETURNING id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted
+                RETURNING id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted,
+                    invoice_line_id
                 "#,
             )
             .bind(duration.entry_line_id)
@@ -295,42 +494,151 @@ impl TrackerRepositoryTrait for TrackerRepository {
             .bind(duration.created_at)
             .bind(duration.updated_at)
             .bind(duration.is_deleted)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(duration)
         })
     }
 
-    fn get_line_durations(
-        &self,
-        pool: SqlitePool,
+    fn get_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLineDuration>>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            let duration = sqlx::query_as::<_, TrackerEntryLineDuration>(
+                r#"
+                SELECT id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted,
+                    invoice_line_id
+                FROM tracker_entry_line_duration
+                WHERE id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(duration)
+        })
+    }
+
+    fn get_line_durations<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         line: TrackerEntryLine,
     ) -> std::pin::Pin<
-        Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + '_>,
+        Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>,
     > {
         Box::pin(async move {
             let durations = sqlx::query_as::<_, TrackerEntryLineDuration>(
                 r#"
-                SELECT id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted
+                SELECT id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted,
+                    invoice_line_id
                 FROM tracker_entry_line_duration
                 WHERE entry_line_id = ? AND is_deleted = 0
                 ORDER BY started_at DESC
                 "#,
             )
             .bind(line.id)
-            .fetch_all(&pool)
+            .fetch_all(&mut *conn)
             .await?;
 
             Ok(durations)
         })
     }
 
-    fn update_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn get_matching_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_line_id: i64,
+        started_at: DateTime<Utc>,
+        ended_at: Option<DateTime<Utc>>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = sqlx::Result<Option<TrackerEntryLineDuration>>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            // julianday() normalises the stored formats, the tolerance absorbs its rounding
+            let duration = sqlx::query_as::<_, TrackerEntryLineDuration>(
+                r#"
+                SELECT id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted,
+                    invoice_line_id
+                FROM tracker_entry_line_duration
+                WHERE entry_line_id = ? AND is_deleted = 0
+                    AND abs(julianday(started_at) - julianday(?)) * 86400000 < 1
+                    AND ((ended_at IS NULL AND ? IS NULL)
+                        OR abs(julianday(ended_at) - julianday(?)) * 86400000 < 1)
+                LIMIT 1
+                "#,
+            )
+            .bind(entry_line_id)
+            .bind(started_at)
+            .bind(ended_at)
+            .bind(ended_at)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(duration)
+        })
+    }
+
+    fn get_durations_for_all_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            let durations = sqlx::query_as::<_, TrackerEntryLineDuration>(
+                r#"
+                SELECT d.id, d.entry_line_id, d.started_at, d.ended_at, d.created_at, d.updated_at, d.is_deleted,
+                    d.invoice_line_id
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                ORDER BY d.started_at DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(durations)
+        })
+    }
+
+    fn get_open_durations<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = sqlx::Result<Vec<TrackerEntryLineDuration>>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            let durations = sqlx::query_as::<_, TrackerEntryLineDuration>(
+                r#"
+                SELECT d.id, d.entry_line_id, d.started_at, d.ended_at, d.created_at, d.updated_at, d.is_deleted,
+                    d.invoice_line_id
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE d.ended_at IS NULL AND d.is_deleted = 0 AND l.is_deleted = 0 AND e.is_deleted = 0
+                ORDER BY d.started_at
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(durations)
+        })
+    }
+
+    fn update_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + '_>>
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<TrackerEntryLineDuration>> + Send + 'a>>
     {
         Box::pin(async move {
             let duration = sqlx::query_as::<_, TrackerEntryLineDuration>(
@@ -338,35 +646,110 @@ impl TrackerRepositoryTrait for TrackerRepository {
                 UPDATE tracker_entry_line_duration
                 SET started_at = ?, ended_at = ?, updated_at = ?
                 WHERE id = ?
-                RETURNING id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted
+                RETURNING id, entry_line_id, started_at, ended_at, created_at, updated_at, is_deleted,
+                    invoice_line_id
                 "#,
             )
             .bind(duration.started_at)
             .bind(duration.ended_at)
             .bind(duration.updated_at)
             .bind(duration.id)
-            .fetch_one(&pool)
+            .fetch_one(&mut *conn)
             .await?;
 
             Ok(duration)
         })
     }
 
-    fn delete_line_duration(
-        &self,
-        pool: SqlitePool,
+    fn move_durations_to_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        source: TrackerEntryLine,
+        target: TrackerEntryLine,
+        updated_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line_duration
+                SET entry_line_id = ?, updated_at = ?
+                WHERE entry_line_id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(target.id)
+            .bind(updated_at)
+            .bind(source.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_durations_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrackerEntryLine,
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line_duration
+                SET is_deleted = 1, deleted_at = ?
+                WHERE entry_line_id = ? AND is_deleted = 0
+                "#,
+            )
+            .bind(deleted_at)
+            .bind(line.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_durations_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrackerEntry,
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line_duration
+                SET is_deleted = 1, deleted_at = ?
+                WHERE is_deleted = 0
+                    AND entry_line_id IN (SELECT id FROM tracker_entry_line WHERE entry_id = ?)
+                "#,
+            )
+            .bind(deleted_at)
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_line_duration<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
         duration: TrackerEntryLineDuration,
-    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + '_>> {
+        deleted_at: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
         Box::pin(async move {
             sqlx::query(
                 r#"
                 UPDATE tracker_entry_line_duration
-                SET is_deleted = 1
+                SET is_deleted = 1, deleted_at = ?
                 WHERE id = ? AND is_deleted = 0
                 "#,
             )
+            .bind(deleted_at)
             .bind(duration.id)
-            .execute(&pool)
+            .execute(&mut *conn)
             .await?;
 
             Ok(())
@@ -1,23 +1,318 @@
 use crate::{
-    domains::tracker::{
-        TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration, TrackerRepositoryTrait,
-        TrackerServiceTrait,
-        dto::tracker_dto::{
-            TrackerEntryCreateDto, TrackerEntryDeleteDto, TrackerEntryLineCreateDto,
-            TrackerEntryLineDeleteDto, TrackerEntryLineDurationViewDto, TrackerEntryLineUpdateDto,
-            TrackerEntryLineViewDto, TrackerEntryViewDto,
+    domains::{
+        billing::{BillingRepository, BillingRepositoryTrait, EntryRate, LineRate, parse_rate},
+        budget::{BudgetRepository, BudgetRepositoryTrait, EntryBudget},
+        client::{
+            Client, ClientRepository, ClientRepositoryTrait, ClientTrackersDto, ClientViewDto,
+        },
+        custom_field::{
+            CustomFieldRepository, CustomFieldRepositoryTrait, CustomFieldValueViewDto,
+        },
+        history::HistoryRecorder,
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+        tag::{TagRepository, TagRepositoryTrait, TagViewDto},
+        tracker::{
+            SINGLE_ACTIVE_SETTING_KEY, TrackerEntry, TrackerEntryLine, TrackerEntryLineDuration,
+            TrackerRepositoryTrait, TrackerServiceTrait,
+            domain::model::validate_color,
+            dto::tracker_dto::{
+                TrackerEntryCreateDto, TrackerEntryDeleteDto, TrackerEntryLineCreateDto,
+                TrackerEntryLineDeleteDto, TrackerEntryLineDurationCreateDto,
+                TrackerEntryLineDurationDeleteDto, TrackerEntryLineDurationUpdateDto,
+                TrackerEntryLineDurationViewDto, TrackerEntryLineUpdateDto,
+                TrackerEntryLineViewDto, TrackerEntryUpdateDto, TrackerEntryViewDto,
+                TrackerQueryDto, TrackerSwitchDto,
+            },
+            infra::impl_repository::TrackerRepository,
         },
-        infra::impl_repository::TrackerRepository,
     },
     error::AppError,
 };
-use chrono::Utc;
-use sqlx::SqlitePool;
-use std::{future::Future, sync::Arc};
+use chrono::{DateTime, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
 
 pub struct TrackerService {
     pool: SqlitePool,
     repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    tag_repo: Arc<dyn TagRepositoryTrait + Send + Sync>,
+    client_repo: Arc<dyn ClientRepositoryTrait + Send + Sync>,
+    billing_repo: Arc<dyn BillingRepositoryTrait + Send + Sync>,
+    budget_repo: Arc<dyn BudgetRepositoryTrait + Send + Sync>,
+    field_repo: Arc<dyn CustomFieldRepositoryTrait + Send + Sync>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+fn invoiced_error(duration_id: i64) -> AppError {
+    AppError::ValidationError(format!(
+        "Interval {} has been invoiced and can no longer be changed",
+        duration_id
+    ))
+}
+
+fn archived_error(entry_id: i64) -> AppError {
+    AppError::ValidationError(format!(
+        "Tracker {} is archived, unarchive it to track time",
+        entry_id
+    ))
+}
+
+impl TrackerService {
+    /// Whether only one line may run at a time, off unless it was turned on.
+    async fn single_active(&self, conn: &mut SqliteConnection) -> Result<bool, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, SINGLE_ACTIVE_SETTING_KEY)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        Ok(setting.value.parse().unwrap_or_else(|_| {
+            log::warn!(
+                "Invalid single-active setting '{}', letting several lines run",
+                setting.value
+            );
+            false
+        }))
+    }
+
+    /// Ends every running interval at `ended_at`, except the one on `keep_line_id`, and
+    /// returns the lines that were stopped.
+    async fn close_open_durations(
+        &self,
+        conn: &mut SqliteConnection,
+        ended_at: DateTime<Utc>,
+        keep_line_id: Option<i64>,
+    ) -> Result<Vec<TrackerEntryLine>, AppError> {
+        let mut stopped = Vec::new();
+        for mut duration in self.repo.get_open_durations(conn).await? {
+            if Some(duration.entry_line_id) == keep_line_id {
+                continue;
+            }
+
+            duration.ended_at = Some(ended_at);
+            duration.updated_at = ended_at;
+            let updated = self.repo.update_line_duration(conn, duration).await?;
+
+            if let Some(line) = self
+                .repo
+                .get_entry_line(conn, updated.entry_line_id)
+                .await?
+            {
+                stopped.push(line);
+            }
+        }
+
+        Ok(stopped)
+    }
+
+    /// Looks up the requested client, or the default one when none was requested.
+    async fn resolve_client(
+        &self,
+        conn: &mut SqliteConnection,
+        client_id: Option<i64>,
+    ) -> Result<Client, AppError> {
+        match client_id {
+            Some(client_id) => self
+                .client_repo
+                .get_client(conn, client_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Client with id {} not found", client_id))
+                }),
+            None => Ok(self.client_repo.get_default_client(conn).await?),
+        }
+    }
+
+    /// Builds the line view with its tags and fields, all of its live durations and their
+    /// amounts.
+    async fn line_view(
+        &self,
+        conn: &mut SqliteConnection,
+        line: TrackerEntryLine,
+    ) -> Result<TrackerEntryLineViewDto, AppError> {
+        let tags = self.tag_repo.get_tags_for_line(conn, line.id).await?;
+        let fields = self.field_repo.get_values_for_line(conn, line.id).await?;
+        let durations = self.repo.get_line_durations(conn, line.clone()).await?;
+        let rate = self.billing_repo.get_line_rate(conn, line.id).await?;
+
+        let mut line_dto = TrackerEntryLineViewDto::from(line);
+        line_dto.tags = tags.into_iter().map(TagViewDto::from).collect();
+        line_dto.fields = fields
+            .into_iter()
+            .map(CustomFieldValueViewDto::from)
+            .collect();
+        line_dto.durations = durations
+            .into_iter()
+            .map(TrackerEntryLineDurationViewDto::from)
+            .collect();
+        if let Some(rate) = rate {
+            line_dto.apply_rate(&rate);
+        }
+
+        Ok(line_dto)
+    }
+
+    /// Builds the tracker view with its fields and all of its live lines and their durations.
+    async fn entry_view(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: TrackerEntry,
+    ) -> Result<TrackerEntryViewDto, AppError> {
+        let lines = self.repo.get_lines_for_entry(conn, entry.clone()).await?;
+
+        let mut line_dtos = Vec::with_capacity(lines.len());
+        for line in lines {
+            line_dtos.push(self.line_view(conn, line).await?);
+        }
+
+        let rate = self.billing_repo.get_entry_rate(conn, entry.id).await?;
+        let fields = self.field_repo.get_values_for_entry(conn, entry.id).await?;
+
+        let mut entry_dto = TrackerEntryViewDto::from(entry);
+        entry_dto.fields = fields
+            .into_iter()
+            .map(CustomFieldValueViewDto::from)
+            .collect();
+        entry_dto.lines = line_dtos;
+        if let Some(rate) = rate {
+            entry_dto.apply_rate(&rate);
+        }
+
+        Ok(entry_dto)
+    }
+
+    /// Moves the live intervals and tags of `source` onto `target` and trashes `source`.
+    /// Invoiced intervals stay where they were billed, so a line holding any cannot be merged.
+    async fn merge_line_into(
+        &self,
+        conn: &mut SqliteConnection,
+        source: TrackerEntryLine,
+        target: TrackerEntryLine,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let durations = self.repo.get_line_durations(conn, source.clone()).await?;
+        let target_durations = self.repo.get_line_durations(conn, target.clone()).await?;
+
+        for duration in &durations {
+            if duration.invoice_line_id.is_some() {
+                return Err(invoiced_error(duration.id));
+            }
+
+            let moved = TrackerEntryLineDuration {
+                entry_line_id: target.id,
+                ..duration.clone()
+            };
+            moved.validate(&target_durations).map_err(|e| {
+                AppError::ValidationError(format!("Interval {}: {}", duration.id, e))
+            })?;
+        }
+
+        self.repo
+            .move_durations_to_line(conn, source.clone(), target.clone(), now)
+            .await?;
+
+        let mut tag_ids: Vec<i64> = self
+            .tag_repo
+            .get_tags_for_line(conn, target.id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.tag_id)
+            .collect();
+        for tag in self.tag_repo.get_tags_for_line(conn, source.id).await? {
+            if !tag_ids.contains(&tag.tag_id) {
+                tag_ids.push(tag.tag_id);
+            }
+        }
+        self.tag_repo
+            .set_line_tags(conn, target.id, tag_ids)
+            .await?;
+
+        self.repo.delete_entry_line(conn, source, now).await?;
+
+        Ok(())
+    }
+
+    /// Hands the rate, budget and field values of `source` to `target` where it has none of
+    /// its own. Settings the two disagree on are refused rather than dropped.
+    async fn carry_over_settings(
+        &self,
+        conn: &mut SqliteConnection,
+        source: &TrackerEntry,
+        target: &TrackerEntry,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let conflict = |setting: &str| {
+            AppError::ValidationError(format!(
+                "Trackers '{}' and '{}' have different {}, make them match before merging",
+                source.label, target.label, setting
+            ))
+        };
+
+        let source_rate = self.billing_repo.get_entry_rate(conn, source.id).await?;
+        let target_rate = self.billing_repo.get_entry_rate(conn, target.id).await?;
+        let rate_of = |rate: Option<EntryRate>| {
+            rate.and_then(|rate| Some((parse_rate(&rate.rate_override)?, rate.currency_override)))
+        };
+        match (rate_of(source_rate), rate_of(target_rate)) {
+            (Some((rate, currency)), None) => {
+                self.billing_repo
+                    .set_entry_rate(conn, target.id, Some(rate), currency, now)
+                    .await?;
+            }
+            (Some(source_rate), Some(target_rate)) if source_rate != target_rate => {
+                return Err(conflict("hourly rates"));
+            }
+            _ => {}
+        }
+
+        let budget_of = |budget: Option<EntryBudget>| {
+            budget.and_then(|budget| Some((budget.budget_seconds?, budget.budget_period)))
+        };
+        let source_budget = budget_of(self.budget_repo.get_entry_budget(conn, source.id).await?);
+        let target_budget = budget_of(self.budget_repo.get_entry_budget(conn, target.id).await?);
+        match (source_budget, target_budget) {
+            (Some((seconds, period)), None) => {
+                self.budget_repo
+                    .set_entry_budget(conn, target.id, Some(seconds), period, now)
+                    .await?;
+            }
+            (Some(source_budget), Some(target_budget)) if source_budget != target_budget => {
+                return Err(conflict("budgets"));
+            }
+            _ => {}
+        }
+
+        let target_values = self
+            .field_repo
+            .get_values_for_entry(conn, target.id)
+            .await?;
+        for value in self
+            .field_repo
+            .get_values_for_entry(conn, source.id)
+            .await?
+        {
+            match target_values.iter().find(|v| v.field_id == value.field_id) {
+                None => {
+                    self.field_repo
+                        .set_entry_value(conn, value.field_id, target.id, Some(value.value), now)
+                        .await?;
+                }
+                Some(target_value) if target_value.value != value.value => {
+                    return Err(conflict(&format!("values for '{}'", value.name)));
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl TrackerServiceTrait for TrackerService {
@@ -28,6 +323,13 @@ impl TrackerServiceTrait for TrackerService {
         Arc::new(Self {
             pool,
             repo: Arc::new(TrackerRepository {}),
+            tag_repo: Arc::new(TagRepository {}),
+            client_repo: Arc::new(ClientRepository {}),
+            billing_repo: Arc::new(BillingRepository {}),
+            budget_repo: Arc::new(BudgetRepository {}),
+            field_repo: Arc::new(CustomFieldRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+            history: HistoryRecorder::default(),
         })
     }
 
@@ -37,59 +339,224 @@ impl TrackerServiceTrait for TrackerService {
     ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
     {
         Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+            let before = self.history.capture(&mut tx, Vec::new()).await?;
+
+            let client = self.resolve_client(&mut tx, dto.client_id).await?;
+
             let entry = TrackerEntry {
+                client_id: client.id,
                 label: dto.label,
                 ..Default::default()
             };
 
-            let created = self.repo.create_entry(self.pool.clone(), entry).await?;
+            let created = self.repo.create_entry(&mut tx, entry).await?;
+            self.history
+                .record(&mut tx, "Create tracker", before, &[created.id])
+                .await?;
+
+            // The view picks up the rate the tracker inherits from its client
+            let entry_dto = self.entry_view(&mut tx, created).await?;
 
-            Ok(created.into())
+            tx.commit().await?;
+
+            Ok(entry_dto)
         })
     }
 
     fn get_trackers(
         &self,
+        query: TrackerQueryDto,
     ) -> std::pin::Pin<
         Box<dyn Future<Output = Result<Vec<TrackerEntryViewDto>, AppError>> + Send + '_>,
     > {
         Box::pin(async move {
-            let entries = self.repo.get_all_entries(self.pool.clone()).await?;
+            let mut conn = self.pool.acquire().await?;
 
-            let mut dtos: Vec<TrackerEntryViewDto> = Vec::new();
+            // Load the whole tree in a fixed number of queries and assemble it in memory
+            let entries = self
+                .repo
+                .get_all_entries(&mut conn, query.include_archived)
+                .await?;
+            let lines = self.repo.get_lines_for_all_entries(&mut conn).await?;
+            let durations = self.repo.get_durations_for_all_lines(&mut conn).await?;
+            let line_tags = self.tag_repo.get_tags_for_all_lines(&mut conn).await?;
+            let entry_rates = self.billing_repo.get_entry_rates(&mut conn).await?;
+            let line_rates = self.billing_repo.get_line_rates(&mut conn).await?;
+            let field_values = self.field_repo.get_all_values(&mut conn).await?;
 
-            for entry in entries {
-                let lines = self
-                    .repo
-                    .get_lines_for_entry(self.pool.clone(), entry.clone())
-                    .await?;
+            let entry_rates: HashMap<i64, EntryRate> = entry_rates
+                .into_iter()
+                .map(|rate| (rate.entry_id, rate))
+                .collect();
+            let line_rates: HashMap<i64, LineRate> = line_rates
+                .into_iter()
+                .map(|rate| (rate.line_id, rate))
+                .collect();
 
-                let mut line_dtos: Vec<TrackerEntryLineViewDto> = Vec::new();
+            let mut tags_by_line: HashMap<i64, Vec<TagViewDto>> = HashMap::new();
+            for line_tag in line_tags {
+                tags_by_line
+                    .entry(line_tag.line_id)
+                    .or_default()
+                    .push(TagViewDto::from(line_tag));
+            }
 
-                for line in lines {
-                    let durations = self
-                        .repo
-                        .get_line_durations(self.pool.clone(), line.clone())
-                        .await?;
-                    let duration_dtos: Vec<TrackerEntryLineDurationViewDto> = durations
-                        .into_iter()
-                        .map(TrackerEntryLineDurationViewDto::from)
-                        .collect();
-
-                    let mut line_dto = TrackerEntryLineViewDto::from(line);
-                    line_dto.durations = duration_dtos;
-                    line_dtos.push(line_dto);
+            let mut fields_by_entry: HashMap<i64, Vec<CustomFieldValueViewDto>> = HashMap::new();
+            let mut fields_by_line: HashMap<i64, Vec<CustomFieldValueViewDto>> = HashMap::new();
+            for value in field_values {
+                match (value.entry_id, value.line_id) {
+                    (Some(entry_id), _) => fields_by_entry
+                        .entry(entry_id)
+                        .or_default()
+                        .push(CustomFieldValueViewDto::from(value)),
+                    (None, Some(line_id)) => fields_by_line
+                        .entry(line_id)
+                        .or_default()
+                        .push(CustomFieldValueViewDto::from(value)),
+                    (None, None) => {}
                 }
+            }
+
+            let mut durations_by_line: HashMap<i64, Vec<TrackerEntryLineDurationViewDto>> =
+                HashMap::new();
+            for duration in durations {
+                durations_by_line
+                    .entry(duration.entry_line_id)
+                    .or_default()
+                    .push(TrackerEntryLineDurationViewDto::from(duration));
+            }
 
-                let mut entry_dto = TrackerEntryViewDto::from(entry);
-                entry_dto.lines = line_dtos;
-                dtos.push(entry_dto);
+            let mut lines_by_entry: HashMap<i64, Vec<TrackerEntryLineViewDto>> = HashMap::new();
+            for line in lines {
+                let entry_id = line.entry_id;
+                let mut line_dto = TrackerEntryLineViewDto::from(line);
+                line_dto.tags = tags_by_line.remove(&line_dto.id).unwrap_or_default();
+                line_dto.fields = fields_by_line.remove(&line_dto.id).unwrap_or_default();
+                line_dto.durations = durations_by_line.remove(&line_dto.id).unwrap_or_default();
+                if let Some(rate) = line_rates.get(&line_dto.id) {
+                    line_dto.apply_rate(rate);
+                }
+                lines_by_entry.entry(entry_id).or_default().push(line_dto);
             }
 
+            let mut dtos: Vec<TrackerEntryViewDto> = entries
+                .into_iter()
+                .map(|entry| {
+                    let mut entry_dto = TrackerEntryViewDto::from(entry);
+                    entry_dto.fields = fields_by_entry.remove(&entry_dto.id).unwrap_or_default();
+                    entry_dto.lines = lines_by_entry.remove(&entry_dto.id).unwrap_or_default();
+                    if let Some(rate) = entry_rates.get(&entry_dto.id) {
+                        entry_dto.apply_rate(rate);
+                    }
+                    entry_dto
+                })
+                .collect();
+            query.sort.sort(&mut dtos, Utc::now());
+
             Ok(dtos)
         })
     }
 
+    fn get_trackers_by_client(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<Vec<ClientTrackersDto>, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let clients = {
+                let mut conn = self.pool.acquire().await?;
+                self.client_repo.get_all_clients(&mut conn).await?
+            };
+
+            let mut trackers_by_client: HashMap<i64, Vec<TrackerEntryViewDto>> = HashMap::new();
+            for tracker in self.get_trackers(TrackerQueryDto::default()).await? {
+                trackers_by_client
+                    .entry(tracker.client_id)
+                    .or_default()
+                    .push(tracker);
+            }
+
+            let groups = clients
+                .into_iter()
+                .map(|client| ClientTrackersDto {
+                    trackers: trackers_by_client.remove(&client.id).unwrap_or_default(),
+                    client: ClientViewDto::from(client),
+                })
+                .collect();
+
+            Ok(groups)
+        })
+    }
+
+    fn get_lines_by_tags(
+        &self,
+        tag_ids: Vec<i64>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<TrackerEntryLineViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let line_ids: HashSet<i64> = {
+                let mut conn = self.pool.acquire().await?;
+                self.tag_repo
+                    .get_line_ids_with_tags(&mut conn, tag_ids)
+                    .await?
+                    .into_iter()
+                    .collect()
+            };
+
+            // Reuse the bulk loader, it already skips deleted lines and trackers
+            let lines = self
+                .get_trackers(TrackerQueryDto::default())
+                .await?
+                .into_iter()
+                .flat_map(|tracker| tracker.lines)
+                .filter(|line| line_ids.contains(&line.id))
+                .collect();
+
+            Ok(lines)
+        })
+    }
+
+    fn update_tracker(
+        &self,
+        dto: TrackerEntryUpdateDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let label = dto.label.trim().to_string();
+            if label.is_empty() {
+                return Err(AppError::ValidationError(
+                    "Tracker label must not be empty".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let mut entry =
+                self.repo.get_entry(&mut tx, dto.id).await?.ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", dto.id))
+                })?;
+            let before = self.history.capture(&mut tx, vec![entry.id]).await?;
+
+            if dto.client_id.is_some() {
+                entry.client_id = self.resolve_client(&mut tx, dto.client_id).await?.id;
+            }
+            entry.label = label;
+            entry.updated_at = Utc::now();
+
+            let updated = self.repo.update_entry(&mut tx, entry).await?;
+            self.history
+                .record(&mut tx, "Edit tracker", before, &[])
+                .await?;
+
+            let entry_dto = self.entry_view(&mut tx, updated).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
+        })
+    }
+
     fn start_tracking(
         &self,
         dto: TrackerEntryLineCreateDto,
@@ -97,20 +564,43 @@ impl TrackerServiceTrait for TrackerService {
         Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
     > {
         Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .repo
+                .get_entry(&mut tx, dto.entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", dto.entry_id))
+                })?;
+            if entry.is_archived {
+                return Err(archived_error(entry.id));
+            }
+            let entry_ids = self.history.with_running(&mut tx, &[entry.id]).await?;
+            let before = self.history.capture(&mut tx, entry_ids).await?;
+
+            // Other lines stop at the very instant this one starts, so reports see no gap
+            // and no overlap between them
+            let now = Utc::now();
+            if self.single_active(&mut tx).await? {
+                self.close_open_durations(&mut tx, now, None).await?;
+            }
+
             let line = TrackerEntryLine::new(0, dto.entry_id, dto.desc);
 
-            let created_line = self.repo.create_entry_line(self.pool.clone(), line).await?;
+            let created_line = self.repo.create_entry_line(&mut tx, line).await?;
 
             // Create initial duration entry
-            let duration = TrackerEntryLineDuration::new(0, created_line.id, Utc::now(), None);
+            let duration = TrackerEntryLineDuration::new(0, created_line.id, now, None);
 
-            let created_duration = self
-                .repo
-                .create_line_duration(self.pool.clone(), duration)
+            self.repo.create_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Start tracking", before, &[])
                 .await?;
 
-            let mut line_dto = TrackerEntryLineViewDto::from(created_line);
-            line_dto.durations = vec![TrackerEntryLineDurationViewDto::from(created_duration)];
+            let line_dto = self.line_view(&mut tx, created_line).await?;
+
+            tx.commit().await?;
 
             Ok(line_dto)
         })
@@ -123,18 +613,17 @@ impl TrackerServiceTrait for TrackerService {
         Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
     > {
         Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
             // Get the line
             let line = self
                 .repo
-                .get_entry_line(self.pool.clone(), line_id)
+                .get_entry_line(&mut tx, line_id)
                 .await?
                 .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", line_id)))?;
 
             // Get all durations for this line
-            let durations = self
-                .repo
-                .get_line_durations(self.pool.clone(), line.clone())
-                .await?;
+            let durations = self.repo.get_line_durations(&mut tx, line.clone()).await?;
 
             // Find the active duration (one without ended_at)
             if let Some(active_duration) = durations.iter().find(|d| d.ended_at.is_none()) {
@@ -142,23 +631,19 @@ impl TrackerServiceTrait for TrackerService {
                 updated_duration.ended_at = Some(Utc::now());
                 updated_duration.updated_at = Utc::now();
 
+                let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
                 let _updated = self
                     .repo
-                    .update_line_duration(self.pool.clone(), updated_duration)
+                    .update_line_duration(&mut tx, updated_duration)
+                    .await?;
+                self.history
+                    .record(&mut tx, "Stop tracking", before, &[])
                     .await?;
 
                 // Get all durations again to return complete data
-                let all_durations = self
-                    .repo
-                    .get_line_durations(self.pool.clone(), line.clone())
-                    .await?;
-                let duration_dtos: Vec<TrackerEntryLineDurationViewDto> = all_durations
-                    .into_iter()
-                    .map(TrackerEntryLineDurationViewDto::from)
-                    .collect();
+                let line_dto = self.line_view(&mut tx, line).await?;
 
-                let mut line_dto = TrackerEntryLineViewDto::from(line);
-                line_dto.durations = duration_dtos;
+                tx.commit().await?;
 
                 Ok(line_dto)
             } else {
@@ -169,6 +654,55 @@ impl TrackerServiceTrait for TrackerService {
         })
     }
 
+    fn stop_interval_at(
+        &self,
+        duration_id: i64,
+        ended_at: DateTime<Utc>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Option<TrackerEntryLineViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            // The user may have stopped it in the meantime
+            let Some(mut duration) = self.repo.get_line_duration(&mut tx, duration_id).await?
+            else {
+                return Ok(None);
+            };
+            if duration.ended_at.is_some() {
+                return Ok(None);
+            }
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, duration.entry_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", duration.entry_line_id))
+                })?;
+
+            duration.ended_at = Some(ended_at);
+            duration.updated_at = Utc::now();
+
+            let siblings = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            duration
+                .validate(&siblings)
+                .map_err(AppError::ValidationError)?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo.update_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Scheduled stop", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(Some(line_dto))
+        })
+    }
+
     fn resume_tracking(
         &self,
         line_id: i64,
@@ -176,18 +710,17 @@ impl TrackerServiceTrait for TrackerService {
         Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
     > {
         Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
             // Get the line
             let line = self
                 .repo
-                .get_entry_line(self.pool.clone(), line_id)
+                .get_entry_line(&mut tx, line_id)
                 .await?
                 .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", line_id)))?;
 
             // Get all durations for this line
-            let durations = self
-                .repo
-                .get_line_durations(self.pool.clone(), line.clone())
-                .await?;
+            let durations = self.repo.get_line_durations(&mut tx, line.clone()).await?;
 
             // Check if there's already an active duration
             if durations.iter().any(|d| d.ended_at.is_none()) {
@@ -196,104 +729,962 @@ impl TrackerServiceTrait for TrackerService {
                 ));
             }
 
+            if self
+                .repo
+                .get_entry(&mut tx, line.entry_id)
+                .await?
+                .is_some_and(|entry| entry.is_archived)
+            {
+                return Err(archived_error(line.entry_id));
+            }
+
+            let entry_ids = self.history.with_running(&mut tx, &[line.entry_id]).await?;
+            let before = self.history.capture(&mut tx, entry_ids).await?;
+
+            let now = Utc::now();
+            if self.single_active(&mut tx).await? {
+                self.close_open_durations(&mut tx, now, Some(line.id))
+                    .await?;
+            }
+
             // Create new duration entry
-            let duration = TrackerEntryLineDuration::new(0, line.id, Utc::now(), None);
+            let duration = TrackerEntryLineDuration::new(0, line.id, now, None);
 
-            let _created_duration = self
-                .repo
-                .create_line_duration(self.pool.clone(), duration)
+            let _created_duration = self.repo.create_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Resume tracking", before, &[])
                 .await?;
 
             // Get all durations to return complete data
-            let all_durations = self
-                .repo
-                .get_line_durations(self.pool.clone(), line.clone())
-                .await?;
-            let duration_dtos: Vec<TrackerEntryLineDurationViewDto> = all_durations
-                .into_iter()
-                .map(TrackerEntryLineDurationViewDto::from)
-                .collect();
+            let line_dto = self.line_view(&mut tx, line).await?;
 
-            let mut line_dto = TrackerEntryLineViewDto::from(line);
-            line_dto.durations = duration_dtos;
+            tx.commit().await?;
 
             Ok(line_dto)
         })
     }
 
-    fn update_tracked(
+    fn switch_tracking(
         &self,
-        dto: TrackerEntryLineUpdateDto,
-    ) -> std::pin::Pin<
-        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
-    > {
+        from_line_id: i64,
+        to_line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerSwitchDto, AppError>> + Send + '_>>
+    {
         Box::pin(async move {
-            let mut line = self
+            if from_line_id == to_line_id {
+                return Err(AppError::ValidationError(
+                    "Cannot switch a line to itself".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let from_line = self
                 .repo
-                .get_entry_line(self.pool.clone(), dto.id)
+                .get_entry_line(&mut tx, from_line_id)
                 .await?
-                .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", dto.id)))?;
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", from_line_id))
+                })?;
+            let to_line = self
+                .repo
+                .get_entry_line(&mut tx, to_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", to_line_id))
+                })?;
 
-            line.desc = dto.desc;
-            line.updated_at = Utc::now();
+            let from_durations = self
+                .repo
+                .get_line_durations(&mut tx, from_line.clone())
+                .await?;
+            let Some(active_duration) = from_durations.into_iter().find(|d| d.ended_at.is_none())
+            else {
+                return Err(AppError::ValidationError(
+                    "No active duration found for this line".to_string(),
+                ));
+            };
 
-            let updated = self
+            let to_durations = self
                 .repo
-                .update_entry_line(self.pool.clone(), line.clone())
+                .get_line_durations(&mut tx, to_line.clone())
                 .await?;
+            if to_durations.iter().any(|d| d.ended_at.is_none()) {
+                return Err(AppError::ValidationError(
+                    "Line already has an active duration".to_string(),
+                ));
+            }
 
-            // Get durations for complete data
-            let durations = self
+            if self
                 .repo
-                .get_line_durations(self.pool.clone(), updated.clone())
+                .get_entry(&mut tx, to_line.entry_id)
+                .await?
+                .is_some_and(|entry| entry.is_archived)
+            {
+                return Err(archived_error(to_line.entry_id));
+            }
+
+            let entry_ids = self
+                .history
+                .with_running(&mut tx, &[from_line.entry_id, to_line.entry_id])
                 .await?;
-            let duration_dtos: Vec<TrackerEntryLineDurationViewDto> = durations
-                .into_iter()
-                .map(TrackerEntryLineDurationViewDto::from)
-                .collect();
+            let before = self.history.capture(&mut tx, entry_ids).await?;
 
-            let mut line_dto = TrackerEntryLineViewDto::from(updated);
-            line_dto.durations = duration_dtos;
+            // Both ends share one instant, so the two intervals meet exactly
+            let now = Utc::now();
 
-            Ok(line_dto)
+            let mut stopped_duration = active_duration;
+            stopped_duration.ended_at = Some(now);
+            stopped_duration.updated_at = now;
+            self.repo
+                .update_line_duration(&mut tx, stopped_duration)
+                .await?;
+
+            let mut stopped_lines = vec![from_line];
+            if self.single_active(&mut tx).await? {
+                stopped_lines.extend(
+                    self.close_open_durations(&mut tx, now, Some(to_line.id))
+                        .await?,
+                );
+            }
+
+            let duration = TrackerEntryLineDuration::new(0, to_line.id, now, None);
+            self.repo.create_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Switch tracking", before, &[])
+                .await?;
+
+            let mut stopped = Vec::with_capacity(stopped_lines.len());
+            for line in stopped_lines {
+                stopped.push(self.line_view(&mut tx, line).await?);
+            }
+            let started = self.line_view(&mut tx, to_line).await?;
+
+            tx.commit().await?;
+
+            Ok(TrackerSwitchDto { started, stopped })
         })
     }
 
-    fn remove_tracked(
+    fn get_single_active(
         &self,
-        dto: TrackerEntryLineDeleteDto,
-    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<bool, AppError>> + Send + '_>> {
         Box::pin(async move {
-            let line = self
-                .repo
-                .get_entry_line(self.pool.clone(), dto.id)
-                .await?
-                .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", dto.id)))?;
+            let mut conn = self.pool.acquire().await?;
 
-            self.repo.delete_entry_line(self.pool.clone(), line).await?;
-
-            Ok(())
+            self.single_active(&mut conn).await
         })
     }
 
-    fn delete_tracker(
+    fn set_single_active(
         &self,
-        dto: TrackerEntryDeleteDto,
-    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        enabled: bool,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<bool, AppError>> + Send + '_>> {
         Box::pin(async move {
-            let entry = self
-                .repo
-                .get_entry(self.pool.clone(), dto.id)
-                .await?
-                .ok_or_else(|| AppError::NotFound(format!("Entry with id {} not found", dto.id)))?;
+            let mut conn = self.pool.acquire().await?;
 
-            self.repo
-                .delete_lines_for_entry(self.pool.clone(), entry.clone())
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(SINGLE_ACTIVE_SETTING_KEY, enabled.to_string()),
+                )
                 .await?;
 
-            self.repo.delete_entry(self.pool.clone(), entry).await?;
+            Ok(enabled)
+        })
+    }
 
-            Ok(())
+    fn stop_all_tracking(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<TrackerEntryLineViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            // Get all trackers (which include their lines), archived ones may still be running
+            let all_trackers = self
+                .get_trackers(TrackerQueryDto {
+                    include_archived: true,
+                    ..Default::default()
+                })
+                .await?;
+
+            // Extract all lines from all trackers and find active ones
+            let active_lines: Vec<TrackerEntryLineViewDto> = all_trackers
+                .into_iter()
+                .flat_map(|tracker| tracker.lines)
+                .filter(|line| line.durations.iter().any(|d| d.ended_at.is_none()))
+                .collect();
+
+            // Stop each active line
+            let mut stopped_lines = Vec::new();
+            for line in active_lines {
+                match self.stop_tracking(line.id).await {
+                    Ok(updated_line) => {
+                        log::info!("Stopped active tracking line: {}", updated_line.id);
+                        stopped_lines.push(updated_line);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to stop tracking line {}: {}", line.id, e);
+                    }
+                }
+            }
+
+            Ok(stopped_lines)
+        })
+    }
+
+    fn add_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationCreateDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if dto.ended_at > Utc::now() {
+                return Err(AppError::ValidationError(
+                    "Manual intervals must end in the past".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, dto.entry_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", dto.entry_line_id))
+                })?;
+
+            let duration =
+                TrackerEntryLineDuration::new(0, line.id, dto.started_at, Some(dto.ended_at));
+
+            let siblings = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            duration
+                .validate(&siblings)
+                .map_err(AppError::ValidationError)?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo.create_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Add interval", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn update_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationUpdateDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let now = Utc::now();
+            if dto.started_at > now || dto.ended_at.is_some_and(|ended_at| ended_at > now) {
+                return Err(AppError::ValidationError(
+                    "Intervals cannot be moved into the future".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let mut duration = self
+                .repo
+                .get_line_duration(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Duration with id {} not found", dto.id))
+                })?;
+
+            if duration.invoice_line_id.is_some() {
+                return Err(invoiced_error(duration.id));
+            }
+
+            // Reopening a finished interval would start a second timer on the line
+            if dto.ended_at.is_none() && duration.ended_at.is_some() {
+                return Err(AppError::ValidationError(
+                    "Only a running interval can be left without an end".to_string(),
+                ));
+            }
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, duration.entry_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", duration.entry_line_id))
+                })?;
+
+            duration.started_at = dto.started_at;
+            duration.ended_at = dto.ended_at;
+            duration.updated_at = now;
+
+            let siblings = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            duration
+                .validate(&siblings)
+                .map_err(AppError::ValidationError)?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo.update_line_duration(&mut tx, duration).await?;
+            self.history
+                .record(&mut tx, "Edit interval", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn delete_line_duration(
+        &self,
+        dto: TrackerEntryLineDurationDeleteDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let duration = self
+                .repo
+                .get_line_duration(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Duration with id {} not found", dto.id))
+                })?;
+
+            if duration.invoice_line_id.is_some() {
+                return Err(invoiced_error(duration.id));
+            }
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, duration.entry_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", duration.entry_line_id))
+                })?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo
+                .delete_line_duration(&mut tx, duration, Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Delete interval", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn split_line_duration(
+        &self,
+        duration_id: i64,
+        at: DateTime<Utc>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let now = Utc::now();
+            let mut tx = self.pool.begin().await?;
+
+            let mut first = self
+                .repo
+                .get_line_duration(&mut tx, duration_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Duration with id {} not found", duration_id))
+                })?;
+            if first.invoice_line_id.is_some() {
+                return Err(invoiced_error(first.id));
+            }
+            if at <= first.started_at || at >= first.ended_at.unwrap_or(now) {
+                return Err(AppError::ValidationError(
+                    "Split point must fall inside the interval".to_string(),
+                ));
+            }
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, first.entry_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", first.entry_line_id))
+                })?;
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+
+            let second = TrackerEntryLineDuration::new(0, line.id, at, first.ended_at);
+            first.ended_at = Some(at);
+            first.updated_at = now;
+
+            let siblings = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            first
+                .validate(&siblings)
+                .map_err(AppError::ValidationError)?;
+            self.repo.update_line_duration(&mut tx, first).await?;
+
+            let siblings = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            second
+                .validate(&siblings)
+                .map_err(AppError::ValidationError)?;
+            self.repo.create_line_duration(&mut tx, second).await?;
+            self.history
+                .record(&mut tx, "Split interval", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn merge_lines(
+        &self,
+        source_line_id: i64,
+        target_line_id: i64,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            if source_line_id == target_line_id {
+                return Err(AppError::ValidationError(
+                    "A line cannot be merged into itself".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let source = self
+                .repo
+                .get_entry_line(&mut tx, source_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", source_line_id))
+                })?;
+            let target = self
+                .repo
+                .get_entry_line(&mut tx, target_line_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Line with id {} not found", target_line_id))
+                })?;
+            if source.entry_id != target.entry_id {
+                return Err(AppError::ValidationError(
+                    "Only lines of the same tracker can be merged".to_string(),
+                ));
+            }
+
+            let entry = self
+                .repo
+                .get_entry(&mut tx, target.entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", target.entry_id))
+                })?;
+            if entry.is_archived {
+                return Err(archived_error(entry.id));
+            }
+
+            let before = self.history.capture(&mut tx, vec![target.entry_id]).await?;
+            self.merge_line_into(&mut tx, source, target.clone(), Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Merge lines", before, &[])
+                .await?;
+
+            let line_dto = self.line_view(&mut tx, target).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn merge_trackers(
+        &self,
+        source_entry_id: i64,
+        target_entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            if source_entry_id == target_entry_id {
+                return Err(AppError::ValidationError(
+                    "A tracker cannot be merged into itself".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let source = self
+                .repo
+                .get_entry(&mut tx, source_entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", source_entry_id))
+                })?;
+            let target = self
+                .repo
+                .get_entry(&mut tx, target_entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", target_entry_id))
+                })?;
+            if target.is_archived {
+                return Err(archived_error(target.id));
+            }
+
+            let before = self
+                .history
+                .capture(&mut tx, vec![source.id, target.id])
+                .await?;
+
+            let now = Utc::now();
+            self.carry_over_settings(&mut tx, &source, &target, now)
+                .await?;
+
+            let target_lines = self
+                .repo
+                .get_lines_for_entry(&mut tx, target.clone())
+                .await?;
+            for mut line in self
+                .repo
+                .get_lines_for_entry(&mut tx, source.clone())
+                .await?
+            {
+                // Only the exact description merges, lines differing in case stay apart
+                let same_desc = target_lines
+                    .iter()
+                    .find(|target_line| target_line.desc == line.desc);
+
+                match same_desc {
+                    Some(target_line) => {
+                        self.merge_line_into(&mut tx, line, target_line.clone(), now)
+                            .await?;
+                    }
+                    None => {
+                        let durations = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+                        if let Some(duration) =
+                            durations.iter().find(|d| d.invoice_line_id.is_some())
+                        {
+                            return Err(invoiced_error(duration.id));
+                        }
+
+                        line.entry_id = target.id;
+                        line.updated_at = now;
+                        self.repo.update_entry_line(&mut tx, line).await?;
+                    }
+                }
+            }
+
+            self.repo.delete_entry(&mut tx, source, now).await?;
+            self.history
+                .record(&mut tx, "Merge trackers", before, &[])
+                .await?;
+
+            let entry_dto = self.entry_view(&mut tx, target).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
+        })
+    }
+
+    fn update_tracked(
+        &self,
+        dto: TrackerEntryLineUpdateDto,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<TrackerEntryLineViewDto, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let desc = dto.desc.trim().to_string();
+            if desc.is_empty() {
+                return Err(AppError::ValidationError(
+                    "Line description must not be empty".to_string(),
+                ));
+            }
+
+            let mut tx = self.pool.begin().await?;
+
+            let mut line = self
+                .repo
+                .get_entry_line(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", dto.id)))?;
+
+            // Moving the line to another tracker requires that tracker to exist and to take
+            // time, the line brings its intervals along
+            if dto.entry_id != line.entry_id {
+                let target = self
+                    .repo
+                    .get_entry(&mut tx, dto.entry_id)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("Entry with id {} not found", dto.entry_id))
+                    })?;
+                if target.is_archived {
+                    return Err(archived_error(target.id));
+                }
+            }
+
+            let before = self
+                .history
+                .capture(&mut tx, vec![line.entry_id, dto.entry_id])
+                .await?;
+
+            line.entry_id = dto.entry_id;
+            line.desc = desc;
+            line.updated_at = Utc::now();
+
+            let updated = self.repo.update_entry_line(&mut tx, line.clone()).await?;
+            self.history
+                .record(&mut tx, "Edit line", before, &[])
+                .await?;
+
+            // Get tags and durations for complete data
+            let line_dto = self.line_view(&mut tx, updated).await?;
+
+            tx.commit().await?;
+
+            Ok(line_dto)
+        })
+    }
+
+    fn remove_tracked(
+        &self,
+        dto: TrackerEntryLineDeleteDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_entry_line(&mut tx, dto.id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Line with id {} not found", dto.id)))?;
+
+            // Invoiced intervals stay where they were billed, trashing them would hide them
+            let durations = self.repo.get_line_durations(&mut tx, line.clone()).await?;
+            if let Some(duration) = durations.iter().find(|d| d.invoice_line_id.is_some()) {
+                return Err(invoiced_error(duration.id));
+            }
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+
+            // Durations share the line's deletion time so the trash can restore them together
+            let deleted_at = Utc::now();
+            self.repo
+                .delete_durations_for_line(&mut tx, line.clone(), deleted_at)
+                .await?;
+            self.repo
+                .delete_entry_line(&mut tx, line, deleted_at)
+                .await?;
+            self.history
+                .record(&mut tx, "Delete line", before, &[])
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_tracker(
+        &self,
+        dto: TrackerEntryDeleteDto,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry =
+                self.repo.get_entry(&mut tx, dto.id).await?.ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", dto.id))
+                })?;
+
+            for line in self
+                .repo
+                .get_lines_for_entry(&mut tx, entry.clone())
+                .await?
+            {
+                let durations = self.repo.get_line_durations(&mut tx, line).await?;
+                if let Some(duration) = durations.iter().find(|d| d.invoice_line_id.is_some()) {
+                    return Err(invoiced_error(duration.id));
+                }
+            }
+
+            let before = self.history.capture(&mut tx, vec![entry.id]).await?;
+
+            // Children share the tracker's deletion time so the trash can restore them together
+            let deleted_at = Utc::now();
+            self.repo
+                .delete_durations_for_entry(&mut tx, entry.clone(), deleted_at)
+                .await?;
+            self.repo
+                .delete_lines_for_entry(&mut tx, entry.clone(), deleted_at)
+                .await?;
+
+            self.repo.delete_entry(&mut tx, entry, deleted_at).await?;
+            self.history
+                .record(&mut tx, "Delete tracker", before, &[])
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn archive_tracker(
+        &self,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .repo
+                .get_entry(&mut tx, entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", entry_id))
+                })?;
+            if entry.is_archived {
+                return self.entry_view(&mut tx, entry).await;
+            }
+
+            // Nothing could stop a timer hidden from the list
+            let entry_dto = self.entry_view(&mut tx, entry).await?;
+            if entry_dto
+                .lines
+                .iter()
+                .any(|line| line.durations.iter().any(|d| d.ended_at.is_none()))
+            {
+                return Err(AppError::ValidationError(
+                    "Stop tracking on this tracker before archiving it".to_string(),
+                ));
+            }
+
+            let before = self.history.capture(&mut tx, vec![entry_id]).await?;
+            let now = Utc::now();
+            let archived = self
+                .repo
+                .set_entry_archived(&mut tx, entry_id, Some(now), now)
+                .await?;
+            self.history
+                .record(&mut tx, "Archive tracker", before, &[])
+                .await?;
+
+            let entry_dto = self.entry_view(&mut tx, archived).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
+        })
+    }
+
+    fn unarchive_tracker(
+        &self,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .repo
+                .get_entry(&mut tx, entry_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Entry with id {} not found", entry_id))
+                })?;
+            if !entry.is_archived {
+                return self.entry_view(&mut tx, entry).await;
+            }
+
+            let before = self.history.capture(&mut tx, vec![entry_id]).await?;
+            let unarchived = self
+                .repo
+                .set_entry_archived(&mut tx, entry_id, None, Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Unarchive tracker", before, &[])
+                .await?;
+
+            let entry_dto = self.entry_view(&mut tx, unarchived).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
+        })
+    }
+
+    fn reorder_trackers(
+        &self,
+        entry_ids: Vec<i64>,
+    ) -> std::pin::Pin<
+        Box<dyn Future<Output = Result<Vec<TrackerEntryViewDto>, AppError>> + Send + '_>,
+    > {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entries = self.repo.get_all_entries(&mut tx, true).await?;
+            let known: HashSet<i64> = entries.iter().map(|entry| entry.id).collect();
+
+            let mut listed = HashSet::new();
+            for entry_id in &entry_ids {
+                if !known.contains(entry_id) {
+                    return Err(AppError::NotFound(format!(
+                        "Entry with id {} not found",
+                        entry_id
+                    )));
+                }
+                if !listed.insert(*entry_id) {
+                    return Err(AppError::ValidationError(format!(
+                        "Tracker {} is listed more than once",
+                        entry_id
+                    )));
+                }
+            }
+            if let Some(missing) = entries
+                .iter()
+                .find(|entry| !entry.is_archived && !listed.contains(&entry.id))
+            {
+                return Err(AppError::ValidationError(format!(
+                    "The new order is missing tracker {}",
+                    missing.id
+                )));
+            }
+
+            // Archived trackers that were left out keep their relative order after the rest
+            let unlisted = entries
+                .iter()
+                .filter(|entry| !listed.contains(&entry.id))
+                .map(|entry| entry.id);
+            // Only the trackers that move are written, and so kept in the history
+            let sort_orders: HashMap<i64, i64> = entries
+                .iter()
+                .map(|entry| (entry.id, entry.sort_order))
+                .collect();
+            let moved: Vec<(i64, i64)> = entry_ids
+                .iter()
+                .copied()
+                .chain(unlisted)
+                .enumerate()
+                .map(|(sort_order, entry_id)| (entry_id, sort_order as i64))
+                .filter(|(entry_id, sort_order)| sort_orders.get(entry_id) != Some(sort_order))
+                .collect();
+
+            let before = self
+                .history
+                .capture(
+                    &mut tx,
+                    moved.iter().map(|(entry_id, _)| *entry_id).collect(),
+                )
+                .await?;
+
+            let now = Utc::now();
+            for (entry_id, sort_order) in moved {
+                self.repo
+                    .set_entry_sort_order(&mut tx, entry_id, sort_order, now)
+                    .await?;
+            }
+            self.history
+                .record(&mut tx, "Reorder trackers", before, &[])
+                .await?;
+
+            tx.commit().await?;
+
+            self.get_trackers(TrackerQueryDto::default()).await
+        })
+    }
+
+    fn set_tracker_pinned(
+        &self,
+        entry_id: i64,
+        is_pinned: bool,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            if self.repo.get_entry(&mut tx, entry_id).await?.is_none() {
+                return Err(AppError::NotFound(format!(
+                    "Entry with id {} not found",
+                    entry_id
+                )));
+            }
+
+            let before = self.history.capture(&mut tx, vec![entry_id]).await?;
+            let updated = self
+                .repo
+                .set_entry_pinned(&mut tx, entry_id, is_pinned, Utc::now())
+                .await?;
+            let label = if is_pinned {
+                "Pin tracker"
+            } else {
+                "Unpin tracker"
+            };
+            self.history.record(&mut tx, label, before, &[]).await?;
+
+            let entry_dto = self.entry_view(&mut tx, updated).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
+        })
+    }
+
+    fn set_tracker_color(
+        &self,
+        entry_id: i64,
+        color: Option<String>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrackerEntryViewDto, AppError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let color = color
+                .as_deref()
+                .map(validate_color)
+                .transpose()
+                .map_err(AppError::ValidationError)?;
+
+            let mut tx = self.pool.begin().await?;
+
+            if self.repo.get_entry(&mut tx, entry_id).await?.is_none() {
+                return Err(AppError::NotFound(format!(
+                    "Entry with id {} not found",
+                    entry_id
+                )));
+            }
+
+            let before = self.history.capture(&mut tx, vec![entry_id]).await?;
+            let updated = self
+                .repo
+                .set_entry_color(&mut tx, entry_id, color, Utc::now())
+                .await?;
+            self.history
+                .record(&mut tx, "Change tracker color", before, &[])
+                .await?;
+
+            let entry_dto = self.entry_view(&mut tx, updated).await?;
+
+            tx.commit().await?;
+
+            Ok(entry_dto)
         })
     }
 }
@@ -0,0 +1,21 @@
+mod domain {
+    pub mod model;
+    pub mod repository;
+    pub mod service;
+}
+
+pub mod dto {
+    pub mod trash_dto;
+}
+
+mod infra {
+    mod impl_repository;
+    pub mod impl_service;
+}
+
+// Re-export commonly used items for convenience
+pub use domain::model::{DEFAULT_RETENTION_DAYS, RETENTION_SETTING_KEY, TrashedEntry, TrashedLine};
+pub use domain::repository::TrashRepositoryTrait;
+pub use domain::service::TrashServiceTrait;
+pub use dto::trash_dto::*;
+pub use infra::impl_service::TrashService;
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Setting that holds the number of days deleted data is kept before it is purged.
+pub const RETENTION_SETTING_KEY: &str = "trash.retention_days";
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+/// A soft-deleted tracker together with the number of lines deleted along with it.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct TrashedEntry {
+    pub id: i64,
+    pub label: String,
+    pub line_count: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl fmt::Display for TrashedEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TrashedEntry(id: {}, label: {}, line_count: {}, deleted_at: {})",
+            self.id, self.label, self.line_count, self.deleted_at
+        )
+    }
+}
+
+/// A soft-deleted line whose tracker is still live.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, sqlx::FromRow)]
+pub struct TrashedLine {
+    pub id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub desc: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl fmt::Display for TrashedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TrashedLine(id: {}, entry_id: {}, desc: {}, deleted_at: {})",
+            self.id, self.entry_id, self.desc, self.deleted_at
+        )
+    }
+}
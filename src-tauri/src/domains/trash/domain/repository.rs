@@ -0,0 +1,80 @@
+use crate::domains::trash::domain::model::{TrashedEntry, TrashedLine};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Children deleted together with their parent carry the parent's `deleted_at`, which is how a
+/// restore tells them apart from rows that were deleted on their own earlier.
+pub trait TrashRepositoryTrait {
+    fn get_deleted_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrashedEntry>>> + Send + 'a>>;
+
+    fn get_deleted_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrashedEntry>>> + Send + 'a>>;
+
+    /// Returns deleted lines of live trackers, lines of deleted trackers belong to their tracker.
+    fn get_deleted_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrashedLine>>> + Send + 'a>>;
+
+    fn get_deleted_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<Option<TrashedLine>>> + Send + 'a>>;
+
+    fn restore_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrashedEntry,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    fn restore_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrashedLine,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>>;
+
+    /// Returns how many intervals of the tracker have been invoiced, trashed ones included.
+    fn count_invoiced_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>>;
+
+    /// Returns how many intervals of the line have been invoiced, trashed ones included.
+    fn count_invoiced_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>>;
+
+    /// Hard-deletes the tracker with all of its lines and durations, returns the rows removed.
+    fn purge_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrashedEntry,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>>;
+
+    /// Hard-deletes the line with all of its durations, returns the rows removed.
+    fn purge_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrashedLine,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>>;
+
+    /// Hard-deletes every row deleted before `cutoff` together with its children. Lines with
+    /// invoiced intervals, and the trackers holding them, are kept.
+    fn purge_deleted_before<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        cutoff: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>>;
+}
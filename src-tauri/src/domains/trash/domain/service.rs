@@ -0,0 +1,43 @@
+use crate::{domains::trash::dto::trash_dto::TrashDto, error::AppError};
+use sqlx::SqlitePool;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+pub trait TrashServiceTrait: Send + Sync {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TrashServiceTrait>
+    where
+        Self: Sized;
+
+    fn get_trash(&self) -> Pin<Box<dyn Future<Output = Result<TrashDto, AppError>> + Send + '_>>;
+
+    fn restore_tracker(
+        &self,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    fn restore_line(
+        &self,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    fn purge_tracker(
+        &self,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    fn purge_line(
+        &self,
+        id: i64,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>>;
+
+    /// Purges everything older than the retention period, returns the number of rows removed.
+    fn purge_expired(&self) -> Pin<Box<dyn Future<Output = Result<u64, AppError>> + Send + '_>>;
+
+    fn get_retention_days(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+
+    fn set_retention_days(
+        &self,
+        days: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>>;
+}
@@ -0,0 +1,54 @@
+use crate::domains::trash::{TrashedEntry, TrashedLine};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashTrackerDto {
+    pub id: i64,
+    pub label: String,
+    pub line_count: i64,
+    pub deleted_at: DateTime<Utc>,
+    pub purge_at: DateTime<Utc>,
+}
+
+impl TrashTrackerDto {
+    pub fn new(entry: TrashedEntry, retention: TimeDelta) -> Self {
+        Self {
+            id: entry.id,
+            label: entry.label,
+            line_count: entry.line_count,
+            deleted_at: entry.deleted_at,
+            purge_at: entry.deleted_at + retention,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashLineDto {
+    pub id: i64,
+    pub entry_id: i64,
+    pub entry_label: String,
+    pub desc: String,
+    pub deleted_at: DateTime<Utc>,
+    pub purge_at: DateTime<Utc>,
+}
+
+impl TrashLineDto {
+    pub fn new(line: TrashedLine, retention: TimeDelta) -> Self {
+        Self {
+            id: line.id,
+            entry_id: line.entry_id,
+            entry_label: line.entry_label,
+            desc: line.desc,
+            deleted_at: line.deleted_at,
+            purge_at: line.deleted_at + retention,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashDto {
+    pub retention_days: u32,
+    pub trackers: Vec<TrashTrackerDto>,
+    pub lines: Vec<TrashLineDto>,
+}
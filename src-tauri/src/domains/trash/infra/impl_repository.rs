@@ -0,0 +1,385 @@
+use crate::domains::trash::{
+    TrashRepositoryTrait,
+    domain::model::{TrashedEntry, TrashedLine},
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use std::future::Future;
+
+pub struct TrashRepository;
+
+impl TrashRepositoryTrait for TrashRepository {
+    fn get_deleted_entries<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrashedEntry>>> + Send + 'a>> {
+        Box::pin(async move {
+            // julianday() normalises the stored formats, the tolerance absorbs its rounding
+            let entries = sqlx::query_as::<_, TrashedEntry>(
+                r#"
+                SELECT e.id, e.label, e.deleted_at,
+                    (SELECT count(*) FROM tracker_entry_line l
+                        WHERE l.entry_id = e.id AND l.is_deleted = 1
+                            AND abs(julianday(l.deleted_at) - julianday(e.deleted_at)) * 86400000 < 1
+                    ) AS line_count
+                FROM tracker_entry e
+                WHERE e.is_deleted = 1 AND e.deleted_at IS NOT NULL
+                ORDER BY julianday(e.deleted_at) DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(entries)
+        })
+    }
+
+    fn get_deleted_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrashedEntry>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let entry = sqlx::query_as::<_, TrashedEntry>(
+                r#"
+                SELECT e.id, e.label, e.deleted_at,
+                    (SELECT count(*) FROM tracker_entry_line l
+                        WHERE l.entry_id = e.id AND l.is_deleted = 1
+                            AND abs(julianday(l.deleted_at) - julianday(e.deleted_at)) * 86400000 < 1
+                    ) AS line_count
+                FROM tracker_entry e
+                WHERE e.id = ? AND e.is_deleted = 1 AND e.deleted_at IS NOT NULL
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(entry)
+        })
+    }
+
+    fn get_deleted_lines<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Vec<TrashedLine>>> + Send + 'a>> {
+        Box::pin(async move {
+            let lines = sqlx::query_as::<_, TrashedLine>(
+                r#"
+                SELECT l.id, l.entry_id, e.label AS entry_label, l.desc, l.deleted_at
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE l.is_deleted = 1 AND l.deleted_at IS NOT NULL AND e.is_deleted = 0
+                ORDER BY julianday(l.deleted_at) DESC
+                "#,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            Ok(lines)
+        })
+    }
+
+    fn get_deleted_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<Option<TrashedLine>>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let line = sqlx::query_as::<_, TrashedLine>(
+                r#"
+                SELECT l.id, l.entry_id, e.label AS entry_label, l.desc, l.deleted_at
+                FROM tracker_entry_line l
+                JOIN tracker_entry e ON e.id = l.entry_id
+                WHERE l.id = ? AND l.is_deleted = 1 AND l.deleted_at IS NOT NULL
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            Ok(line)
+        })
+    }
+
+    fn restore_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrashedEntry,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line_duration
+                SET is_deleted = 0, deleted_at = NULL
+                WHERE is_deleted = 1
+                    AND abs(julianday(deleted_at) - julianday(?)) * 86400000 < 1
+                    AND entry_line_id IN (SELECT id FROM tracker_entry_line WHERE entry_id = ?)
+                "#,
+            )
+            .bind(entry.deleted_at)
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line
+                SET is_deleted = 0, deleted_at = NULL
+                WHERE entry_id = ? AND is_deleted = 1
+                    AND abs(julianday(deleted_at) - julianday(?)) * 86400000 < 1
+                "#,
+            )
+            .bind(entry.id)
+            .bind(entry.deleted_at)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry
+                SET is_deleted = 0, deleted_at = NULL
+                WHERE id = ? AND is_deleted = 1
+                "#,
+            )
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn restore_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrashedLine,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line_duration
+                SET is_deleted = 0, deleted_at = NULL
+                WHERE entry_line_id = ? AND is_deleted = 1
+                    AND abs(julianday(deleted_at) - julianday(?)) * 86400000 < 1
+                "#,
+            )
+            .bind(line.id)
+            .bind(line.deleted_at)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE tracker_entry_line
+                SET is_deleted = 0, deleted_at = NULL
+                WHERE id = ? AND is_deleted = 1
+                "#,
+            )
+            .bind(line.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn count_invoiced_for_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>> {
+        Box::pin(async move {
+            let count = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT count(*)
+                FROM tracker_entry_line_duration d
+                JOIN tracker_entry_line l ON l.id = d.entry_line_id
+                WHERE l.entry_id = ? AND d.invoice_line_id IS NOT NULL
+                "#,
+            )
+            .bind(entry_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(count)
+        })
+    }
+
+    fn count_invoiced_for_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line_id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<i64>> + Send + 'a>> {
+        Box::pin(async move {
+            let count = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT count(*)
+                FROM tracker_entry_line_duration
+                WHERE entry_line_id = ? AND invoice_line_id IS NOT NULL
+                "#,
+            )
+            .bind(line_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(count)
+        })
+    }
+
+    fn purge_entry<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        entry: TrashedEntry,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>> {
+        Box::pin(async move {
+            // Children go first because of the foreign key constraints
+            let durations = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_duration
+                WHERE entry_line_id IN (SELECT id FROM tracker_entry_line WHERE entry_id = ?)
+                "#,
+            )
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            let lines = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line
+                WHERE entry_id = ?
+                "#,
+            )
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            let entries = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry
+                WHERE id = ? AND is_deleted = 1
+                "#,
+            )
+            .bind(entry.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(durations.rows_affected() + lines.rows_affected() + entries.rows_affected())
+        })
+    }
+
+    fn purge_line<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        line: TrashedLine,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>> {
+        Box::pin(async move {
+            let durations = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_duration
+                WHERE entry_line_id = ?
+                "#,
+            )
+            .bind(line.id)
+            .execute(&mut *conn)
+            .await?;
+
+            let lines = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line
+                WHERE id = ? AND is_deleted = 1
+                "#,
+            )
+            .bind(line.id)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(durations.rows_affected() + lines.rows_affected())
+        })
+    }
+
+    fn purge_deleted_before<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        cutoff: DateTime<Utc>,
+    ) -> std::pin::Pin<Box<dyn Future<Output = sqlx::Result<u64>> + Send + 'a>> {
+        Box::pin(async move {
+            // Invoiced intervals stay, and so do their whole line and tracker, rather than
+            // leaving a tracker behind with only some of its lines
+            let durations = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line_duration
+                WHERE invoice_line_id IS NULL
+                    AND ((is_deleted = 1 AND julianday(deleted_at) < julianday(?))
+                        OR entry_line_id IN (
+                            SELECT id FROM tracker_entry_line
+                            WHERE ((is_deleted = 1 AND julianday(deleted_at) < julianday(?))
+                                    OR entry_id IN (
+                                        SELECT id FROM tracker_entry
+                                        WHERE is_deleted = 1
+                                            AND julianday(deleted_at) < julianday(?)
+                                            AND id NOT IN (
+                                                SELECT l.entry_id FROM tracker_entry_line l
+                                                JOIN tracker_entry_line_duration d
+                                                    ON d.entry_line_id = l.id
+                                                WHERE d.invoice_line_id IS NOT NULL
+                                            )
+                                    ))
+                                AND id NOT IN (
+                                    SELECT entry_line_id FROM tracker_entry_line_duration
+                                    WHERE invoice_line_id IS NOT NULL
+                                )
+                        ))
+                "#,
+            )
+            .bind(cutoff)
+            .bind(cutoff)
+            .bind(cutoff)
+            .execute(&mut *conn)
+            .await?;
+
+            let lines = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry_line
+                WHERE ((is_deleted = 1 AND julianday(deleted_at) < julianday(?))
+                        OR entry_id IN (
+                            SELECT id FROM tracker_entry
+                            WHERE is_deleted = 1 AND julianday(deleted_at) < julianday(?)
+                                AND id NOT IN (
+                                    SELECT l.entry_id FROM tracker_entry_line l
+                                    JOIN tracker_entry_line_duration d ON d.entry_line_id = l.id
+                                    WHERE d.invoice_line_id IS NOT NULL
+                                )
+                        ))
+                    AND id NOT IN (
+                        SELECT entry_line_id FROM tracker_entry_line_duration
+                        WHERE invoice_line_id IS NOT NULL
+                    )
+                "#,
+            )
+            .bind(cutoff)
+            .bind(cutoff)
+            .execute(&mut *conn)
+            .await?;
+
+            let entries = sqlx::query(
+                r#"
+                DELETE FROM tracker_entry
+                WHERE is_deleted = 1 AND julianday(deleted_at) < julianday(?)
+                    AND id NOT IN (
+                        SELECT l.entry_id FROM tracker_entry_line l
+                        JOIN tracker_entry_line_duration d ON d.entry_line_id = l.id
+                        WHERE d.invoice_line_id IS NOT NULL
+                    )
+                "#,
+            )
+            .bind(cutoff)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(durations.rows_affected() + lines.rows_affected() + entries.rows_affected())
+        })
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    domains::{
+        history::HistoryRecorder,
+        settings::{AppSetting, SettingsRepository, SettingsRepositoryTrait},
+        tracker::{TrackerRepository, TrackerRepositoryTrait},
+        trash::{
+            DEFAULT_RETENTION_DAYS, RETENTION_SETTING_KEY, TrashRepositoryTrait, TrashServiceTrait,
+            dto::trash_dto::{TrashDto, TrashLineDto, TrashTrackerDto},
+            infra::impl_repository::TrashRepository,
+        },
+    },
+    error::AppError,
+};
+use chrono::{TimeDelta, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::{future::Future, sync::Arc};
+
+/// Upper bound for the retention period, roughly ten years.
+const MAX_RETENTION_DAYS: u32 = 3650;
+
+pub struct TrashService {
+    pool: SqlitePool,
+    repo: Arc<dyn TrashRepositoryTrait + Send + Sync>,
+    tracker_repo: Arc<dyn TrackerRepositoryTrait + Send + Sync>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait + Send + Sync>,
+    history: HistoryRecorder,
+}
+
+impl TrashService {
+    async fn retention_days(&self, conn: &mut SqliteConnection) -> Result<u32, AppError> {
+        let Some(setting) = self
+            .settings_repo
+            .get_setting(conn, RETENTION_SETTING_KEY)
+            .await?
+        else {
+            return Ok(DEFAULT_RETENTION_DAYS);
+        };
+
+        Ok(setting.value.parse().unwrap_or_else(|_| {
+            log::warn!(
+                "Invalid trash retention '{}', using {} days",
+                setting.value,
+                DEFAULT_RETENTION_DAYS
+            );
+            DEFAULT_RETENTION_DAYS
+        }))
+    }
+}
+
+impl TrashServiceTrait for TrashService {
+    fn create_service(pool: SqlitePool) -> Arc<dyn TrashServiceTrait>
+    where
+        Self: Sized,
+    {
+        Arc::new(Self {
+            pool,
+            repo: Arc::new(TrashRepository {}),
+            tracker_repo: Arc::new(TrackerRepository {}),
+            settings_repo: Arc::new(SettingsRepository {}),
+            history: HistoryRecorder::default(),
+        })
+    }
+
+    fn get_trash(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TrashDto, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            let retention_days = self.retention_days(&mut conn).await?;
+            let retention = TimeDelta::days(retention_days.into());
+
+            let trackers = self
+                .repo
+                .get_deleted_entries(&mut conn)
+                .await?
+                .into_iter()
+                .map(|entry| TrashTrackerDto::new(entry, retention))
+                .collect();
+            let lines = self
+                .repo
+                .get_deleted_lines(&mut conn)
+                .await?
+                .into_iter()
+                .map(|line| TrashLineDto::new(line, retention))
+                .collect();
+
+            Ok(TrashDto {
+                retention_days,
+                trackers,
+                lines,
+            })
+        })
+    }
+
+    fn restore_tracker(
+        &self,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .repo
+                .get_deleted_entry(&mut tx, id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Deleted entry with id {} not found", id))
+                })?;
+
+            let before = self.history.capture(&mut tx, vec![entry.id]).await?;
+            self.repo.restore_entry(&mut tx, entry).await?;
+            self.history
+                .record(&mut tx, "Restore tracker", before, &[])
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn restore_line(
+        &self,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_deleted_line(&mut tx, id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Deleted line with id {} not found", id))
+                })?;
+
+            if self
+                .tracker_repo
+                .get_entry(&mut tx, line.entry_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Tracker '{}' is deleted, restore it first",
+                    line.entry_label
+                )));
+            }
+
+            let before = self.history.capture(&mut tx, vec![line.entry_id]).await?;
+            self.repo.restore_line(&mut tx, line).await?;
+            self.history
+                .record(&mut tx, "Restore line", before, &[])
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn purge_tracker(
+        &self,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let entry = self
+                .repo
+                .get_deleted_entry(&mut tx, id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Deleted entry with id {} not found", id))
+                })?;
+
+            // Invoices point at the intervals they billed, those are kept for good
+            if self
+                .repo
+                .count_invoiced_for_entry(&mut tx, entry.id)
+                .await?
+                > 0
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Tracker '{}' has invoiced time and cannot be purged",
+                    entry.label
+                )));
+            }
+
+            self.repo.purge_entry(&mut tx, entry).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn purge_line(
+        &self,
+        id: i64,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<(), AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let line = self
+                .repo
+                .get_deleted_line(&mut tx, id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Deleted line with id {} not found", id))
+                })?;
+
+            if self.repo.count_invoiced_for_line(&mut tx, line.id).await? > 0 {
+                return Err(AppError::ValidationError(format!(
+                    "Line '{}' has invoiced time and cannot be purged",
+                    line.desc
+                )));
+            }
+
+            self.repo.purge_line(&mut tx, line).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn purge_expired(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u64, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            let retention_days = self.retention_days(&mut tx).await?;
+            let cutoff = Utc::now() - TimeDelta::days(retention_days.into());
+
+            let purged = self.repo.purge_deleted_before(&mut tx, cutoff).await?;
+
+            tx.commit().await?;
+
+            Ok(purged)
+        })
+    }
+
+    fn get_retention_days(
+        &self,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.pool.acquire().await?;
+
+            self.retention_days(&mut conn).await
+        })
+    }
+
+    fn set_retention_days(
+        &self,
+        days: u32,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<u32, AppError>> + Send + '_>> {
+        Box::pin(async move {
+            if !(1..=MAX_RETENTION_DAYS).contains(&days) {
+                return Err(AppError::ValidationError(format!(
+                    "Retention must be between 1 and {} days",
+                    MAX_RETENTION_DAYS
+                )));
+            }
+
+            let mut conn = self.pool.acquire().await?;
+
+            self.settings_repo
+                .set_setting(
+                    &mut conn,
+                    AppSetting::new(RETENTION_SETTING_KEY, days.to_string()),
+                )
+                .await?;
+
+            Ok(days)
+        })
+    }
+}
@@ -10,4 +10,10 @@ pub enum AppError {
 
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("File error: {0}")]
+    FileError(#[from] std::io::Error),
 }
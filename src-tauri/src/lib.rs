@@ -1,11 +1,29 @@
 mod app;
-mod database;
-mod domains;
-mod error;
+pub mod database;
+pub mod domains;
+pub mod error;
 
 use app::{
-    AppState, create_tracker, delete_tracker, delete_tracker_line, get_trackers, initialize_app,
-    resume_tracking, start_tracking, stop_all_active_tracking, stop_tracking, truncate_tables,
+    AppState, add_line_duration, archive_tracker, cancel_timebox, create_client,
+    create_custom_field, create_invoice, create_tag, create_tracker, delete_client,
+    delete_custom_field, delete_line_duration, delete_tag, delete_tracker, delete_tracker_line,
+    export_invoice, export_tracked_time, get_backup_keep_count, get_client_rates, get_clients,
+    get_custom_fields, get_forced_stops, get_history, get_idle_threshold, get_invoice,
+    get_invoices, get_lines_by_tags, get_pending_idle, get_period_totals, get_recovery_cases,
+    get_recovery_policy, get_report, get_single_active_tracking, get_stop_rules, get_tag_totals,
+    get_tags, get_timebox, get_timebox_breaks, get_tracker_budget, get_tracker_budgets,
+    get_tracker_totals, get_trackers, get_trackers_by_client, get_trash, get_trash_retention,
+    import_tracked_time, initialize_app, list_backups, merge_tags, merge_tracker_lines,
+    merge_trackers, pick_import_file, preview_invoice, purge_expired_trash, purge_tracker,
+    purge_tracker_line, redo, rename_tag, reorder_trackers, resolve_idle, resolve_recovery_case,
+    restore_backup, restore_tracker, restore_tracker_line, resume_tracking, set_backup_keep_count,
+    set_client_rate, set_custom_field_value, set_idle_threshold, set_line_billing,
+    set_line_estimate, set_line_tags, set_recovery_policy, set_single_active_tracking,
+    set_stop_rules, set_tracker_budget, set_tracker_color, set_tracker_pinned, set_tracker_rate,
+    set_trash_retention, split_line_duration, start_timebox, start_tracking,
+    stop_all_active_tracking, stop_tracking, switch_tracking, truncate_tables, unarchive_tracker,
+    undo, update_client, update_custom_field, update_line_duration, update_tracker,
+    update_tracker_line,
 };
 use tauri::Manager;
 
@@ -22,13 +40,96 @@ pub fn run() {
             initialize_app,
             get_trackers,
             create_tracker,
+            update_tracker,
             start_tracking,
             stop_tracking,
             resume_tracking,
             delete_tracker,
+            archive_tracker,
+            unarchive_tracker,
+            reorder_trackers,
+            set_tracker_pinned,
+            set_tracker_color,
+            update_tracker_line,
             delete_tracker_line,
+            add_line_duration,
+            update_line_duration,
+            delete_line_duration,
             truncate_tables,
-            stop_all_active_tracking
+            stop_all_active_tracking,
+            get_report,
+            get_period_totals,
+            get_tracker_totals,
+            export_tracked_time,
+            pick_import_file,
+            import_tracked_time,
+            get_trash,
+            restore_tracker,
+            restore_tracker_line,
+            purge_tracker,
+            purge_tracker_line,
+            purge_expired_trash,
+            get_trash_retention,
+            set_trash_retention,
+            get_recovery_cases,
+            resolve_recovery_case,
+            get_recovery_policy,
+            set_recovery_policy,
+            get_tag_totals,
+            get_lines_by_tags,
+            get_tags,
+            create_tag,
+            rename_tag,
+            merge_tags,
+            delete_tag,
+            set_line_tags,
+            get_trackers_by_client,
+            get_clients,
+            create_client,
+            update_client,
+            delete_client,
+            get_client_rates,
+            set_client_rate,
+            set_tracker_rate,
+            set_line_billing,
+            preview_invoice,
+            create_invoice,
+            get_invoices,
+            get_invoice,
+            export_invoice,
+            get_tracker_budgets,
+            get_tracker_budget,
+            set_tracker_budget,
+            set_line_estimate,
+            get_custom_fields,
+            create_custom_field,
+            update_custom_field,
+            delete_custom_field,
+            set_custom_field_value,
+            switch_tracking,
+            get_single_active_tracking,
+            set_single_active_tracking,
+            start_timebox,
+            get_timebox,
+            cancel_timebox,
+            get_timebox_breaks,
+            get_pending_idle,
+            resolve_idle,
+            get_idle_threshold,
+            set_idle_threshold,
+            get_stop_rules,
+            set_stop_rules,
+            get_forced_stops,
+            split_line_duration,
+            merge_tracker_lines,
+            merge_trackers,
+            undo,
+            redo,
+            get_history,
+            list_backups,
+            restore_backup,
+            get_backup_keep_count,
+            set_backup_keep_count
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")